@@ -1,6 +1,10 @@
 use crate::common::state::{AppState, MaskHistoryItem};
 use crate::common::errors::{AppError, AppResult};
+use crate::infra::config::loader::ConfigLoader;
+use crate::infra::fs::batch::{self, BatchOptions, BatchSummary};
 use crate::infra::fs::processor;
+use crate::infra::fs::scan_report::{ScanExportFormat, ScanReport, DEFAULT_MAX_SAMPLES};
+use crate::infra::jobs::Job;
 use tauri::{AppHandle, Emitter, State, Manager};
 use serde::Serialize;
 use log::info;
@@ -29,29 +33,17 @@ pub async fn process_file_gui(
     // 1. 校验并规范化输入路径（防任意路径写入 / 不存在 / 非文件）
     let input = validate_input_path(&input_path)?;
 
-    // 2. 派生输出路径（stem 已由 file_name 拆分，天然不含路径分隔符）
-    let stem = input
-        .file_stem()
-        .and_then(|s| s.to_str())
-        .unwrap_or("output");
-    let ext = input
-        .extension()
-        .and_then(|s| s.to_str())
-        .unwrap_or("log");
-    let parent = input.parent().ok_or_else(|| {
-        AppError::Config(format!("无法获取父目录: {}", input.display()))
-    })?;
-
-    // PDF 输出为纯文本
-    let target_ext = if ext.eq_ignore_ascii_case("pdf") { "txt" } else { ext };
-    let output_path = parent.join(format!("{}.masked.{}", stem, target_ext));
+    // 2. 派生输出路径
+    let output_path = derive_output_path(&input)?;
+    let parent = output_path.parent().unwrap_or(&input).to_path_buf();
     let output_path_str = output_path.to_string_lossy().to_string();
 
-    // 3. 获取引擎快照（读锁快进快出，避免跨 await 持锁）
+    // 3. 获取引擎与处理选项快照（读锁快进快出，避免跨 await 持锁）
     let engine_snapshot = {
         let guard = state.engine.read();
         guard.clone()
     };
+    let process_options = state.settings.read().process_options();
 
     // 4. 后台执行文件处理
     let app_clone = app.clone();
@@ -62,6 +54,7 @@ pub async fn process_file_gui(
             &input_for_thread,
             &output_for_thread,
             &engine_snapshot,
+            &process_options,
             move |progress| {
                 let _ = app_clone.emit(
                     "file-progress",
@@ -107,6 +100,238 @@ pub async fn process_file_gui(
     })
 }
 
+/// 扫描文件（只检测，不写脱敏结果）
+///
+/// 多个文件依次扫描并合并为一份报告；进度通过 `scan-progress` 事件推送。
+/// 完整报告（含超出样本上限的命中）保留在内存中，供 [`export_scan_report`] 导出。
+#[tauri::command]
+pub async fn scan_files_gui(
+    app: AppHandle,
+    state: State<'_, AppState>,
+    input_paths: Vec<String>,
+    max_samples: Option<usize>,
+) -> AppResult<ScanReport> {
+    let inputs = input_paths
+        .iter()
+        .map(|p| validate_input_path(p))
+        .collect::<AppResult<Vec<_>>>()?;
+    let max_samples = max_samples.unwrap_or(DEFAULT_MAX_SAMPLES);
+
+    let engine_snapshot = {
+        let guard = state.engine.read();
+        guard.clone()
+    };
+    let process_options = state.settings.read().process_options();
+
+    let report = tauri::async_runtime::spawn_blocking(move || {
+        let mut report = ScanReport::new(max_samples);
+        for input in &inputs {
+            let file = input.to_string_lossy().to_string();
+            let file_report = processor::scan_file(input, &engine_snapshot, &process_options, max_samples, |progress| {
+                let _ = app.emit(
+                    "scan-progress",
+                    serde_json::json!({ "file": file, "percentage": progress * 100.0 }),
+                );
+            })?;
+            report.merge(file_report);
+        }
+        Ok::<_, anyhow::Error>(report)
+    })
+    .await
+    .map_err(|e| AppError::Internal(format!("Runtime Error: {}", e)))?
+    .map_err(|e| AppError::Internal(format!("Scan Error: {:#}", e)))?;
+
+    *state.last_scan_report.lock() = Some(report.clone());
+    Ok(report)
+}
+
+/// 扫描目录（仅检测，不写输出文件）
+///
+/// 过滤选项与批量脱敏相同；每完成一个文件发送 `scan-progress` 事件。
+/// 完整报告同样保留在内存中，供 [`export_scan_report`] 导出。
+#[tauri::command]
+pub async fn scan_directory_gui(
+    app: AppHandle,
+    state: State<'_, AppState>,
+    input_dir: String,
+    options: Option<BatchOptions>,
+    max_samples: Option<usize>,
+) -> AppResult<ScanReport> {
+    let input = validate_input_dir(&input_dir)?;
+    let options = options.unwrap_or_default();
+    let max_samples = max_samples.unwrap_or(DEFAULT_MAX_SAMPLES);
+
+    let engine_snapshot = {
+        let guard = state.engine.read();
+        guard.clone()
+    };
+    let process_options = state.settings.read().process_options();
+
+    let report = tauri::async_runtime::spawn_blocking(move || {
+        batch::scan_directory(&input, &options, &engine_snapshot, &process_options, max_samples, move |progress| {
+            let _ = app.emit("scan-progress", progress);
+        })
+    })
+    .await
+    .map_err(|e| AppError::Internal(format!("Runtime Error: {}", e)))?
+    .map_err(|e| AppError::Internal(format!("Scan Error: {:#}", e)))?;
+
+    *state.last_scan_report.lock() = Some(report.clone());
+    Ok(report)
+}
+
+/// 导出最近一次扫描的报告（`format`: json / csv / html / sarif）
+///
+/// 报告取自内存中的完整结果：SARIF 包含超出样本上限的全部命中，指纹使用本机指纹密钥；
+/// 规则元数据（组、优先级、掩码）取自当前规则配置。
+#[tauri::command]
+pub async fn export_scan_report(
+    app: AppHandle,
+    state: State<'_, AppState>,
+    format: String,
+    output_path: String,
+) -> AppResult<()> {
+    let format = ScanExportFormat::parse(&format)
+        .ok_or_else(|| AppError::Config(format!("不支持的报告格式: {}", format)))?;
+    let rules = if format == ScanExportFormat::Sarif {
+        ConfigLoader::load_all_rules(&app)
+    } else {
+        Vec::new()
+    };
+    let content = {
+        let guard = state.last_scan_report.lock();
+        let report = guard
+            .as_ref()
+            .ok_or_else(|| AppError::Config("没有可导出的扫描报告，请先执行扫描".into()))?;
+        report
+            .render(format, &rules, &state.fingerprint_key)
+            .map_err(|e| AppError::Internal(format!("报告渲染失败: {}", e)))?
+    };
+    std::fs::write(&output_path, content.as_bytes())?;
+    Ok(())
+}
+
+/// 提交后台文件脱敏任务
+///
+/// 立即返回任务信息；进度与状态变化通过 `job-update` 事件推送。
+#[tauri::command]
+pub async fn enqueue_file_job(
+    state: State<'_, AppState>,
+    input_path: String,
+    max_retries: Option<u32>,
+) -> AppResult<Job> {
+    let input = validate_input_path(&input_path)?;
+    let output = derive_output_path(&input)?;
+    Ok(state.jobs.submit(&input, &output, max_retries.unwrap_or(0)))
+}
+
+/// 列出任务（最新提交的在前，含已持久化的历史）
+#[tauri::command]
+pub async fn list_jobs(state: State<'_, AppState>) -> AppResult<Vec<Job>> {
+    Ok(state.jobs.list())
+}
+
+/// 取消排队中或运行中的任务
+#[tauri::command]
+pub async fn cancel_job(state: State<'_, AppState>, id: String) -> AppResult<Job> {
+    state.jobs.cancel(&id)
+}
+
+/// 将失败、取消或已完成的任务重新入队
+#[tauri::command]
+pub async fn requeue_job(state: State<'_, AppState>, id: String) -> AppResult<Job> {
+    state.jobs.requeue(&id)
+}
+
+/// 批量脱敏目录
+///
+/// 递归处理 `input_dir` 下的文件，镜像目录结构写入 `output_dir`
+/// （为空时使用同级的 `<目录名>.masked`）。每完成一个文件发送 `batch-progress` 事件。
+#[tauri::command]
+pub async fn process_directory_gui(
+    app: AppHandle,
+    state: State<'_, AppState>,
+    input_dir: String,
+    output_dir: Option<String>,
+    options: Option<BatchOptions>,
+) -> AppResult<BatchSummary> {
+    let input = validate_input_dir(&input_dir)?;
+    let output = match output_dir.filter(|s| !s.trim().is_empty()) {
+        Some(dir) => PathBuf::from(dir),
+        None => {
+            let name = input
+                .file_name()
+                .and_then(|s| s.to_str())
+                .unwrap_or("output");
+            let parent = input.parent().ok_or_else(|| {
+                AppError::Config(format!("无法获取父目录: {}", input.display()))
+            })?;
+            parent.join(format!("{}.masked", name))
+        }
+    };
+    let options = options.unwrap_or_default();
+
+    // 引擎与处理选项快照：整个批处理共享，期间规则热更新或修改设置不影响本次任务
+    let engine_snapshot = {
+        let guard = state.engine.read();
+        guard.clone()
+    };
+    let process_options = state.settings.read().process_options();
+
+    let app_clone = app.clone();
+    let summary = tauri::async_runtime::spawn_blocking(move || {
+        batch::process_directory(&input, &output, &options, &engine_snapshot, &process_options, move |progress| {
+            let _ = app_clone.emit("batch-progress", progress);
+        })
+    })
+    .await
+    .map_err(|e| AppError::Internal(format!("Runtime Error: {}", e)))?
+    .map_err(|e| AppError::Internal(format!("Batch Error: {:#}", e)))?;
+
+    info!(
+        "📁 批量脱敏完成: 处理 {} / 跳过 {} / 失败 {}，耗时 {:.2}s",
+        summary.processed, summary.skipped, summary.failed, summary.duration_secs
+    );
+    Ok(summary)
+}
+
+/// 派生输出路径：同目录下的 `<stem>.masked.<ext>`，PDF 输出为纯文本
+///
+/// stem 已由 file_name 拆分，天然不含路径分隔符。
+fn derive_output_path(input: &Path) -> AppResult<PathBuf> {
+    let stem = input
+        .file_stem()
+        .and_then(|s| s.to_str())
+        .unwrap_or("output");
+    let ext = input
+        .extension()
+        .and_then(|s| s.to_str())
+        .unwrap_or("log");
+    let parent = input.parent().ok_or_else(|| {
+        AppError::Config(format!("无法获取父目录: {}", input.display()))
+    })?;
+
+    let target_ext = if ext.eq_ignore_ascii_case("pdf") { "txt" } else { ext };
+    Ok(parent.join(format!("{}.masked.{}", stem, target_ext)))
+}
+
+/// 校验输入目录并返回规范化的绝对路径
+fn validate_input_dir(raw: &str) -> AppResult<PathBuf> {
+    if raw.trim().is_empty() {
+        return Err(AppError::Config("输入路径为空".into()));
+    }
+    let canonical = Path::new(raw)
+        .canonicalize()
+        .map_err(|e| AppError::Config(format!("无法解析路径 {}: {}", raw, e)))?;
+    if !canonical.is_dir() {
+        return Err(AppError::Config(format!(
+            "不是目录: {}",
+            canonical.display()
+        )));
+    }
+    Ok(canonical)
+}
+
 /// 校验输入路径并返回规范化的绝对路径
 ///
 /// 拒绝：空路径、不存在、非常规文件。规范化会跟随 symlink 并解析 `.`/`..`，
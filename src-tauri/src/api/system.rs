@@ -1,6 +1,8 @@
 use crate::common::state::{AppState, MaskHistoryItem};
 use crate::common::errors::AppResult;
 use crate::core::rules::Rule;
+use crate::core::allowlist::{Allowlist, AllowlistEntry};
+use crate::core::recognizer::{DictionaryBuildReport, DictionarySpec, EdmColumnSpec, EdmIndex, MappedDictionary};
 use crate::core::hybrid_engine::HybridEngine;
 use crate::infra::config::loader::ConfigLoader;
 // 🚀 核心修复：必须引入 Emitter 才能使用 .emit() 方法
@@ -43,13 +45,151 @@ pub async fn delete_rule_api(app: AppHandle, state: State<'_, AppState>, name: S
     Ok("规则已删除".into())
 }
 
+/// 获取白名单条目
+#[tauri::command]
+pub async fn get_allowlist(app: AppHandle) -> AppResult<Vec<AllowlistEntry>> {
+    Ok(ConfigLoader::load_allowlist(&app))
+}
+
+/// 保存或更新白名单条目，返回条目 ID
+#[tauri::command]
+pub async fn save_allowlist_entry(app: AppHandle, state: State<'_, AppState>, entry: AllowlistEntry) -> AppResult<String> {
+    let id = ConfigLoader::save_allowlist_entry(&app, entry)?;
+    reload_engine_internal(app, state).await?;
+    Ok(id)
+}
+
+/// 删除白名单条目
+#[tauri::command]
+pub async fn delete_allowlist_entry(app: AppHandle, state: State<'_, AppState>, id: String) -> AppResult<String> {
+    ConfigLoader::delete_allowlist_entry(&app, &id)?;
+    reload_engine_internal(app, state).await?;
+    Ok("白名单条目已删除".into())
+}
+
+/// 从 CSV 构建 EDM 精确匹配索引（加盐哈希，不保存明文）并立即生效
+#[tauri::command]
+pub async fn build_edm_index(
+    app: AppHandle,
+    state: State<'_, AppState>,
+    csv_path: String,
+    columns: Vec<EdmColumnSpec>,
+) -> AppResult<serde_json::Value> {
+    let path = ConfigLoader::edm_index_path(&app);
+    let index_path = path.clone();
+    // 大 CSV 的读取、哈希与落盘都是阻塞操作，放到阻塞线程池，避免占住异步运行时
+    let index = tauri::async_runtime::spawn_blocking(move || -> AppResult<EdmIndex> {
+        let file = std::fs::File::open(&csv_path)?;
+        let index = EdmIndex::build_from_csv(std::io::BufReader::new(file), &columns)?;
+        if let Some(dir) = index_path.parent() {
+            std::fs::create_dir_all(dir)?;
+        }
+        index.save(&index_path)?;
+        Ok(index)
+    })
+    .await
+    .map_err(|e| crate::common::errors::AppError::Internal(format!("Runtime Error: {}", e)))??;
+    info!("🔐 EDM 索引已构建: {} 条记录 -> {:?}", index.entry_count(), path);
+    let summary = edm_index_summary(Some(&index));
+    reload_engine_internal(app, state).await?;
+    Ok(summary)
+}
+
+/// 获取 EDM 索引信息（记录数与列）
+#[tauri::command]
+pub async fn get_edm_index_info(app: AppHandle) -> AppResult<serde_json::Value> {
+    Ok(edm_index_summary(ConfigLoader::load_edm_index(&app).as_ref()))
+}
+
+/// 删除 EDM 索引
+#[tauri::command]
+pub async fn delete_edm_index(app: AppHandle, state: State<'_, AppState>) -> AppResult<()> {
+    let path = ConfigLoader::edm_index_path(&app);
+    if path.exists() {
+        std::fs::remove_file(&path)?;
+    }
+    reload_engine_internal(app, state).await
+}
+
+fn edm_index_summary(index: Option<&EdmIndex>) -> serde_json::Value {
+    match index {
+        Some(index) => serde_json::json!({
+            "exists": true,
+            "entries": index.entry_count(),
+            "columns": index.columns(),
+        }),
+        None => serde_json::json!({ "exists": false, "entries": 0, "columns": [] }),
+    }
+}
+
+/// 获取外部字典登记列表
+#[tauri::command]
+pub async fn get_dictionaries(app: AppHandle) -> AppResult<Vec<DictionarySpec>> {
+    Ok(ConfigLoader::load_dictionary_specs(&app))
+}
+
+/// 保存或更新外部字典登记并重新加载（变化的词表会自动重建）
+#[tauri::command]
+pub async fn save_dictionary(app: AppHandle, state: State<'_, AppState>, spec: DictionarySpec) -> AppResult<String> {
+    ConfigLoader::save_dictionary_spec(&app, spec)?;
+    reload_engine_internal(app, state).await?;
+    Ok("字典已保存并应用".into())
+}
+
+/// 删除外部字典登记
+#[tauri::command]
+pub async fn delete_dictionary(app: AppHandle, state: State<'_, AppState>, name: String) -> AppResult<String> {
+    ConfigLoader::delete_dictionary_spec(&app, &name)?;
+    reload_engine_internal(app, state).await?;
+    Ok("字典已删除".into())
+}
+
+/// 重建外部字典（`force` 为 false 时只重建源词表有变化的字典）
+#[tauri::command]
+pub async fn rebuild_dictionaries(
+    app: AppHandle,
+    state: State<'_, AppState>,
+    force: bool,
+) -> AppResult<Vec<DictionaryBuildReport>> {
+    let (dictionaries, reports) = ConfigLoader::prepare_dictionaries(&app, force);
+    reload_engine_with_dictionaries(app, state, dictionaries).await?;
+    Ok(reports)
+}
+
 /// 内部函数：重新加载规则并替换引擎（保留 AI 引擎）
 async fn reload_engine_internal(app: AppHandle, state: State<'_, AppState>) -> AppResult<()> {
+    let dictionaries = ConfigLoader::prepare_dictionaries(&app, false).0;
+    reload_engine_with_dictionaries(app, state, dictionaries).await
+}
+
+/// 使用已编译好的外部字典重新加载规则并替换引擎
+async fn reload_engine_with_dictionaries(
+    app: AppHandle,
+    state: State<'_, AppState>,
+    dictionaries: Vec<MappedDictionary>,
+) -> AppResult<()> {
     let rules = ConfigLoader::load_all_rules(&app);
     let models_dir = state.models_dir.clone();
     let mut new_engine = HybridEngine::from_rules(rules);
-    // 同步脱敏标签包裹样式
-    new_engine.set_wrapper_style(&state.settings.read().mask_wrapper_style);
+    new_engine.set_allowlist(Allowlist::new(&ConfigLoader::load_allowlist(&app)));
+    if let Some(index) = ConfigLoader::load_edm_index(&app) {
+        new_engine.set_edm_index(Arc::new(index));
+    }
+    new_engine.set_dictionaries(dictionaries);
+    // 同步脱敏标签包裹样式与保留地址过滤
+    {
+        let settings = state.settings.read();
+        new_engine.set_wrapper_style(&settings.mask_wrapper_style);
+        new_engine.set_network_filter(
+            settings.ip_skip_private,
+            settings.ip_skip_loopback,
+            settings.ip_skip_documentation,
+        );
+        new_engine.set_connection_string_parts(settings.conn_mask_user, settings.conn_mask_host);
+        if settings.international_pack_enabled {
+            new_engine.enable_international_pack();
+        }
+    }
     // 🚀 重新启用 AI 引擎，确保 reload 后 AI 识别器不丢失
     new_engine.enable_ai_engine(&models_dir);
     let new_engine = Arc::new(new_engine);
@@ -171,6 +311,7 @@ pub async fn update_app_settings(
 
     // 缓存旧值，在写入 state 前记录（用于后续 dirty check）
     let old_writer_enabled = state.settings.read().record_writer_enabled;
+    let pack_changed = state.settings.read().international_pack_enabled != new_settings.international_pack_enabled;
 
     {
         let mut guard = state.settings.write();
@@ -186,8 +327,22 @@ pub async fn update_app_settings(
         ).map_err(crate::common::errors::AppError::Internal)?;
     }
 
-    // 同步脱敏标签包裹样式到引擎
-    state.engine.read().set_wrapper_style(&new_settings.mask_wrapper_style);
+    // 同步脱敏标签包裹样式与保留地址过滤到引擎
+    {
+        let engine = state.engine.read();
+        engine.set_wrapper_style(&new_settings.mask_wrapper_style);
+        engine.set_network_filter(
+            new_settings.ip_skip_private,
+            new_settings.ip_skip_loopback,
+            new_settings.ip_skip_documentation,
+        );
+        engine.set_connection_string_parts(new_settings.conn_mask_user, new_settings.conn_mask_host);
+    }
+
+    // 识别包变化需要重建引擎（注册/移除识别器）
+    if pack_changed {
+        reload_engine_internal(app.clone(), state.clone()).await?;
+    }
 
     // 无条件重建记录写入器（无论配置是否变化，确保 writer 与 state 一致）
     info!("[RecordWriter] 保存触发重建 (old={}, new={})",
@@ -535,11 +690,8 @@ pub async fn import_custom_rules(
 pub async fn export_custom_rules_yaml(app: AppHandle) -> AppResult<String> {
     use crate::core::rules::RuleGroup;
     let rules = ConfigLoader::load_custom_rules_only(&app);
-    let yaml = serde_yaml::to_string(&RuleGroup {
-        group: "CUSTOM".into(),
-        rules,
-    })
-    .map_err(|e| crate::common::errors::AppError::Config(format!("导出序列化失败: {}", e)))?;
+    let yaml = serde_yaml::to_string(&RuleGroup::new("CUSTOM", rules))
+        .map_err(|e| crate::common::errors::AppError::Config(format!("导出序列化失败: {}", e)))?;
     Ok(yaml)
 }
 
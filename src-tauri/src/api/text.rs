@@ -7,7 +7,8 @@ pub async fn mask_text(
     state: State<'_, AppState>,
     text: String
 ) -> AppResult<String> {
+    let options = state.settings.read().key_value_options.clone();
     let engine = state.engine.read();
-    let result = engine.mask_line(text.as_bytes());
+    let (result, _) = crate::infra::fs::key_value::mask_clipboard_text(&engine, &options, text.as_bytes());
     Ok(String::from_utf8_lossy(&result).to_string())
 }
\ No newline at end of file
@@ -6,10 +6,15 @@ use serde::{Serialize, Deserialize};
 use parking_lot::{Mutex, RwLock};
 use crate::core::config::AppSettings;
 use crate::infra::record_writer::RecordWriter;
+use crate::infra::fs::scan_report::ScanReport;
+use crate::infra::jobs::JobManager;
 
 /// 共享混合引擎类型
 pub type SharedEngine = Arc<RwLock<Arc<HybridEngine>>>;
 
+/// 共享配置类型
+pub type SharedSettings = Arc<RwLock<AppSettings>>;
+
 pub struct AppState {
     /// 脱敏引擎 (支持热重载)
     pub engine: SharedEngine,
@@ -18,10 +23,13 @@ pub struct AppState {
     pub shadow_store: Arc<RwLock<ShadowClipboard>>,
     
     /// 持久化配置
-    pub settings: Arc<RwLock<AppSettings>>,
+    pub settings: SharedSettings,
 
     /// 设备指纹（UUID v4，持久化到磁盘，用于下载令牌签名）
     pub device_id: Arc<String>,
+
+    /// 扫描报告指纹密钥（本机随机生成并持久化，用于 SARIF 指纹的 HMAC）
+    pub fingerprint_key: Arc<Vec<u8>>,
     
     /// 正在执行魔术粘贴的标记 (原子操作，防止递归触发)
     pub is_magic_pasting: Arc<AtomicBool>,
@@ -43,6 +51,12 @@ pub struct AppState {
 
     /// 可插拔的记录写入器（持久化脱敏映射到 .md 文件）
     pub record_writer: Arc<RwLock<Option<Arc<dyn RecordWriter>>>>,
+
+    /// 后台文件脱敏任务队列
+    pub jobs: JobManager,
+
+    /// 最近一次扫描的完整报告（含超出样本上限的命中，只保留在内存中供导出）
+    pub last_scan_report: Arc<Mutex<Option<ScanReport>>>,
 }
 
 /// 影子剪贴板：存储当前的“影子宇宙”状态
@@ -66,6 +80,15 @@ pub struct EntitySpanBrief {
     pub entity_type: String,
     /// 脱敏标签 "<EMAIL>" | "[PERSON]" | ...
     pub mask_label: String,
+    /// 行号（从 1 开始）；仅文件处理时填写，此时 `start` / `end` 为全文件字节偏移
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub line: Option<u64>,
+    /// 列号（从 1 开始，按字符计）
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub column: Option<u64>,
+    /// CSV / TSV 字段序号（从 1 开始）
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub field: Option<u64>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
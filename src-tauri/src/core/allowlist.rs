@@ -0,0 +1,265 @@
+//! 白名单（豁免）子系统
+//!
+//! 在 `ConflictResolver` 之后执行，移除命中白名单的实体跨度，
+//! 用于"永不脱敏 `example.com`、`127.0.0.1`、公司客服电话、`SafeMask`"之类的需求，
+//! 而不必删除或削弱整条规则。
+//!
+//! 条目支持三种匹配方式：
+//! - `exact`：实体原文与值完全一致（忽略 ASCII 大小写、首尾空白）
+//! - `regex`：实体原文整体匹配正则（自动锚定首尾，`example\.com` 不会豁免 `ceo@example.com`）
+//! - `entity_type`：豁免指定类型的全部实体
+//!
+//! `entity_types` 为空表示不限类型；`expires_on` 过期（当天仍有效）后条目自动失效。
+//! 条目持久化在 `custom/allowlist.yaml`，与 `user_rules.yaml` 同目录。
+
+use crate::common::errors::{AppError, AppResult};
+use crate::core::recognizer::{EntitySpan, EntityType};
+use chrono::NaiveDate;
+use log::warn;
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+
+/// 白名单匹配方式
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum AllowlistKind {
+    /// 精确值
+    #[default]
+    Exact,
+    /// 正则表达式
+    Regex,
+    /// 整个实体类型
+    EntityType,
+}
+
+/// 白名单条目
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AllowlistEntry {
+    /// 条目 ID（唯一标识，保存时自动生成）
+    #[serde(default)]
+    pub id: String,
+
+    /// 匹配方式
+    #[serde(default)]
+    pub kind: AllowlistKind,
+
+    /// 精确值或正则表达式（`entity_type` 方式下忽略）
+    #[serde(default)]
+    pub value: String,
+
+    /// 作用的实体类型标签（如 `EMAIL`、`ip`），为空表示不限
+    #[serde(default)]
+    pub entity_types: Vec<String>,
+
+    /// 过期日期（含当天），为空表示永久有效
+    #[serde(default)]
+    pub expires_on: Option<NaiveDate>,
+
+    /// 豁免原因（便于审计）
+    #[serde(default)]
+    pub reason: String,
+
+    /// 是否启用
+    #[serde(default = "default_true")]
+    pub enabled: bool,
+}
+
+fn default_true() -> bool {
+    true
+}
+
+/// 用于 YAML 存储的包装结构
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct AllowlistFile {
+    pub entries: Vec<AllowlistEntry>,
+}
+
+impl AllowlistEntry {
+    /// 校验条目（正则可编译、类型方式必须指定类型、值不为空）
+    pub fn validate(&self) -> AppResult<()> {
+        match self.kind {
+            AllowlistKind::Exact if self.value.trim().is_empty() => {
+                Err(AppError::Config("白名单值不能为空".into()))
+            }
+            AllowlistKind::Regex => {
+                Regex::new(&self.value)?;
+                Ok(())
+            }
+            AllowlistKind::EntityType if self.entity_types.is_empty() => {
+                Err(AppError::Config("按类型豁免时必须指定实体类型".into()))
+            }
+            _ => Ok(()),
+        }
+    }
+
+    /// 条目在指定日期是否已过期
+    pub fn is_expired(&self, today: NaiveDate) -> bool {
+        self.expires_on.is_some_and(|d| d < today)
+    }
+}
+
+/// 编译后的白名单条目
+struct CompiledEntry {
+    kind: AllowlistKind,
+    value: String,
+    regex: Option<Regex>,
+    entity_types: Vec<String>,
+    expires_on: Option<NaiveDate>,
+}
+
+impl CompiledEntry {
+    fn type_matches(&self, entity_type: &EntityType) -> bool {
+        self.entity_types.is_empty()
+            || self.entity_types.iter().any(|t| {
+                t.eq_ignore_ascii_case(entity_type.display_label())
+                    || t.eq_ignore_ascii_case(entity_type.en_label())
+                    || EntityType::from_label(t) == *entity_type
+            })
+    }
+
+    fn matches(&self, entity_type: &EntityType, value: &str) -> bool {
+        if !self.type_matches(entity_type) {
+            return false;
+        }
+        match self.kind {
+            AllowlistKind::Exact => value.trim().eq_ignore_ascii_case(self.value.trim()),
+            AllowlistKind::Regex => self.regex.as_ref().is_some_and(|re| re.is_match(value)),
+            AllowlistKind::EntityType => true,
+        }
+    }
+}
+
+/// 白名单
+#[derive(Default)]
+pub struct Allowlist {
+    entries: Vec<CompiledEntry>,
+}
+
+impl Allowlist {
+    /// 编译白名单条目
+    ///
+    /// 停用或无效（如正则无法编译）的条目会被跳过并记录警告，不影响其余条目。
+    pub fn new(entries: &[AllowlistEntry]) -> Self {
+        let mut compiled = Vec::with_capacity(entries.len());
+        for entry in entries.iter().filter(|e| e.enabled) {
+            if let Err(e) = entry.validate() {
+                warn!("⚠️ 跳过无效白名单条目 {}: {}", entry.id, e);
+                continue;
+            }
+            compiled.push(CompiledEntry {
+                kind: entry.kind,
+                value: entry.value.clone(),
+                regex: match entry.kind {
+                    AllowlistKind::Regex => Regex::new(&format!("^(?:{})$", entry.value)).ok(),
+                    _ => None,
+                },
+                entity_types: entry.entity_types.clone(),
+                expires_on: entry.expires_on,
+            });
+        }
+        Self { entries: compiled }
+    }
+
+    /// 有效条目数量
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// 是否为空
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// 实体跨度是否被豁免
+    pub fn is_allowed(&self, span: &EntitySpan, text: &[u8], today: NaiveDate) -> bool {
+        let Some(bytes) = text.get(span.start..span.end) else {
+            return false;
+        };
+        let value = String::from_utf8_lossy(bytes);
+        self.entries
+            .iter()
+            .filter(|e| e.expires_on.is_none_or(|d| d >= today))
+            .any(|e| e.matches(&span.entity_type, &value))
+    }
+
+    /// 移除被豁免的实体跨度
+    pub fn filter(&self, spans: Vec<EntitySpan>, text: &[u8]) -> Vec<EntitySpan> {
+        if self.entries.is_empty() {
+            return spans;
+        }
+        let today = chrono::Local::now().date_naive();
+        spans.into_iter().filter(|s| !self.is_allowed(s, text, today)).collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry(kind: AllowlistKind, value: &str, types: &[&str]) -> AllowlistEntry {
+        AllowlistEntry {
+            id: String::new(),
+            kind,
+            value: value.to_string(),
+            entity_types: types.iter().map(|t| t.to_string()).collect(),
+            expires_on: None,
+            reason: String::new(),
+            enabled: true,
+        }
+    }
+
+    fn span_of(text: &str, needle: &str, entity_type: EntityType) -> EntitySpan {
+        let start = text.find(needle).unwrap();
+        EntitySpan::new(start, start + needle.len(), entity_type, 1.0, "test")
+    }
+
+    #[test]
+    fn test_exact_and_type_scope() {
+        let text = "SafeMask support 400-800-1234";
+        let allowlist = Allowlist::new(&[
+            entry(AllowlistKind::Exact, "safemask", &[]),
+            entry(AllowlistKind::Exact, "400-800-1234", &["PHONE"]),
+        ]);
+        let today = NaiveDate::from_ymd_opt(2026, 1, 1).unwrap();
+        assert!(allowlist.is_allowed(&span_of(text, "SafeMask", EntityType::Person), text.as_bytes(), today));
+        assert!(allowlist.is_allowed(&span_of(text, "400-800-1234", EntityType::Phone), text.as_bytes(), today));
+        // 类型不符不豁免
+        assert!(!allowlist.is_allowed(&span_of(text, "400-800-1234", EntityType::BankCard), text.as_bytes(), today));
+    }
+
+    #[test]
+    fn test_regex_entity_type_and_expiry() {
+        let text = "a@example.com 10.0.0.1";
+        let mut expired = entry(AllowlistKind::EntityType, "", &["ip"]);
+        expired.expires_on = NaiveDate::from_ymd_opt(2025, 12, 31);
+        let allowlist = Allowlist::new(&[entry(AllowlistKind::Regex, r"[^@]+@example\.com", &[]), expired]);
+
+        let spans = [span_of(text, "a@example.com", EntityType::Email), span_of(text, "10.0.0.1", EntityType::IpAddress)];
+        let before = NaiveDate::from_ymd_opt(2025, 12, 31).unwrap();
+        let after = NaiveDate::from_ymd_opt(2026, 1, 1).unwrap();
+        assert!(allowlist.is_allowed(&spans[0], text.as_bytes(), after));
+        assert!(allowlist.is_allowed(&spans[1], text.as_bytes(), before));
+        assert!(!allowlist.is_allowed(&spans[1], text.as_bytes(), after));
+    }
+
+    #[test]
+    fn test_regex_must_match_whole_entity() {
+        let text = "ceo@example.com https://example.com/a example.com";
+        let allowlist = Allowlist::new(&[entry(AllowlistKind::Regex, r"example\.com", &[])]);
+        let today = NaiveDate::from_ymd_opt(2026, 1, 1).unwrap();
+        assert!(!allowlist.is_allowed(&span_of(text, "ceo@example.com", EntityType::Email), text.as_bytes(), today));
+        assert!(!allowlist.is_allowed(&span_of(text, "https://example.com/a", EntityType::Url), text.as_bytes(), today));
+        let bare = text.rfind("example.com").unwrap();
+        let span = EntitySpan::new(bare, text.len(), EntityType::Url, 1.0, "test");
+        assert!(allowlist.is_allowed(&span, text.as_bytes(), today));
+    }
+
+    #[test]
+    fn test_validate() {
+        assert!(entry(AllowlistKind::Regex, "(", &[]).validate().is_err());
+        assert!(entry(AllowlistKind::Exact, " ", &[]).validate().is_err());
+        assert!(entry(AllowlistKind::EntityType, "", &[]).validate().is_err());
+        // 无效条目被跳过
+        assert!(Allowlist::new(&[entry(AllowlistKind::Regex, "(", &[])]).is_empty());
+    }
+}
@@ -1,3 +1,4 @@
+use crate::core::file_options::{CsvOptions, KeyValueOptions, ProcessOptions, StructuredOptions, DEFAULT_CHUNK_OVERLAP_LINES};
 use serde::{Deserialize, Serialize};
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -86,6 +87,42 @@ pub struct AppSettings {
     /// 全局脱敏标签包裹样式: "angle" (尖括号) 或 "square" (方括号)
     pub mask_wrapper_style: String,
 
+    // --- 网络地址识别 ---
+    /// 是否跳过私有/链路本地地址（10/8、172.16/12、192.168/16、fc00::/7、fe80::/10 等）
+    pub ip_skip_private: bool,
+    /// 是否跳过回环与未指定地址（127/8、::1、::）
+    pub ip_skip_loopback: bool,
+    /// 是否跳过文档示例地址（192.0.2/24、198.51.100/24、203.0.113/24、2001:db8::/32）
+    pub ip_skip_documentation: bool,
+
+    // --- 连接串 ---
+    /// 连接串中是否同时脱敏用户名（密码始终脱敏）
+    pub conn_mask_user: bool,
+    /// 连接串中是否同时脱敏主机名
+    pub conn_mask_host: bool,
+
+    // --- 识别包 ---
+    /// 是否加载国际证件识别包（美国 SSN/ITIN、英国 NINO、德国 Steuer-ID、法国 NIR、欧盟增值税号）
+    pub international_pack_enabled: bool,
+
+    // --- 大文件分块 ---
+    /// 分块处理时前后块之间的重叠行数（跨行实体与上下文识别），0 表示关闭
+    pub chunk_overlap_lines: usize,
+    /// GBK / UTF-16 / Latin-1 等非 UTF-8 文本统一输出为 UTF-8（默认按原编码写回）
+    pub text_output_utf8: bool,
+
+    // --- CSV / TSV ---
+    /// 列策略（按表头名或列序号指定整列脱敏 / 识别 / 跳过）与整列实体推断
+    pub csv_options: CsvOptions,
+
+    // --- JSON / YAML ---
+    /// 结构化脱敏的键名规则（如 password / secret / token）与 JSONPath 选择器
+    pub structured_options: StructuredOptions,
+
+    // --- .env / .properties / .ini / TOML ---
+    /// 键值配置的敏感键模式（如 `*_PASSWORD`、`*_TOKEN`、`DSN`），命中的键其值整体脱敏
+    pub key_value_options: KeyValueOptions,
+
     // --- 外观主题 ---
     /// UI 主题标识符，例如 "default" | "claude"。
     ///
@@ -117,11 +154,35 @@ impl Default for AppSettings {
             ],
             record_writer_enabled: false,
             mask_wrapper_style: "angle".to_string(),
+            ip_skip_private: false,
+            ip_skip_loopback: false,
+            ip_skip_documentation: false,
+            conn_mask_user: true,
+            conn_mask_host: false,
+            international_pack_enabled: false,
+            chunk_overlap_lines: DEFAULT_CHUNK_OVERLAP_LINES,
+            text_output_utf8: false,
+            csv_options: CsvOptions::default(),
+            structured_options: StructuredOptions::default(),
+            key_value_options: KeyValueOptions::default(),
             theme: default_theme(),
         }
     }
 }
 
+impl AppSettings {
+    /// 文件处理选项快照
+    pub fn process_options(&self) -> ProcessOptions {
+        ProcessOptions {
+            chunk_overlap_lines: self.chunk_overlap_lines,
+            output_utf8: self.text_output_utf8,
+            csv: self.csv_options.clone(),
+            structured: self.structured_options.clone(),
+            key_value: self.key_value_options.clone(),
+        }
+    }
+}
+
 // ── Tests ────────────────────────────────────────────────────────────────
 
 #[cfg(test)]
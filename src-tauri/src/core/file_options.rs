@@ -0,0 +1,139 @@
+//! 文件处理选项
+//!
+//! 由 `AppSettings` 生成快照，随调用显式传入文件处理器、批处理与任务队列；
+//! 一次处理（单个文件、一个目录批次或一次任务尝试）自始至终使用同一份快照，中途修改设置不影响进行中的处理。
+
+use serde::{Deserialize, Serialize};
+
+/// 分块处理默认的块间重叠行数（前后各）
+pub const DEFAULT_CHUNK_OVERLAP_LINES: usize = 32;
+
+/// 文件处理选项快照
+#[derive(Debug, Clone, PartialEq)]
+pub struct ProcessOptions {
+    /// 分块处理时前后块之间的重叠行数，0 表示关闭
+    pub chunk_overlap_lines: usize,
+    /// 非 UTF-8 文本统一输出为 UTF-8（默认按原编码写回）
+    pub output_utf8: bool,
+    pub csv: CsvOptions,
+    pub structured: StructuredOptions,
+    pub key_value: KeyValueOptions,
+}
+
+impl Default for ProcessOptions {
+    fn default() -> Self {
+        Self {
+            chunk_overlap_lines: DEFAULT_CHUNK_OVERLAP_LINES,
+            output_utf8: false,
+            csv: CsvOptions::default(),
+            structured: StructuredOptions::default(),
+            key_value: KeyValueOptions::default(),
+        }
+    }
+}
+
+// ─────────────────────────────────────────────────────────────────────────────
+// CSV / TSV
+// ─────────────────────────────────────────────────────────────────────────────
+
+/// 列引用：表头名（ASCII 不区分大小写）或列序号（从 1 开始，与实体摘要的 `field` 一致）
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum ColumnRef {
+    Index(usize),
+    Name(String),
+}
+
+/// 列处理方式
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ColumnAction {
+    /// 整格脱敏
+    Mask,
+    /// 按识别结果脱敏
+    #[default]
+    Detect,
+    /// 原样保留
+    Skip,
+}
+
+/// 单列策略
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ColumnPolicy {
+    pub column: ColumnRef,
+    pub action: ColumnAction,
+    /// `mask` 使用的脱敏策略（replace / partial_mask / hash / redact / token / template），默认 replace
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub strategy: Option<String>,
+    /// `mask` 的实体类型（如 `phone`），为空时取推断类型，仍无则以表头名作为标签
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub entity_type: Option<String>,
+}
+
+/// CSV / TSV 处理选项
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct CsvOptions {
+    /// 列策略（同一列命中多条时取第一条）
+    pub policies: Vec<ColumnPolicy>,
+    /// 是否根据抽样行推断整列实体类型
+    pub infer_columns: bool,
+    /// 列推断抽样的数据行数
+    pub sample_rows: usize,
+}
+
+impl Default for CsvOptions {
+    fn default() -> Self {
+        Self {
+            policies: Vec::new(),
+            infer_columns: true,
+            sample_rows: 200,
+        }
+    }
+}
+
+// ─────────────────────────────────────────────────────────────────────────────
+// JSON / YAML
+// ─────────────────────────────────────────────────────────────────────────────
+
+/// 结构化脱敏选项
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct StructuredOptions {
+    /// 键名规则词（命中的键其值整体脱敏）
+    pub key_rules: Vec<String>,
+    /// JSONPath 选择器（命中的节点整体脱敏）
+    pub paths: Vec<String>,
+}
+
+impl Default for StructuredOptions {
+    fn default() -> Self {
+        let key_rules = [
+            "password", "passwd", "pwd", "secret", "token", "api_key", "private_key", "access_key", "credential",
+            "authorization",
+        ];
+        Self {
+            key_rules: key_rules.iter().map(|k| k.to_string()).collect(),
+            paths: Vec::new(),
+        }
+    }
+}
+
+// ─────────────────────────────────────────────────────────────────────────────
+// .env / .properties / .ini / TOML
+// ─────────────────────────────────────────────────────────────────────────────
+
+/// 键值配置脱敏选项
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct KeyValueOptions {
+    /// 敏感键 glob 模式（不区分大小写），命中的键其值整体脱敏
+    pub sensitive_keys: Vec<String>,
+}
+
+impl Default for KeyValueOptions {
+    fn default() -> Self {
+        let patterns = ["*PASSWORD", "*PASSWD", "*_PWD", "*SECRET", "*TOKEN", "*_KEY", "*APIKEY", "DSN", "*_DSN"];
+        Self { sensitive_keys: patterns.iter().map(|p| p.to_string()).collect() }
+    }
+}
@@ -59,12 +59,40 @@ impl MaskingEngine {
             })
     }
 
+    /// 获取实体跨度对应的策略
+    ///
+    /// 优先匹配带元数据条件的键 `"{类型}[{key}={value}]"`（如 `"bank_card[cross_border=true]"`、
+    /// `"bank_card[card_brand=VISA]"`），未命中时回退到按实体类型路由。
+    fn strategy_for_span(&self, span: &EntitySpan) -> &dyn MaskingStrategy {
+        let entity_key = span.entity_type.en_label();
+        let mut conditions: Vec<(&String, &String)> = span.metadata.iter().collect();
+        conditions.sort();
+
+        for (key, value) in conditions {
+            let scoped = format!("{}[{}={}]", entity_key, key, value);
+            if let Some(strategy_type) = self.config.entity_strategies.get(&scoped)
+                && let Some(strategy) = self.strategies.get(strategy_type) {
+                    return strategy.as_ref();
+                }
+        }
+
+        self.strategy_for_entity(&span.entity_type)
+    }
+
     /// 对单个实体执行脱敏
     pub fn mask_entity(&self, original: &str, span: &EntitySpan) -> String {
-        let strategy = self.strategy_for_entity(&span.entity_type);
+        let strategy = self.strategy_for_span(span);
         strategy.mask(original, span, &self.config)
     }
 
+    /// 按指定策略对单个实体脱敏（不走实体类型路由，用于 CSV 按列指定策略）
+    pub fn mask_with(&self, strategy: &MaskStrategyType, original: &str, span: &EntitySpan) -> String {
+        match self.strategies.get(strategy) {
+            Some(s) => s.mask(original, span, &self.config),
+            None => self.mask_entity(original, span),
+        }
+    }
+
     /// 对文本和识别结果执行完整脱敏
     pub fn apply(&self, text: &str, spans: &[EntitySpan]) -> MaskResult {
         if spans.is_empty() {
@@ -181,4 +209,20 @@ mod tests {
         let result = engine.mask_entity("test@example.com", &span);
         assert_eq!(result, "***");
     }
+
+    #[test]
+    fn test_strategy_scoped_by_metadata() {
+        let mut config = MaskConfig::default();
+        config.entity_strategies.insert("bank_card[cross_border=true]".to_string(), MaskStrategyType::Redact);
+
+        let engine = MaskingEngine::new(config);
+        let foreign = make_span(EntityType::BankCard, 0, 16)
+            .with_metadata("card_brand", "VISA")
+            .with_metadata("cross_border", "true");
+        let domestic = make_span(EntityType::BankCard, 0, 16)
+            .with_metadata("card_brand", "UNIONPAY")
+            .with_metadata("cross_border", "false");
+        assert_eq!(engine.mask_entity("4111111111111111", &foreign), "***");
+        assert_eq!(engine.mask_entity("6212345678901232", &domestic), "[BANK_CARD]");
+    }
 }
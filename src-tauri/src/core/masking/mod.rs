@@ -34,6 +34,9 @@ pub struct MaskConfig {
     /// 默认脱敏策略
     pub default_strategy: MaskStrategyType,
     /// 按实体类型指定的策略
+    ///
+    /// 键为实体类型英文标签（如 `"bank_card"`），也可以带元数据条件
+    /// （如 `"bank_card[cross_border=true]"`），条件键优先匹配。
     pub entity_strategies: std::collections::HashMap<String, MaskStrategyType>,
     /// 自定义模板 (用于 Template 策略)
     pub templates: std::collections::HashMap<String, String>,
@@ -95,13 +95,41 @@ impl PartialMaskStrategy {
 
     fn mask_ip(original: &str) -> String {
         let parts: Vec<&str> = original.split('.').collect();
-        if parts.len() == 4 {
+        // IPv4 首段不含冒号（"1.2.3.4:80"）；IPv6 含内嵌 IPv4 时首段形如 "::ffff:10"
+        if original.starts_with('[') || parts[0].contains(':') {
+            Self::mask_ipv6(original)
+        } else if parts.len() == 4 {
             format!("{}.*.*.{}", parts[0], parts[3])
         } else {
             Self::mask_fallback(original)
         }
     }
 
+    /// IPv6 保留首尾两组，中间各组替换为 `*`；方括号、端口、Zone ID、前缀长度原样保留
+    ///
+    /// `[2001:db8::1]:443` → `[2001:*:*:*:*:*:*:1]:443`；
+    /// 内嵌 IPv4（如 IPv4 映射地址）保留原前缀并按 IPv4 脱敏：`::ffff:10.0.0.1` → `::ffff:10.*.*.1`
+    fn mask_ipv6(original: &str) -> String {
+        let (open, body, tail) = match original.strip_prefix('[').and_then(|r| r.split_once(']')) {
+            Some((inner, rest)) => ("[", inner, format!("]{}", rest)),
+            None => ("", original, String::new()),
+        };
+        let addr_end = body.find(['%', '/']).unwrap_or(body.len());
+        let (addr, suffix) = body.split_at(addr_end);
+
+        match addr.parse::<std::net::Ipv6Addr>() {
+            Ok(_) if addr.contains('.') => {
+                let (prefix, quad) = addr.rsplit_once(':').unwrap_or(("", addr));
+                format!("{}{}:{}{}{}", open, prefix, Self::mask_ip(quad), suffix, tail)
+            }
+            Ok(ip) => {
+                let seg = ip.segments();
+                format!("{}{:x}:*:*:*:*:*:*:{:x}{}{}", open, seg[0], seg[7], suffix, tail)
+            }
+            Err(_) => Self::mask_fallback(original),
+        }
+    }
+
     fn mask_fallback(original: &str) -> String {
         let chars: Vec<char> = original.chars().collect();
         let len = chars.len();
@@ -260,9 +288,22 @@ impl MaskingStrategy for TokenStrategy {
 /// 模板策略
 ///
 /// 使用用户定义的模板进行替换。
-/// 模板中可以使用 `{type}` 作为实体类型的占位符。
+/// 模板中可以使用 `{type}` 作为实体类型的占位符，
+/// 以及 `{元数据键}`（如 `{card_brand}`）引用实体元数据。
 pub struct TemplateStrategy;
 
+impl TemplateStrategy {
+    fn render(template: &str, span: &EntitySpan) -> String {
+        let mut out = template
+            .replace("{type}", span.entity_type.display_label())
+            .replace("{label}", span.entity_type.en_label());
+        for (key, value) in &span.metadata {
+            out = out.replace(&format!("{{{}}}", key), value);
+        }
+        out
+    }
+}
+
 impl MaskingStrategy for TemplateStrategy {
     fn name(&self) -> &str {
         "template"
@@ -277,16 +318,12 @@ impl MaskingStrategy for TemplateStrategy {
 
         // 查找实体类型的模板
         if let Some(template) = config.templates.get(entity_key) {
-            return template
-                .replace("{type}", span.entity_type.display_label())
-                .replace("{label}", span.entity_type.en_label());
+            return Self::render(template, span);
         }
 
         // 查找默认模板
         if let Some(template) = config.templates.get("default") {
-            return template
-                .replace("{type}", span.entity_type.display_label())
-                .replace("{label}", span.entity_type.en_label());
+            return Self::render(template, span);
         }
 
         // 回退到替换策略
@@ -438,6 +475,26 @@ mod tests {
         assert_eq!(result, "192.*.*.100");
     }
 
+    #[test]
+    fn test_template_metadata_placeholder() {
+        let strategy = TemplateStrategy;
+        let mut config = default_config();
+        config.templates.insert("bank_card".to_string(), "<CARD:{card_brand}>".to_string());
+        let span = make_span(EntityType::BankCard).with_metadata("card_brand", "UNIONPAY");
+        assert_eq!(strategy.mask("6212345678901232", &span, &config), "<CARD:UNIONPAY>");
+    }
+
+    #[test]
+    fn test_partial_mask_ipv6() {
+        let strategy = PartialMaskStrategy;
+        let span = make_span(EntityType::IpAddress);
+        let config = default_config();
+        assert_eq!(strategy.mask("2001:db8::1", &span, &config), "2001:*:*:*:*:*:*:1");
+        assert_eq!(strategy.mask("[fe80::a%eth0]:443", &span, &config), "[fe80:*:*:*:*:*:*:a%eth0]:443");
+        assert_eq!(strategy.mask("::ffff:10.0.0.1", &span, &config), "::ffff:10.*.*.1");
+        assert_eq!(strategy.mask("[64:ff9b::192.0.2.33]:80", &span, &config), "[64:ff9b::192.*.*.33]:80");
+    }
+
     #[test]
     fn test_partial_mask_short_email() {
         let strategy = PartialMaskStrategy;
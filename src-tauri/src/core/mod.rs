@@ -1,7 +1,9 @@
 pub mod rules;
 pub mod config;
+pub mod file_options;
 pub mod recognizer;
 pub mod resolver;
+pub mod allowlist;
 pub mod hybrid_engine;
 pub mod masking;
 pub mod orchestrator;
@@ -166,6 +166,10 @@ mod tests {
                 priority: 0,
                 enabled: true,
                 is_custom: false,
+                case_insensitive: false,
+                whole_word: false,
+                unicode_boundary: false,
+                group: String::new(),
             },
         ];
         Arc::new(RwLock::new(HybridEngine::from_rules(rules)))
@@ -10,10 +10,17 @@
 //!   - n: 文本长度, m: 模式总长度, z: 匹配数
 //! - 使用 `LeftmostLongest` 匹配策略，避免重叠匹配
 //! - 适合大量固定词的场景（数千到数万条）
+//!
+//! # 匹配选项
+//!
+//! 每条规则可单独开启 `case_insensitive`（忽略大小写，在小写折叠文本上匹配）、
+//! `whole_word` / `unicode_boundary`（整词匹配）。自动机按重叠方式扫描，
+//! 先按规则的边界要求过滤，再取最左最长的不重叠结果，
+//! 因此被边界过滤掉的长匹配不会挡住其后合法的短匹配。
 
 use super::types::*;
 use super::Recognizer;
-use aho_corasick::{AhoCorasick, MatchKind};
+use aho_corasick::{AhoCorasick, AhoCorasickBuilder, MatchKind};
 
 /// 字典条目
 struct DictEntry {
@@ -23,13 +30,23 @@ struct DictEntry {
     source: String,
     /// 替换掩码
     mask: String,
+    /// 词边界要求
+    boundary: WordBoundary,
+}
+
+/// 自动机及其模式索引到字典条目下标的映射
+struct Automaton {
+    ac: AhoCorasick,
+    entry_index: Vec<usize>,
 }
 
 /// Aho-Corasick 字典识别器
 pub struct AhoCorasickRecognizer {
-    /// Aho-Corasick 自动机
-    engine: Option<AhoCorasick>,
-    /// 字典条目列表（与自动机模式索引对应）
+    /// 区分大小写的自动机
+    engine: Option<Automaton>,
+    /// 忽略大小写的自动机（模式已转小写）
+    folded_engine: Option<Automaton>,
+    /// 字典条目列表
     entries: Vec<DictEntry>,
     /// 识别器名称
     name: String,
@@ -49,6 +66,7 @@ impl AhoCorasickRecognizer {
         priority: Option<i32>,
     ) -> Self {
         let mut patterns = Vec::new();
+        let mut folded_patterns = Vec::new();
         let mut entries = Vec::new();
 
         for rule in rules {
@@ -61,25 +79,22 @@ impl AhoCorasickRecognizer {
                 continue;
             }
 
-            patterns.push(rule.pattern.clone());
+            if rule.case_insensitive {
+                folded_patterns.push((rule.pattern.to_lowercase(), entries.len()));
+            } else {
+                patterns.push((rule.pattern.clone(), entries.len()));
+            }
             entries.push(DictEntry {
                 entity_type: guess_entity_type(&rule.name),
                 source: format!("rule:{}", rule.name),
                 mask: rule.mask.clone(),
+                boundary: rule.word_boundary(),
             });
         }
 
-        let engine = if patterns.is_empty() {
-            None
-        } else {
-            AhoCorasick::builder()
-                .match_kind(MatchKind::LeftmostLongest)
-                .build(&patterns)
-                .ok()
-        };
-
         Self {
-            engine,
+            engine: Automaton::build(patterns, false),
+            folded_engine: Automaton::build(folded_patterns, true),
             entries,
             name: name.unwrap_or("aho_corasick_engine").to_string(),
             enabled: true,
@@ -105,25 +120,18 @@ impl AhoCorasickRecognizer {
             if word.is_empty() || word.starts_with('#') {
                 continue;
             }
-            patterns.push(word.to_string());
+            patterns.push((word.to_string(), entries.len()));
             entries.push(DictEntry {
                 entity_type: entity_type.clone(),
                 source: format!("dict:{}", dict_path),
                 mask: format!("[{}]", entity_type.display_label()),
+                boundary: WordBoundary::None,
             });
         }
 
-        let engine = if patterns.is_empty() {
-            None
-        } else {
-            AhoCorasick::builder()
-                .match_kind(MatchKind::LeftmostLongest)
-                .build(&patterns)
-                .ok()
-        };
-
         Ok(Self {
-            engine,
+            engine: Automaton::build(patterns, false),
+            folded_engine: None,
             entries,
             name: name.unwrap_or("dict_engine").to_string(),
             enabled: true,
@@ -135,6 +143,59 @@ impl AhoCorasickRecognizer {
     pub fn entry_count(&self) -> usize {
         self.entries.len()
     }
+
+    /// 收集满足边界要求的候选匹配 `(start, end, entry)`
+    fn collect_candidates(&self, input: &[u8]) -> Vec<(usize, usize, usize)> {
+        let mut candidates = Vec::new();
+        let mut push = |start: usize, end: usize, entry: usize| {
+            if start < end && self.entries[entry].boundary.is_bounded(input, start, end) {
+                candidates.push((start, end, entry));
+            }
+        };
+
+        if let Some(ref automaton) = self.engine {
+            for mat in automaton.ac.find_overlapping_iter(input) {
+                push(mat.start(), mat.end(), automaton.entry_index[mat.pattern().as_usize()]);
+            }
+        }
+
+        if let Some(ref automaton) = self.folded_engine {
+            match std::str::from_utf8(input) {
+                // 在折叠文本上匹配，再映射回原文偏移
+                Ok(text) => {
+                    let (folded, offsets) = fold_case(text);
+                    for mat in automaton.ac.find_overlapping_iter(&folded) {
+                        let entry = automaton.entry_index[mat.pattern().as_usize()];
+                        push(offsets[mat.start()], offsets[mat.end()], entry);
+                    }
+                }
+                // 非法 UTF-8 时退化为 ASCII 忽略大小写
+                Err(_) => {
+                    for mat in automaton.ac.find_overlapping_iter(input) {
+                        push(mat.start(), mat.end(), automaton.entry_index[mat.pattern().as_usize()]);
+                    }
+                }
+            }
+        }
+
+        candidates
+    }
+}
+
+impl Automaton {
+    /// 构建自动机（重叠匹配需要 `Standard` 匹配策略）
+    fn build(patterns: Vec<(String, usize)>, ascii_case_insensitive: bool) -> Option<Self> {
+        if patterns.is_empty() {
+            return None;
+        }
+        let (patterns, entry_index): (Vec<String>, Vec<usize>) = patterns.into_iter().unzip();
+        AhoCorasickBuilder::new()
+            .match_kind(MatchKind::Standard)
+            .ascii_case_insensitive(ascii_case_insensitive)
+            .build(&patterns)
+            .ok()
+            .map(|ac: AhoCorasick| Self { ac, entry_index })
+    }
 }
 
 impl Recognizer for AhoCorasickRecognizer {
@@ -153,24 +214,29 @@ impl Recognizer for AhoCorasickRecognizer {
 
     fn analyze(&self, context: &AnalysisContext) -> AnalysisResult {
         let input = context.text;
-        let mut spans = Vec::new();
+        let mut candidates = self.collect_candidates(input);
 
-        if let Some(ref ac) = self.engine {
-            for mat in ac.find_iter(input) {
-                let pattern_idx = mat.pattern().as_usize();
-                if let Some(entry) = self.entries.get(pattern_idx) {
-                    spans.push(EntitySpan {
-                        start: mat.start(),
-                        end: mat.end(),
-                        entity_type: entry.entity_type.clone(),
-                        confidence: 1.0, // 字典匹配是确定性的
-                        source: self.name.clone(),
-                        context: Some(entry.source.clone()),
-                        mask: Some(entry.mask.clone()),
-                        priority: 0,
-                    });
-                }
+        // 最左最长、互不重叠（同一区间按规则顺序取第一条）
+        candidates.sort_by(|a, b| a.0.cmp(&b.0).then(b.1.cmp(&a.1)).then(a.2.cmp(&b.2)));
+        let mut spans = Vec::new();
+        let mut last_end = 0;
+        for (start, end, idx) in candidates {
+            if start < last_end {
+                continue;
             }
+            let entry = &self.entries[idx];
+            spans.push(EntitySpan {
+                start,
+                end,
+                entity_type: entry.entity_type.clone(),
+                confidence: 1.0, // 字典匹配是确定性的
+                source: self.name.clone(),
+                context: Some(entry.source.clone()),
+                mask: Some(entry.mask.clone()),
+                priority: 0,
+                metadata: Default::default(),
+            });
+            last_end = end;
         }
 
         AnalysisResult {
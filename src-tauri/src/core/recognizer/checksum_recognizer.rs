@@ -7,6 +7,7 @@
 //!
 //! - **中国身份证号** (18 位): ISO 7064:1983.MOD 11-2 校验
 //! - **银行卡号** (16-19 位): Luhn 算法校验
+//! - **IBAN** (15-34 位): 国家长度表 + ISO 13616 mod-97 校验
 //!
 //! # 设计思路
 //!
@@ -124,11 +125,45 @@ impl ChecksumRecognizer {
         sum % 10 == 0
     }
 
+    /// 验证 IBAN (国家长度 + mod-97)
+    ///
+    /// # 校验规则
+    ///
+    /// 1. 去除空格后长度必须与国家长度表一致
+    /// 2. 将前 4 位移到末尾，字母转为两位数字 (A=10 … Z=35)
+    /// 3. 所得大整数 mod 97 必须等于 1
+    pub fn validate_iban(iban: &str) -> bool {
+        let compact: String = iban.chars().filter(|c| !c.is_whitespace()).collect();
+        if !compact.is_ascii() || compact.len() < 5 {
+            return false;
+        }
+        match super::financial_recognizer::iban_length(&compact[..2]) {
+            Some(len) if len == compact.len() => {}
+            _ => return false,
+        }
+
+        let mut remainder: u32 = 0;
+        for c in compact[4..].chars().chain(compact[..4].chars()) {
+            let value = match c.to_digit(36) {
+                Some(v) => v,
+                None => return false,
+            };
+            remainder = if value >= 10 {
+                (remainder * 100 + value) % 97
+            } else {
+                (remainder * 10 + value) % 97
+            };
+        }
+        remainder == 1
+    }
+
     /// 验证实体
     pub fn validate_span(&self, span: &EntitySpan, text: &str) -> Option<EntitySpan> {
         let entity_text = &text[span.start..span.end];
 
         match span.entity_type {
+            // 他国证件号（如 id_kind=KR_RRN）已由各自识别器校验
+            EntityType::IdCard if span.meta("id_kind").is_some_and(|k| k != "CN") => Some(span.clone()),
             EntityType::IdCard => {
                 if Self::validate_chinese_id(entity_text) {
                     Some(span.clone())
@@ -143,6 +178,13 @@ impl ChecksumRecognizer {
                     None // 校验失败，移除
                 }
             }
+            EntityType::Iban => {
+                if Self::validate_iban(entity_text) {
+                    Some(span.clone())
+                } else {
+                    None // 校验失败，移除
+                }
+            }
             _ => Some(span.clone()), // 其他类型不做校验
         }
     }
@@ -166,7 +208,7 @@ impl Recognizer for ChecksumRecognizer {
     }
 
     fn supported_entities(&self) -> Vec<EntityType> {
-        vec![EntityType::IdCard, EntityType::BankCard]
+        vec![EntityType::IdCard, EntityType::BankCard, EntityType::Iban]
     }
 
     fn analyze(&self, _context: &AnalysisContext) -> AnalysisResult {
@@ -246,6 +288,21 @@ mod tests {
         assert!(!ChecksumRecognizer::validate_bank_card("622202020001")); // 12 位
     }
 
+    // ─── IBAN 测试 ───
+
+    #[test]
+    fn test_valid_iban() {
+        assert!(ChecksumRecognizer::validate_iban("DE89370400440532013000"));
+        assert!(ChecksumRecognizer::validate_iban("GB82 WEST 1234 5698 7654 32"));
+    }
+
+    #[test]
+    fn test_invalid_iban() {
+        assert!(!ChecksumRecognizer::validate_iban("DE89370400440532013001")); // 错误校验位
+        assert!(!ChecksumRecognizer::validate_iban("GB82WEST123456987654")); // 长度不符
+        assert!(!ChecksumRecognizer::validate_iban("ZZ89370400440532013000")); // 未知国家
+    }
+
     // ─── 综合测试 ───
 
     #[test]
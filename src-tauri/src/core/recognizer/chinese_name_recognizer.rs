@@ -0,0 +1,373 @@
+//! 中文人名识别器
+//!
+//! 基于《百家姓》姓氏字典 + 上下文线索的离线人名识别，不依赖 AI 模型。
+//!
+//! # 识别策略
+//!
+//! - **前缀线索**: `姓名：张三`、`联系人: 欧阳娜娜` —— 线索词后紧跟 "姓氏 + 1~2 字名"
+//! - **后缀称谓**: `王建国先生`、`李娜同学` —— 称谓前回溯 "姓氏 + 1~2 字名"
+//! - **复姓优先**: 欧阳、司马、诸葛等复姓优先于单姓匹配
+//!
+//! # 误报控制
+//!
+//! - 只有出现上下文线索时才产出实体，裸文本中的 "张伟" 不会被识别
+//! - 名字用字排除常见虚词/功能字（的、了、是…）
+//! - 否定词表过滤 "全体同学"、"高中同学" 等常见组合
+
+use super::types::*;
+use super::Recognizer;
+use std::collections::HashSet;
+
+// ─────────────────────────────────────────────────────────────────────────────
+// 内置词表
+// ─────────────────────────────────────────────────────────────────────────────
+
+/// 单姓（《百家姓》常见部分 + 现代常见姓氏）
+const SINGLE_SURNAMES: &str = "赵钱孙李周吴郑王冯陈褚卫蒋沈韩杨朱秦尤许何吕施张孔曹严华金魏陶姜\
+戚谢邹喻柏水窦章云苏潘葛奚范彭郎鲁韦昌马苗凤花方俞任袁柳酆鲍史唐费廉岑薛雷贺倪汤滕殷罗毕郝邬安常\
+乐于时傅皮卞齐康伍余元卜顾孟平黄和穆萧尹姚邵湛汪祁毛禹狄米贝明臧计伏成戴谈宋茅庞熊纪舒屈项祝董梁\
+杜阮蓝闵席季麻强贾路娄危江童颜郭梅盛林刁钟徐邱骆高夏蔡田樊胡凌霍虞万支柯昝管卢莫经房裘缪干解应宗\
+丁宣贲邓郁单杭洪包诸左石崔吉钮龚程嵇邢滑裴陆荣翁荀羊於惠甄曲家封芮羿储靳汲邴糜松井段富巫乌焦巴弓\
+牧隗山谷车侯宓蓬全郗班仰秋仲伊宫宁仇栾暴甘钭厉戎祖武符刘景詹束龙叶幸司韶郜黎蓟薄印宿白怀蒲邰从鄂\
+索咸籍赖卓蔺屠蒙池乔阴胥能苍双闻莘党翟谭贡劳逄姬申扶堵冉宰郦雍却璩桑桂濮牛寿通边扈燕冀郏浦尚农温\
+别庄晏柴瞿阎充慕连茹习宦艾鱼容向古易慎戈廖庾终暨居衡步都耿满弘匡国文寇广禄阙东欧殳沃利蔚越夔隆师\
+巩厍聂晁勾敖融冷訾辛阚那简饶空曾毋沙乜养鞠须丰巢关蒯相查后荆红游竺权逯盖益桓公肖付覃";
+
+/// 复姓
+const COMPOUND_SURNAMES: &[&str] = &[
+    "欧阳", "司马", "上官", "诸葛", "东方", "皇甫", "尉迟", "公孙", "慕容", "长孙",
+    "宇文", "司徒", "令狐", "夏侯", "端木", "南宫", "西门", "独孤", "轩辕", "万俟",
+    "闻人", "澹台", "公冶", "宗政", "濮阳", "淳于", "单于", "太叔", "申屠", "仲孙",
+    "钟离", "赫连", "呼延", "拓跋", "百里", "东郭", "第五",
+];
+
+/// 前缀线索词（其后可跟全角/半角冒号）
+const PREFIX_CUES: &[&str] = &[
+    "姓名", "名字", "联系人", "收件人", "收货人", "寄件人", "申请人", "负责人",
+    "经办人", "法定代表人", "法人", "户主", "患者", "病人", "学生姓名", "客户",
+];
+
+/// 后缀称谓词
+const SUFFIX_TITLES: &[&str] = &[
+    "先生", "女士", "小姐", "经理", "同学", "老师", "总监", "主任", "医生",
+    "教授", "律师", "同志", "总经理", "董事长", "工程师",
+];
+
+/// 不能出现在名字中的功能字/高频非名用字
+const NAME_STOP_CHARS: &str = "的了是在和与及或说们位个这那对给被把将就都也还又很不没有先女经老同主医教律\
+总副电手性年身地住邮联证号职部单我你他她它您谁各全等请向从于为以及到自之其所";
+
+/// 紧跟称谓时容易被误当作姓氏的虚词（"我和张伟先生" 中的 "和"）
+const PARTICLE_SURNAMES: &str = "和于向从同为以及与那对将把被给";
+
+/// 整体否定词表：即使满足 "姓氏 + 名" 结构也不视为人名
+const NEGATIVE_WORDS: &[&str] = &[
+    "全体", "各位", "诸位", "高中", "初中", "小学", "英语", "语文", "数学", "班主任",
+    "全班", "广大", "公司", "国家", "明天", "时间", "平时", "安全", "同班", "大学",
+    "毕业", "任课", "实习", "值班", "方便", "马上", "高级", "常务", "项目", "部门",
+];
+
+// ─────────────────────────────────────────────────────────────────────────────
+// 中文人名识别器
+// ─────────────────────────────────────────────────────────────────────────────
+
+/// 中文人名识别器
+///
+/// 离线识别中文人名，产出 `EntityType::Person` 实体。
+pub struct ChineseNameRecognizer {
+    /// 识别器名称
+    name: String,
+    /// 是否启用
+    enabled: bool,
+    /// 识别器优先级
+    priority: i32,
+    /// 单姓集合
+    single_surnames: HashSet<char>,
+    /// 名字禁用字
+    stop_chars: HashSet<char>,
+    /// 虚词姓氏
+    particle_surnames: HashSet<char>,
+    /// 否定词表
+    negative_words: HashSet<&'static str>,
+}
+
+impl Default for ChineseNameRecognizer {
+    fn default() -> Self {
+        Self::new(None, None)
+    }
+}
+
+impl ChineseNameRecognizer {
+    /// 创建中文人名识别器
+    ///
+    /// # 参数
+    ///
+    /// - `name`: 识别器名称（默认 "chinese_name_recognizer"）
+    /// - `priority`: 识别器优先级（默认 60，低于规则引擎、高于 AI 引擎）
+    pub fn new(name: Option<&str>, priority: Option<i32>) -> Self {
+        Self {
+            name: name.unwrap_or("chinese_name_recognizer").to_string(),
+            enabled: true,
+            priority: priority.unwrap_or(60),
+            single_surnames: SINGLE_SURNAMES.chars().collect(),
+            stop_chars: NAME_STOP_CHARS.chars().collect(),
+            particle_surnames: PARTICLE_SURNAMES.chars().collect(),
+            negative_words: NEGATIVE_WORDS.iter().copied().collect(),
+        }
+    }
+
+    /// 判断字符是否可作为名字用字
+    fn is_given_char(&self, c: char) -> bool {
+        is_han(c) && !self.stop_chars.contains(&c)
+    }
+
+    /// 判断 `chars[at..at+len]` 是否为姓氏
+    fn is_surname(&self, chars: &[char], at: usize, len: usize) -> bool {
+        match len {
+            1 => chars.get(at).is_some_and(|c| self.single_surnames.contains(c)),
+            2 => {
+                if at + 2 > chars.len() {
+                    return false;
+                }
+                let s: String = chars[at..at + 2].iter().collect();
+                COMPOUND_SURNAMES.contains(&s.as_str())
+            }
+            _ => false,
+        }
+    }
+
+    /// 候选是否命中否定词表
+    fn is_negative(&self, candidate: &str) -> bool {
+        self.negative_words.iter().any(|w| candidate.starts_with(w) || candidate == *w)
+    }
+
+    /// 从 `at` 处向后解析 "姓氏 + 1~2 字名"，返回字符长度
+    fn parse_forward(&self, chars: &[char], at: usize) -> Option<usize> {
+        for surname_len in [2, 1] {
+            if !self.is_surname(chars, at, surname_len) {
+                continue;
+            }
+            let given_start = at + surname_len;
+            let mut given_len = 0;
+            while given_len < 2
+                && chars.get(given_start + given_len).is_some_and(|&c| self.is_given_char(c))
+            {
+                given_len += 1;
+            }
+            if given_len == 0 {
+                continue;
+            }
+            let total = surname_len + given_len;
+            let candidate: String = chars[at..at + total].iter().collect();
+            if !self.is_negative(&candidate) {
+                return Some(total);
+            }
+        }
+        None
+    }
+
+    /// 从称谓位置 `end` 向前回溯 "姓氏 + 1~2 字名"，返回起始字符下标
+    fn parse_backward(&self, chars: &[char], end: usize) -> Option<usize> {
+        let mut options: Vec<(usize, usize)> = Vec::new();
+        for (surname_len, given_len) in [(2, 2), (2, 1), (1, 2), (1, 1)] {
+            let total = surname_len + given_len;
+            if total > end {
+                continue;
+            }
+            let start = end - total;
+            if !self.is_surname(chars, start, surname_len) {
+                continue;
+            }
+            if !chars[start + surname_len..end].iter().all(|&c| self.is_given_char(c)) {
+                continue;
+            }
+            let candidate: String = chars[start..end].iter().collect();
+            if self.is_negative(&candidate) {
+                continue;
+            }
+            options.push((start, total));
+        }
+
+        let &(start, total) = options.first()?;
+        // "我和张伟先生"：三字候选以虚词开头且存在更短的候选时，取更短者
+        if total == 3
+            && self.particle_surnames.contains(&chars[start])
+            && let Some(&(short_start, _)) = options.iter().find(|(_, t)| *t == 2)
+        {
+            return Some(short_start);
+        }
+        Some(start)
+    }
+
+    /// 在字符序列中识别人名，返回 (起始字符下标, 结束字符下标, 置信度)
+    fn find_names(&self, chars: &[char]) -> Vec<(usize, usize, f32)> {
+        let mut found: Vec<(usize, usize, f32)> = Vec::new();
+
+        // ── 前缀线索 ──
+        for cue in PREFIX_CUES {
+            let cue_chars: Vec<char> = cue.chars().collect();
+            let mut i = 0;
+            while i + cue_chars.len() <= chars.len() {
+                if chars[i..i + cue_chars.len()] != cue_chars[..] {
+                    i += 1;
+                    continue;
+                }
+                let mut at = i + cue_chars.len();
+                if matches!(chars.get(at), Some(':') | Some('：')) {
+                    at += 1;
+                }
+                while matches!(chars.get(at), Some(' ') | Some('\u{3000}')) {
+                    at += 1;
+                }
+                if let Some(len) = self.parse_forward(chars, at) {
+                    found.push((at, at + len, 0.9));
+                }
+                i += cue_chars.len();
+            }
+        }
+
+        // ── 后缀称谓 ──
+        for title in SUFFIX_TITLES {
+            let title_chars: Vec<char> = title.chars().collect();
+            let mut i = 0;
+            while i + title_chars.len() <= chars.len() {
+                if chars[i..i + title_chars.len()] != title_chars[..] {
+                    i += 1;
+                    continue;
+                }
+                if let Some(start) = self.parse_backward(chars, i) {
+                    found.push((start, i, 0.85));
+                }
+                i += title_chars.len();
+            }
+        }
+
+        // 去重：同一区间只保留一次，重叠时保留先出现（前缀线索优先）者
+        found.sort_by_key(|&(s, e, _)| (s, std::cmp::Reverse(e)));
+        let mut result: Vec<(usize, usize, f32)> = Vec::with_capacity(found.len());
+        for item in found {
+            if let Some(last) = result.last_mut()
+                && item.0 < last.1
+            {
+                last.2 = last.2.max(item.2);
+                continue;
+            }
+            result.push(item);
+        }
+        result
+    }
+}
+
+impl Recognizer for ChineseNameRecognizer {
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn recognizer_type(&self) -> RecognizerType {
+        RecognizerType::Rule
+    }
+
+    fn supported_entities(&self) -> Vec<EntityType> {
+        vec![EntityType::Person]
+    }
+
+    fn analyze(&self, context: &AnalysisContext) -> AnalysisResult {
+        let Some(text) = context.as_str() else {
+            return AnalysisResult::empty(&self.name);
+        };
+
+        let (offsets, chars): (Vec<usize>, Vec<char>) = text.char_indices().unzip();
+        let byte_at = |idx: usize| offsets.get(idx).copied().unwrap_or(text.len());
+
+        let spans = self
+            .find_names(&chars)
+            .into_iter()
+            .map(|(s, e, confidence)| {
+                let mut span = EntitySpan::new(
+                    byte_at(s),
+                    byte_at(e),
+                    EntityType::Person,
+                    confidence,
+                    self.name.clone(),
+                );
+                span.context = Some("rule:chinese_name".to_string());
+                span
+            })
+            .collect();
+
+        AnalysisResult {
+            spans,
+            elapsed_us: 0,
+            recognizer: self.name.clone(),
+        }
+    }
+
+    fn priority(&self) -> i32 {
+        self.priority
+    }
+
+    fn is_enabled(&self) -> bool {
+        self.enabled
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn names(text: &str) -> Vec<String> {
+        let recognizer = ChineseNameRecognizer::default();
+        let context = AnalysisContext::from_text(text.as_bytes());
+        recognizer
+            .analyze(&context)
+            .spans
+            .iter()
+            .map(|s| s.text_str(text).to_string())
+            .collect()
+    }
+
+    #[test]
+    fn test_prefix_cue() {
+        assert_eq!(names("姓名：张三电话13800138000"), vec!["张三"]);
+        assert_eq!(names("联系人: 李建国"), vec!["李建国"]);
+    }
+
+    #[test]
+    fn test_suffix_title() {
+        assert_eq!(names("请王建国先生到前台"), vec!["王建国"]);
+        assert_eq!(names("李娜同学获得一等奖"), vec!["李娜"]);
+    }
+
+    #[test]
+    fn test_compound_surname() {
+        assert_eq!(names("收件人：欧阳娜娜"), vec!["欧阳娜娜"]);
+        assert_eq!(names("感谢诸葛亮先生"), vec!["诸葛亮"]);
+    }
+
+    #[test]
+    fn test_particle_disambiguation() {
+        assert_eq!(names("我和张伟先生见面"), vec!["张伟"]);
+    }
+
+    #[test]
+    fn test_negative_words() {
+        assert!(names("全体同学请注意").is_empty());
+        assert!(names("他是我的高中同学").is_empty());
+        assert!(names("各位老师好").is_empty());
+    }
+
+    #[test]
+    fn test_no_cue_no_match() {
+        assert!(names("张伟今天来了").is_empty());
+    }
+
+    #[test]
+    fn test_entity_type_and_offsets() {
+        let text = "客户：赵敏";
+        let recognizer = ChineseNameRecognizer::default();
+        let result = recognizer.analyze(&AnalysisContext::from_text(text.as_bytes()));
+        assert_eq!(result.spans.len(), 1);
+        assert_eq!(result.spans[0].entity_type, EntityType::Person);
+        assert_eq!(&text[result.spans[0].start..result.spans[0].end], "赵敏");
+    }
+}
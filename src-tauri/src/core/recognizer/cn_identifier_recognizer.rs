@@ -0,0 +1,365 @@
+//! 中国政务/商务证件识别器
+//!
+//! 覆盖 18 位居民身份证之外的常见证件号码，每类证件产出独立的 `EntityType`：
+//!
+//! | 证件 | 实体类型 | 校验 |
+//! |------|----------|------|
+//! | 统一社会信用代码 | `CreditCode` | GB 32100-2015 校验字符 |
+//! | 护照（普通/外交/公务） | `Passport` | 号段结构 |
+//! | 往来港澳通行证 / 回乡证 / 往来台湾通行证 / 台胞证 | `TravelPermit` | 号段结构，台胞证需上下文 |
+//! | 机动车号牌（含 8 位新能源） | `LicensePlate` | 省份简称 + 发牌机关 + 序号规则 |
+//! | 军官证 / 士兵证 / 警官证 | `MilitaryId` | "X字第NNNNNNNN号" 结构 |
+//!
+//! # 误报控制
+//!
+//! 纯数字候选（无字母的信用代码、8 位台胞证）只在前方窗口出现上下文关键词时产出；
+//! 其余格式有上下文时置信度提升。
+
+use super::checksum_recognizer::ChecksumRecognizer;
+use super::types::*;
+use super::Recognizer;
+use regex::bytes::{Regex, RegexBuilder};
+
+/// 上下文搜索窗口（字节，向前）
+const CONTEXT_WINDOW: usize = 30;
+
+/// 统一社会信用代码字符集（不含 I O S V Z）
+const CREDIT_CODE_CHARSET: &[u8] = b"0123456789ABCDEFGHJKLMNPQRTUWXY";
+
+/// 统一社会信用代码前 17 位加权因子
+const CREDIT_CODE_WEIGHTS: [u32; 17] = [1, 3, 9, 27, 19, 26, 16, 17, 20, 29, 25, 13, 8, 24, 10, 30, 28];
+
+/// 号牌省份简称
+const PLATE_PROVINCES: &str = "京津沪渝冀豫云辽黑湘皖鲁新苏浙赣鄂桂甘晋蒙陕吉闽贵粤青藏川宁琼使领";
+
+// ─────────────────────────────────────────────────────────────────────────────
+// 证件种类
+// ─────────────────────────────────────────────────────────────────────────────
+
+/// 证件种类
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum CnIdKind {
+    CreditCode,
+    Passport,
+    HkMacauPermit,
+    HomeReturnPermit,
+    TaiwanPermit,
+    TaiwanCompatriotPermit,
+    LicensePlate,
+    MilitaryId,
+}
+
+impl CnIdKind {
+    fn entity_type(self) -> EntityType {
+        match self {
+            Self::CreditCode => EntityType::CreditCode,
+            Self::Passport => EntityType::Passport,
+            Self::HkMacauPermit
+            | Self::HomeReturnPermit
+            | Self::TaiwanPermit
+            | Self::TaiwanCompatriotPermit => EntityType::TravelPermit,
+            Self::LicensePlate => EntityType::LicensePlate,
+            Self::MilitaryId => EntityType::MilitaryId,
+        }
+    }
+
+    /// 上下文关键词（小写比较）
+    fn keywords(self) -> &'static [&'static str] {
+        match self {
+            Self::CreditCode => &["信用代码", "社会信用", "税号", "纳税人识别号", "uscc"],
+            Self::Passport => &["护照", "passport"],
+            Self::HkMacauPermit => &["通行证", "港澳", "permit"],
+            Self::HomeReturnPermit => &["回乡证", "来往内地", "通行证"],
+            Self::TaiwanPermit => &["通行证", "往来台湾", "入台"],
+            Self::TaiwanCompatriotPermit => &["台胞证", "台湾居民", "来往大陆"],
+            Self::LicensePlate => &["车牌", "号牌", "牌照", "车辆"],
+            Self::MilitaryId => &["军官证", "士兵证", "警官证", "证件"],
+        }
+    }
+
+    /// 无上下文时是否仍然产出
+    fn requires_context(self) -> bool {
+        matches!(self, Self::TaiwanCompatriotPermit)
+    }
+
+    /// 证件子类型（写入 `id_kind` 元数据）
+    fn label(self) -> &'static str {
+        match self {
+            Self::CreditCode => "USCC",
+            Self::Passport => "PASSPORT",
+            Self::HkMacauPermit => "HK_MACAU_PERMIT",
+            Self::HomeReturnPermit => "HOME_RETURN_PERMIT",
+            Self::TaiwanPermit => "TAIWAN_PERMIT",
+            Self::TaiwanCompatriotPermit => "TAIWAN_COMPATRIOT_PERMIT",
+            Self::LicensePlate => "LICENSE_PLATE",
+            Self::MilitaryId => "MILITARY_ID",
+        }
+    }
+}
+
+/// 编译后的证件模式
+struct IdPattern {
+    kind: CnIdKind,
+    re: Regex,
+}
+
+// ─────────────────────────────────────────────────────────────────────────────
+// 中国证件识别器
+// ─────────────────────────────────────────────────────────────────────────────
+
+/// 中国政务/商务证件识别器
+pub struct CnIdentifierRecognizer {
+    /// 识别器名称
+    name: String,
+    /// 是否启用
+    enabled: bool,
+    /// 识别器优先级
+    priority: i32,
+    /// 证件模式（按声明顺序匹配，先匹配者占位）
+    patterns: Vec<IdPattern>,
+}
+
+impl Default for CnIdentifierRecognizer {
+    fn default() -> Self {
+        Self::new(None, None)
+    }
+}
+
+impl CnIdentifierRecognizer {
+    /// 创建中国证件识别器
+    ///
+    /// # 参数
+    ///
+    /// - `name`: 识别器名称（默认 "cn_identifier_recognizer"）
+    /// - `priority`: 识别器优先级（默认 95，校验通过的结果优先于正则粗匹配）
+    pub fn new(name: Option<&str>, priority: Option<i32>) -> Self {
+        let plate = format!(
+            "[{}][A-HJ-NP-Z][·• ]?(?:[DF][A-HJ-NP-Z0-9][0-9]{{4}}|[0-9]{{5}}[DF]|[A-HJ-NP-Z0-9]{{4}}(?:[A-HJ-NP-Z0-9]|挂|学|警|港|澳))",
+            PLATE_PROVINCES
+        );
+        let sources: Vec<(CnIdKind, String)> = vec![
+            (CnIdKind::MilitaryId, "(?:军|兵|士|文|职|海|空|参|政|后|装|北|沈|兰|济|南|广|成|武|警)字第[0-9]{6,8}号".to_string()),
+            (CnIdKind::LicensePlate, plate),
+            (CnIdKind::CreditCode, "[0-9A-HJ-NPQRTUWXY]{2}[0-9]{6}[0-9A-HJ-NPQRTUWXY]{10}".to_string()),
+            (CnIdKind::Passport, "(?:[EG][0-9]{8}|E[A-HJ-NP-Z][0-9]{7}|[DSP]E[0-9]{7})".to_string()),
+            (CnIdKind::HkMacauPermit, "(?:C[0-9A-HJ-NP-Z][0-9]{7}|W[0-9]{8})".to_string()),
+            (CnIdKind::HomeReturnPermit, "[HM][0-9]{8}(?:[0-9]{2})?".to_string()),
+            (CnIdKind::TaiwanPermit, "L[0-9]{8}".to_string()),
+            (CnIdKind::TaiwanCompatriotPermit, "[0-9]{8}".to_string()),
+        ];
+
+        let patterns = sources
+            .into_iter()
+            .map(|(kind, source)| IdPattern {
+                kind,
+                re: RegexBuilder::new(&source)
+                    .unicode(true)
+                    .build()
+                    .expect("invalid identifier regex"),
+            })
+            .collect();
+
+        Self {
+            name: name.unwrap_or("cn_identifier_recognizer").to_string(),
+            enabled: true,
+            priority: priority.unwrap_or(95),
+            patterns,
+        }
+    }
+
+    /// 验证统一社会信用代码 (GB 32100-2015)
+    ///
+    /// 校验字符 = 31 - (Σ 字符值 × 权重 mod 31)，结果为 31 时取 0。
+    pub fn validate_credit_code(code: &str) -> bool {
+        let bytes = code.as_bytes();
+        if bytes.len() != 18 {
+            return false;
+        }
+        let value = |b: u8| CREDIT_CODE_CHARSET.iter().position(|&c| c == b).map(|p| p as u32);
+
+        let mut sum = 0;
+        for (b, w) in bytes.iter().take(17).zip(CREDIT_CODE_WEIGHTS) {
+            match value(*b) {
+                Some(v) => sum += v * w,
+                None => return false,
+            }
+        }
+        let expected = (31 - sum % 31) % 31;
+        value(bytes[17]) == Some(expected)
+    }
+
+    /// 结构之外的附加校验
+    fn validate(kind: CnIdKind, candidate: &str) -> bool {
+        match kind {
+            // 能通过身份证校验的 18 位纯数字交给身份证规则
+            CnIdKind::CreditCode => {
+                Self::validate_credit_code(candidate)
+                    && !ChecksumRecognizer::validate_chinese_id(candidate)
+            }
+            _ => true,
+        }
+    }
+
+    /// 候选前后不能紧贴 ASCII 字母数字（避免从长串中间截取）
+    fn is_bounded(text: &[u8], start: usize, end: usize) -> bool {
+        let before = start == 0 || !text[start - 1].is_ascii_alphanumeric();
+        let after = end >= text.len() || !text[end].is_ascii_alphanumeric();
+        before && after
+    }
+
+    /// 前方窗口内是否出现上下文关键词
+    fn has_context(text: &[u8], start: usize, keywords: &[&str]) -> bool {
+        let window_start = start.saturating_sub(CONTEXT_WINDOW);
+        let window = String::from_utf8_lossy(&text[window_start..start]).to_lowercase();
+        keywords.iter().any(|k| window.contains(k))
+    }
+}
+
+impl Recognizer for CnIdentifierRecognizer {
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn recognizer_type(&self) -> RecognizerType {
+        RecognizerType::Rule
+    }
+
+    fn supported_entities(&self) -> Vec<EntityType> {
+        vec![
+            EntityType::CreditCode,
+            EntityType::Passport,
+            EntityType::TravelPermit,
+            EntityType::LicensePlate,
+            EntityType::MilitaryId,
+        ]
+    }
+
+    fn analyze(&self, context: &AnalysisContext) -> AnalysisResult {
+        let text = context.text;
+        let mut spans: Vec<EntitySpan> = Vec::new();
+
+        for pattern in &self.patterns {
+            for m in pattern.re.find_iter(text) {
+                let (start, end) = (m.start(), m.end());
+                if !Self::is_bounded(text, start, end)
+                    || spans.iter().any(|s| s.start < end && start < s.end)
+                {
+                    continue;
+                }
+
+                let candidate = std::str::from_utf8(m.as_bytes()).unwrap_or_default();
+                if !Self::validate(pattern.kind, candidate) {
+                    continue;
+                }
+
+                let with_context = Self::has_context(text, start, pattern.kind.keywords());
+                // 纯数字的信用代码与台胞证一样需要上下文支撑
+                let digits_only = candidate.bytes().all(|b| b.is_ascii_digit());
+                if !with_context
+                    && (pattern.kind.requires_context()
+                        || (pattern.kind == CnIdKind::CreditCode && digits_only))
+                {
+                    continue;
+                }
+
+                let confidence = if with_context { 0.95 } else { 0.85 };
+                spans.push(
+                    EntitySpan::new(start, end, pattern.kind.entity_type(), confidence, self.name.clone())
+                        .with_metadata("id_kind", pattern.kind.label()),
+                );
+            }
+        }
+
+        spans.sort_by_key(|s| s.start);
+        AnalysisResult {
+            spans,
+            elapsed_us: 0,
+            recognizer: self.name.clone(),
+        }
+    }
+
+    fn priority(&self) -> i32 {
+        self.priority
+    }
+
+    fn is_enabled(&self) -> bool {
+        self.enabled
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn detect(text: &str) -> Vec<(String, EntityType)> {
+        CnIdentifierRecognizer::default()
+            .analyze(&AnalysisContext::from_text(text.as_bytes()))
+            .spans
+            .iter()
+            .map(|s| (s.text_str(text).to_string(), s.entity_type.clone()))
+            .collect()
+    }
+
+    #[test]
+    fn test_credit_code_checksum() {
+        assert!(CnIdentifierRecognizer::validate_credit_code("91350100M000100Y43"));
+        assert!(CnIdentifierRecognizer::validate_credit_code("91110000600037341L"));
+        assert!(!CnIdentifierRecognizer::validate_credit_code("91350100M000100Y44"));
+        assert!(!CnIdentifierRecognizer::validate_credit_code("91350100M000100YI3")); // 非法字符 I
+
+        assert_eq!(
+            detect("统一社会信用代码：91350100M000100Y43"),
+            vec![("91350100M000100Y43".to_string(), EntityType::CreditCode)]
+        );
+    }
+
+    #[test]
+    fn test_credit_code_digits_only_needs_context() {
+        let code = "911100006000300020";
+        assert!(CnIdentifierRecognizer::validate_credit_code(code));
+        assert!(detect(code).is_empty());
+        assert_eq!(detect(&format!("税号 {}", code)).len(), 1);
+    }
+
+    #[test]
+    fn test_passport_and_permits() {
+        assert_eq!(detect("护照号 E12345678")[0].1, EntityType::Passport);
+        assert_eq!(detect("护照 EA1234567")[0].1, EntityType::Passport);
+        assert_eq!(detect("港澳通行证 C12345678")[0].1, EntityType::TravelPermit);
+        assert_eq!(detect("回乡证 H12345678")[0].1, EntityType::TravelPermit);
+        assert_eq!(detect("通行证 L12345678")[0].1, EntityType::TravelPermit);
+    }
+
+    #[test]
+    fn test_taiwan_compatriot_permit_needs_context() {
+        assert!(detect("订单 12345678").is_empty());
+        assert_eq!(
+            detect("台胞证号码：12345678"),
+            vec![("12345678".to_string(), EntityType::TravelPermit)]
+        );
+    }
+
+    #[test]
+    fn test_license_plates() {
+        assert_eq!(detect("车牌京A12345已登记")[0].0, "京A12345");
+        assert_eq!(detect("粤B·D12345")[0].0, "粤B·D12345");
+        assert_eq!(detect("沪AD12345 新能源")[0].0, "沪AD12345");
+        assert_eq!(detect("苏E12345F")[0].0, "苏E12345F");
+        assert_eq!(detect("川A1234学")[0].0, "川A1234学");
+        assert!(detect("京I12345").is_empty()); // 发牌机关不含 I
+    }
+
+    #[test]
+    fn test_military_id() {
+        assert_eq!(
+            detect("军官证：南字第12345678号"),
+            vec![("南字第12345678号".to_string(), EntityType::MilitaryId)]
+        );
+    }
+
+    #[test]
+    fn test_not_cut_from_longer_token() {
+        assert!(detect("XE123456789").is_empty());
+        assert!(detect("sessionE12345678abc").is_empty());
+    }
+}
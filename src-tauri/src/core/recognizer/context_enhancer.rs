@@ -76,6 +76,10 @@ impl ContextEnhancer {
                 keywords: vec!["+86".to_string(), "86".to_string()],
                 boost: 0.3,
             },
+            ContextKeyword {
+                keywords: vec!["電話".to_string(), "携帯".to_string(), "전화".to_string(), "휴대폰".to_string(), "연락처".to_string()],
+                boost: 0.2,
+            },
         ]);
 
         // 身份证相关上下文
@@ -84,6 +88,10 @@ impl ContextEnhancer {
                 keywords: vec!["身份证".to_string(), "idcard".to_string(), "证件".to_string(), "ID".to_string()],
                 boost: 0.3,
             },
+            ContextKeyword {
+                keywords: vec!["マイナンバー".to_string(), "個人番号".to_string(), "주민등록번호".to_string(), "주민번호".to_string()],
+                boost: 0.3,
+            },
         ]);
 
         // 银行卡相关上下文
@@ -114,6 +122,14 @@ impl ContextEnhancer {
             },
         ]);
 
+        // 邮编相关上下文
+        keyword_map.insert(EntityType::PostalCode, vec![
+            ContextKeyword {
+                keywords: vec!["郵便番号".to_string(), "〒".to_string(), "우편번호".to_string(), "zip".to_string(), "postal".to_string(), "邮编".to_string()],
+                boost: 0.2,
+            },
+        ]);
+
         // 密钥相关上下文
         keyword_map.insert(EntityType::ApiKey, vec![
             ContextKeyword {
@@ -266,4 +282,17 @@ mod tests {
 
         assert!(enhanced.confidence > 0.8);
     }
+
+    #[test]
+    fn test_enhance_jp_kr_with_context() {
+        let enhancer = ContextEnhancer::new();
+
+        let text = "전화: 010-1234-5678";
+        let span = EntitySpan::new(8, 21, EntityType::Phone, 0.6, "test");
+        assert!(enhancer.enhance_span(&span, text).confidence > 0.6);
+
+        let text = "郵便番号 100-0001";
+        let span = EntitySpan::new(13, 21, EntityType::PostalCode, 0.6, "test");
+        assert!(enhancer.enhance_span(&span, text).confidence > 0.6);
+    }
 }
@@ -0,0 +1,291 @@
+//! 加密货币钱包地址识别器
+//!
+//! 支持以下地址格式，全部经过校验和验证，随机的 Base58 / 十六进制串不会被误报：
+//!
+//! | 链 | 格式 | 校验 |
+//! |----|------|------|
+//! | BTC | P2PKH `1…` / P2SH `3…`（含测试网 `m`/`n`/`2`） | Base58Check（双 SHA-256） |
+//! | BTC | SegWit `bc1q…` / Taproot `bc1p…`（含 `tb1`） | bech32 / bech32m（BIP-173 / BIP-350） |
+//! | ETH | `0x` + 40 位十六进制 | 大小写混合时验证 EIP-55 校验和 |
+//! | TRON | `T…` 34 位 | Base58Check，版本字节 0x41 |
+//!
+//! 产出 `EntityType::CryptoAddress`，并在 `chain` / `address_kind` 元数据中标明链与格式，
+//! 脱敏策略可通过 `crypto_address[chain=ETH]` 等键单独配置。
+
+use super::types::*;
+use super::Recognizer;
+use regex::bytes::Regex;
+use sha2::{Digest, Sha256};
+use sha3::Keccak256;
+
+/// 地址种类
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum AddressKind {
+    BtcBase58,
+    BtcSegwit,
+    Eth,
+    Tron,
+}
+
+/// 编译后的地址模式
+struct AddressPattern {
+    kind: AddressKind,
+    re: Regex,
+}
+
+/// 加密货币钱包地址识别器
+pub struct CryptoWalletRecognizer {
+    /// 识别器名称
+    name: String,
+    /// 是否启用
+    enabled: bool,
+    /// 识别器优先级
+    priority: i32,
+    /// 地址模式
+    patterns: Vec<AddressPattern>,
+}
+
+impl Default for CryptoWalletRecognizer {
+    fn default() -> Self {
+        Self::new(None, None)
+    }
+}
+
+impl CryptoWalletRecognizer {
+    /// 创建钱包地址识别器
+    ///
+    /// # 参数
+    ///
+    /// - `name`: 识别器名称（默认 "crypto_recognizer"）
+    /// - `priority`: 识别器优先级（默认 95）
+    pub fn new(name: Option<&str>, priority: Option<i32>) -> Self {
+        let sources: [(AddressKind, &str); 4] = [
+            (AddressKind::BtcSegwit, r"(?:bc|tb)1[02-9ac-hj-np-z]{8,87}|(?:BC|TB)1[02-9AC-HJ-NP-Z]{8,87}"),
+            (AddressKind::Eth, r"0x[0-9a-fA-F]{40}"),
+            (AddressKind::Tron, r"T[1-9A-HJ-NP-Za-km-z]{33}"),
+            (AddressKind::BtcBase58, r"[123mn][1-9A-HJ-NP-Za-km-z]{25,34}"),
+        ];
+
+        Self {
+            name: name.unwrap_or("crypto_recognizer").to_string(),
+            enabled: true,
+            priority: priority.unwrap_or(95),
+            patterns: sources
+                .into_iter()
+                .map(|(kind, src)| AddressPattern {
+                    kind,
+                    re: Regex::new(src).expect("invalid crypto address regex"),
+                })
+                .collect(),
+        }
+    }
+
+    // ─── 校验函数 ───
+
+    /// Base58Check 解码，返回版本字节（校验和错误或长度不符时返回 None）
+    fn base58check_version(address: &str) -> Option<u8> {
+        let bytes = bs58::decode(address).into_vec().ok()?;
+        if bytes.len() != 25 {
+            return None;
+        }
+        let (payload, checksum) = bytes.split_at(21);
+        let hash = Sha256::digest(Sha256::digest(payload));
+        (hash[..4] == *checksum).then_some(payload[0])
+    }
+
+    /// 比特币 Base58 地址，返回地址格式
+    pub fn validate_btc_base58(address: &str) -> Option<&'static str> {
+        match Self::base58check_version(address)? {
+            0x00 | 0x6f => Some("p2pkh"),
+            0x05 | 0xc4 => Some("p2sh"),
+            _ => None,
+        }
+    }
+
+    /// 比特币 SegWit 地址（bech32 用于 v0，bech32m 用于 v1+），返回地址格式
+    pub fn validate_btc_segwit(address: &str) -> Option<&'static str> {
+        let (hrp, version, program) = bech32::segwit::decode(address).ok()?;
+        if !matches!(hrp.to_lowercase().as_str(), "bc" | "tb") {
+            return None;
+        }
+        match (version.to_u8(), program.len()) {
+            (0, 20) => Some("p2wpkh"),
+            (0, 32) => Some("p2wsh"),
+            (1, 32) => Some("p2tr"),
+            _ => Some("segwit"),
+        }
+    }
+
+    /// 波场地址
+    pub fn validate_tron(address: &str) -> bool {
+        Self::base58check_version(address) == Some(0x41)
+    }
+
+    /// 以太坊地址：全小写/全大写视为未带校验和，大小写混合时必须满足 EIP-55
+    ///
+    /// 返回 `Some(true)` 表示通过 EIP-55，`Some(false)` 表示无校验和，`None` 表示校验失败。
+    pub fn validate_eth(address: &str) -> Option<bool> {
+        let hex = address.strip_prefix("0x")?;
+        if hex.len() != 40 || !hex.bytes().all(|b| b.is_ascii_hexdigit()) {
+            return None;
+        }
+        let has_lower = hex.bytes().any(|b| b.is_ascii_lowercase());
+        let has_upper = hex.bytes().any(|b| b.is_ascii_uppercase());
+        if !(has_lower && has_upper) {
+            return Some(false);
+        }
+
+        let hash = Keccak256::digest(hex.to_ascii_lowercase().as_bytes());
+        let checksum_ok = hex.bytes().enumerate().all(|(i, b)| {
+            if !b.is_ascii_alphabetic() {
+                return true;
+            }
+            let nibble = if i % 2 == 0 { hash[i / 2] >> 4 } else { hash[i / 2] & 0x0f };
+            (nibble >= 8) == b.is_ascii_uppercase()
+        });
+        checksum_ok.then_some(true)
+    }
+
+    /// 校验候选并返回 (链, 地址格式, 置信度)
+    fn classify(kind: AddressKind, candidate: &str) -> Option<(&'static str, &'static str, f32)> {
+        match kind {
+            AddressKind::BtcBase58 => Self::validate_btc_base58(candidate).map(|k| ("BTC", k, 0.95)),
+            AddressKind::BtcSegwit => Self::validate_btc_segwit(candidate).map(|k| ("BTC", k, 0.95)),
+            AddressKind::Tron => Self::validate_tron(candidate).then_some(("TRON", "base58", 0.95)),
+            AddressKind::Eth => match Self::validate_eth(candidate)? {
+                true => Some(("ETH", "eip55", 0.95)),
+                // 无校验和的十六进制串可能是其他哈希片段，降低置信度
+                false => Some(("ETH", "hex", 0.8)),
+            },
+        }
+    }
+
+    /// 候选前后不能紧贴 ASCII 字母数字（避免从更长的哈希/密钥中截取）
+    fn is_bounded(text: &[u8], start: usize, end: usize) -> bool {
+        let before = start == 0 || !text[start - 1].is_ascii_alphanumeric();
+        let after = end >= text.len() || !text[end].is_ascii_alphanumeric();
+        before && after
+    }
+}
+
+impl Recognizer for CryptoWalletRecognizer {
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn recognizer_type(&self) -> RecognizerType {
+        RecognizerType::Rule
+    }
+
+    fn supported_entities(&self) -> Vec<EntityType> {
+        vec![EntityType::CryptoAddress]
+    }
+
+    fn analyze(&self, context: &AnalysisContext) -> AnalysisResult {
+        let text = context.text;
+        let mut spans: Vec<EntitySpan> = Vec::new();
+
+        for pattern in &self.patterns {
+            for m in pattern.re.find_iter(text) {
+                let (start, end) = (m.start(), m.end());
+                if !Self::is_bounded(text, start, end)
+                    || spans.iter().any(|s| s.start < end && start < s.end)
+                {
+                    continue;
+                }
+
+                let candidate = std::str::from_utf8(m.as_bytes()).unwrap_or_default();
+                let Some((chain, address_kind, confidence)) = Self::classify(pattern.kind, candidate) else {
+                    continue;
+                };
+                spans.push(
+                    EntitySpan::new(start, end, EntityType::CryptoAddress, confidence, self.name.clone())
+                        .with_metadata("chain", chain)
+                        .with_metadata("address_kind", address_kind),
+                );
+            }
+        }
+
+        spans.sort_by_key(|s| s.start);
+        AnalysisResult {
+            spans,
+            elapsed_us: 0,
+            recognizer: self.name.clone(),
+        }
+    }
+
+    fn priority(&self) -> i32 {
+        self.priority
+    }
+
+    fn is_enabled(&self) -> bool {
+        self.enabled
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn detect(text: &str) -> Vec<(String, String)> {
+        CryptoWalletRecognizer::default()
+            .analyze(&AnalysisContext::from_text(text.as_bytes()))
+            .spans
+            .iter()
+            .map(|s| (s.text_str(text).to_string(), s.meta("address_kind").unwrap_or_default().to_string()))
+            .collect()
+    }
+
+    #[test]
+    fn test_btc_base58check() {
+        assert_eq!(CryptoWalletRecognizer::validate_btc_base58("1BvBMSEYstWetqTFn5Au4m4GFg7xJaNVN2"), Some("p2pkh"));
+        assert_eq!(CryptoWalletRecognizer::validate_btc_base58("3J98t1WpEZ73CNmQviecrnyiWrnqRhWNLy"), Some("p2sh"));
+        // 末位篡改导致校验和失败
+        assert_eq!(CryptoWalletRecognizer::validate_btc_base58("1BvBMSEYstWetqTFn5Au4m4GFg7xJaNVN3"), None);
+
+        assert_eq!(detect("send to 1BvBMSEYstWetqTFn5Au4m4GFg7xJaNVN2 please").len(), 1);
+        assert!(detect("token 1BvBMSEYstWetqTFn5Au4m4GFg7xJaNVN3").is_empty());
+    }
+
+    #[test]
+    fn test_btc_bech32_and_bech32m() {
+        assert_eq!(CryptoWalletRecognizer::validate_btc_segwit("bc1qar0srrr7xfkvy5l643lydnw9re59gtzzwf5mdq"), Some("p2wpkh"));
+        assert_eq!(
+            CryptoWalletRecognizer::validate_btc_segwit("bc1py3m7vwnghyne9gnvcjw82j7gqt2rafgdmlmwmqnn3hvcmdm09rjqcgrtxs"),
+            Some("p2tr")
+        );
+        assert_eq!(CryptoWalletRecognizer::validate_btc_segwit("bc1qar0srrr7xfkvy5l643lydnw9re59gtzzwf5mdx"), None);
+
+        let found = detect("BTC: bc1qar0srrr7xfkvy5l643lydnw9re59gtzzwf5mdq");
+        assert_eq!(found, vec![("bc1qar0srrr7xfkvy5l643lydnw9re59gtzzwf5mdq".to_string(), "p2wpkh".to_string())]);
+    }
+
+    #[test]
+    fn test_eth_eip55() {
+        assert_eq!(CryptoWalletRecognizer::validate_eth("0x5aAeb6053F3E94C9b9A09f33669435E7Ef1BeAed"), Some(true));
+        assert_eq!(CryptoWalletRecognizer::validate_eth("0x5aaeb6053f3e94c9b9a09f33669435e7ef1beaed"), Some(false));
+        // 大小写被改动后 EIP-55 失败
+        assert_eq!(CryptoWalletRecognizer::validate_eth("0x5AAeb6053F3E94C9b9A09f33669435E7Ef1BeAed"), None);
+
+        assert_eq!(detect("to=0x5aAeb6053F3E94C9b9A09f33669435E7Ef1BeAed")[0].1, "eip55");
+        // 64 位交易哈希不会被截取为地址
+        assert!(detect("tx 0x5aaeb6053f3e94c9b9a09f33669435e7ef1beaed5aaeb6053f3e94c9b9a09f33").is_empty());
+    }
+
+    #[test]
+    fn test_tron() {
+        assert!(CryptoWalletRecognizer::validate_tron("TR7NHqjeKQxGTCi8q8ZY4pL8otSzgjLj6t"));
+        assert!(!CryptoWalletRecognizer::validate_tron("TR7NHqjeKQxGTCi8q8ZY4pL8otSzgjLj6u"));
+        let spans = CryptoWalletRecognizer::default()
+            .analyze(&AnalysisContext::from_text("USDT TR7NHqjeKQxGTCi8q8ZY4pL8otSzgjLj6t".as_bytes()))
+            .spans;
+        assert_eq!(spans.len(), 1);
+        assert_eq!(spans[0].entity_type, EntityType::CryptoAddress);
+        assert_eq!(spans[0].meta("chain"), Some("TRON"));
+    }
+
+    #[test]
+    fn test_random_base58_not_flagged() {
+        assert!(detect("id 3kTmWqZpX9bYc7dR2fGhJ4nLs8vUe6aQoP").is_empty());
+    }
+}
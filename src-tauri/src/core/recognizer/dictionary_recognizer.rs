@@ -0,0 +1,502 @@
+//! 外部大字典识别器（磁盘 FST + 内存映射）
+//!
+//! `AhoCorasickRecognizer` 从 YAML 规则构建自动机，适合数千到数万条；
+//! 员工名册、产品代号这类几十万到数百万条的名单改用外部词表：
+//!
+//! 1. 词表为纯文本（每行一个词，`#` 开头为注释），在 `custom/dictionaries.yaml` 中登记
+//! 2. 首次加载时编译为有序 FST 写入缓存目录，之后启动直接内存映射，无需重新构建
+//! 3. 每个字典有 `.stamp` 戳记（源文件大小、修改时间、大小写选项、当前 FST 文件名），
+//!    源词表变化时只重建该字典，其余字典原样复用
+//!
+//! 重建总是写入新文件名再切换戳记，旧引擎仍映射着的旧 FST 不会被覆盖
+//! （Windows 下被映射的文件无法替换），旧文件在不再占用后清理。
+//!
+//! 匹配时从每个候选起点沿 FST 逐字节前进，取最长命中（LeftmostLongest）。
+//! `case_insensitive` 在构建与匹配两侧统一转小写；`whole_word` / `unicode_boundary`
+//! 与规则的同名开关一致（见 [`WordBoundary`]）：前者要求命中两端不与 ASCII 字母数字相连，
+//! 后者按 Unicode 字母数字判断，CJK 文字之间仍视为边界。
+
+use super::types::*;
+use super::Recognizer;
+use crate::common::errors::{AppError, AppResult};
+use fst::raw::Fst;
+use fst::SetBuilder;
+use log::{info, warn};
+use memmap2::{Mmap, MmapOptions};
+use serde::{Deserialize, Serialize};
+use std::fs::{self, File};
+use std::io::{BufRead, BufReader, BufWriter};
+use std::path::{Path, PathBuf};
+use std::time::UNIX_EPOCH;
+
+/// 戳记格式版本（匹配/构建逻辑变化时递增，强制重建）
+const STAMP_VERSION: u32 = 1;
+
+fn default_true() -> bool {
+    true
+}
+
+fn default_entity_type() -> String {
+    "CUSTOM".to_string()
+}
+
+/// 外部字典登记项
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DictionarySpec {
+    /// 字典名称（唯一标识，同时作为缓存文件名）
+    pub name: String,
+    /// 词表路径（相对路径以 custom 目录为基准）
+    pub source: String,
+    /// 实体类型标签（如 `PERSON`）
+    #[serde(default = "default_entity_type")]
+    pub entity_type: String,
+    /// 替换掩码，为空时使用实体类型默认标签
+    #[serde(default)]
+    pub mask: Option<String>,
+    /// 忽略大小写
+    #[serde(default)]
+    pub case_insensitive: bool,
+    /// 整词匹配
+    #[serde(default = "default_true")]
+    pub whole_word: bool,
+    /// 按 Unicode 词边界整词匹配（隐含 `whole_word`）
+    #[serde(default)]
+    pub unicode_boundary: bool,
+    /// 是否启用
+    #[serde(default = "default_true")]
+    pub enabled: bool,
+}
+
+/// 用于 YAML 存储的包装结构
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct DictionaryFile {
+    pub dictionaries: Vec<DictionarySpec>,
+}
+
+/// 编译戳记：与源文件状态一致时复用缓存
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+struct DictionaryStamp {
+    version: u32,
+    source_len: u64,
+    source_mtime_secs: u64,
+    source_mtime_nanos: u32,
+    case_insensitive: bool,
+    terms: usize,
+    /// 当前生效的 FST 文件名（位于缓存目录）
+    fst_file: String,
+}
+
+impl DictionaryStamp {
+    fn of_source(source: &Path, case_insensitive: bool) -> AppResult<Self> {
+        let meta = fs::metadata(source)?;
+        let mtime = meta.modified()?.duration_since(UNIX_EPOCH).unwrap_or_default();
+        Ok(Self {
+            version: STAMP_VERSION,
+            source_len: meta.len(),
+            source_mtime_secs: mtime.as_secs(),
+            source_mtime_nanos: mtime.subsec_nanos(),
+            case_insensitive,
+            terms: 0,
+            fst_file: String::new(),
+        })
+    }
+
+    /// 源文件状态与选项一致即视为未变化
+    fn matches(&self, other: &Self) -> bool {
+        let key = |s: &Self| (s.version, s.source_len, s.source_mtime_secs, s.source_mtime_nanos, s.case_insensitive);
+        key(self) == key(other)
+    }
+}
+
+/// 单个字典的准备结果
+#[derive(Debug, Clone, Serialize)]
+pub struct DictionaryBuildReport {
+    pub name: String,
+    /// 本次是否重新编译
+    pub rebuilt: bool,
+    /// 词条数
+    pub terms: usize,
+    /// 失败原因（失败的字典不会加载）
+    pub error: Option<String>,
+}
+
+/// 已内存映射的字典
+pub struct MappedDictionary {
+    name: String,
+    entity_type: EntityType,
+    mask: Option<String>,
+    case_insensitive: bool,
+    boundary: WordBoundary,
+    fst: Fst<Mmap>,
+}
+
+impl MappedDictionary {
+    /// 词条数
+    pub fn len(&self) -> usize {
+        self.fst.len()
+    }
+
+    /// 是否为空
+    pub fn is_empty(&self) -> bool {
+        self.fst.len() == 0
+    }
+
+    /// 字典名称
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// 内存映射已编译的 FST
+    pub fn open(spec: &DictionarySpec, fst_path: &Path) -> AppResult<Self> {
+        let file = File::open(fst_path)?;
+        let mmap = unsafe { MmapOptions::new().map(&file)? };
+        let fst = Fst::new(mmap).map_err(|e| AppError::Config(format!("字典 {} 的 FST 无效: {}", spec.name, e)))?;
+        Ok(Self {
+            name: spec.name.clone(),
+            entity_type: EntityType::from_label(&spec.entity_type),
+            mask: spec.mask.clone().filter(|m| !m.is_empty()),
+            case_insensitive: spec.case_insensitive,
+            boundary: WordBoundary::from_flags(spec.whole_word, spec.unicode_boundary),
+            fst,
+        })
+    }
+
+    /// 从 `start` 开始沿 FST 前进，返回最长命中的结束位置
+    fn longest_match(&self, text: &[u8], start: usize) -> Option<usize> {
+        let mut node = self.fst.root();
+        let mut best = None;
+        for (i, &b) in text[start..].iter().enumerate() {
+            let Some(t) = node.find_input(b) else { break };
+            node = self.fst.node(node.transition_addr(t));
+            let end = start + i + 1;
+            if node.is_final() && self.boundary.is_bounded(text, end, end) {
+                best = Some(end);
+            }
+        }
+        best
+    }
+
+    /// 在文本中查找全部非重叠命中
+    fn find_all(&self, text: &[u8]) -> Vec<(usize, usize)> {
+        let mut matches = Vec::new();
+        let mut pos = 0;
+        while pos < text.len() {
+            let candidate = !is_continuation(text[pos]) && self.boundary.is_bounded(text, pos, pos);
+            if candidate && let Some(end) = self.longest_match(text, pos) {
+                matches.push((pos, end));
+                pos = end;
+                continue;
+            }
+            pos += 1;
+        }
+        matches
+    }
+}
+
+fn is_continuation(b: u8) -> bool {
+    b & 0xC0 == 0x80
+}
+
+// ─── 编译 ───
+
+/// 解析词表路径（相对路径以 `base_dir` 为基准）
+pub fn resolve_source(spec: &DictionarySpec, base_dir: &Path) -> PathBuf {
+    let source = Path::new(&spec.source);
+    if source.is_absolute() { source.to_path_buf() } else { base_dir.join(source) }
+}
+
+/// 编译字典（源未变化且非强制时复用缓存），返回 (FST 路径, 是否重建, 词条数)
+pub fn compile_dictionary(spec: &DictionarySpec, source: &Path, cache_dir: &Path, force: bool) -> AppResult<(PathBuf, bool, usize)> {
+    if spec.name.is_empty() || spec.name.contains(['/', '\\', '.']) {
+        return Err(AppError::Config(format!("字典名称无效: {:?}", spec.name)));
+    }
+    fs::create_dir_all(cache_dir)?;
+    let stamp_path = cache_dir.join(format!("{}.stamp", spec.name));
+    let mut stamp = DictionaryStamp::of_source(source, spec.case_insensitive)?;
+
+    let cached = fs::read_to_string(&stamp_path)
+        .ok()
+        .and_then(|s| serde_yaml::from_str::<DictionaryStamp>(&s).ok());
+    if !force
+        && let Some(cached) = cached.as_ref().filter(|c| c.matches(&stamp))
+        && cache_dir.join(&cached.fst_file).is_file()
+    {
+        return Ok((cache_dir.join(&cached.fst_file), false, cached.terms));
+    }
+
+    let mut terms: Vec<String> = Vec::new();
+    for line in BufReader::new(File::open(source)?).lines() {
+        let line = line?;
+        let term = line.trim();
+        if term.is_empty() || term.starts_with('#') {
+            continue;
+        }
+        terms.push(if spec.case_insensitive { term.to_lowercase() } else { term.to_string() });
+    }
+    terms.sort_unstable();
+    terms.dedup();
+
+    stamp.fst_file = format!("{}-{}.fst", spec.name, uuid::Uuid::new_v4().simple());
+    let fst_path = cache_dir.join(&stamp.fst_file);
+    let tmp_path = cache_dir.join(format!("{}.tmp", stamp.fst_file));
+    let fst_error = |e: fst::Error| AppError::Internal(format!("字典 {} 编译失败: {}", spec.name, e));
+    let mut builder = SetBuilder::new(BufWriter::new(File::create(&tmp_path)?)).map_err(fst_error)?;
+    for term in &terms {
+        builder.insert(term).map_err(fst_error)?;
+    }
+    builder.finish().map_err(fst_error)?;
+    fs::rename(&tmp_path, &fst_path)?;
+
+    stamp.terms = terms.len();
+    let stamp_yaml = serde_yaml::to_string(&stamp).map_err(|e| AppError::Config(e.to_string()))?;
+    fs::write(&stamp_path, stamp_yaml)?;
+    remove_stale_files(cache_dir, &spec.name, &stamp.fst_file);
+    info!("📖 字典 {} 已编译: {} 个词条", spec.name, terms.len());
+    Ok((fst_path, true, terms.len()))
+}
+
+/// 清理该字典的旧 FST（仍被映射而删除失败的留待下次重建清理）
+fn remove_stale_files(cache_dir: &Path, name: &str, current: &str) {
+    let prefix = format!("{}-", name);
+    let Ok(entries) = fs::read_dir(cache_dir) else { return };
+    for entry in entries.filter_map(Result::ok) {
+        let file_name = entry.file_name().to_string_lossy().into_owned();
+        let owned = file_name
+            .strip_prefix(&prefix)
+            .and_then(|rest| rest.strip_suffix(".fst"))
+            .is_some_and(|id| id.len() == 32 && id.chars().all(|c| c.is_ascii_hexdigit()));
+        if owned && file_name != current {
+            let _ = fs::remove_file(entry.path());
+        }
+    }
+}
+
+/// 编译（按需）并内存映射全部启用的字典
+///
+/// 单个字典失败不影响其余字典，失败原因记录在报告中。
+pub fn prepare_dictionaries(
+    specs: &[DictionarySpec],
+    base_dir: &Path,
+    cache_dir: &Path,
+    force: bool,
+) -> (Vec<MappedDictionary>, Vec<DictionaryBuildReport>) {
+    let mut dictionaries = Vec::new();
+    let mut reports = Vec::new();
+    for spec in specs.iter().filter(|s| s.enabled) {
+        let source = resolve_source(spec, base_dir);
+        let result = compile_dictionary(spec, &source, cache_dir, force)
+            .and_then(|(fst_path, rebuilt, terms)| Ok((MappedDictionary::open(spec, &fst_path)?, rebuilt, terms)));
+        match result {
+            Ok((dictionary, rebuilt, terms)) => {
+                dictionaries.push(dictionary);
+                reports.push(DictionaryBuildReport { name: spec.name.clone(), rebuilt, terms, error: None });
+            }
+            Err(e) => {
+                warn!("⚠️ 字典 {} 加载失败: {}", spec.name, e);
+                reports.push(DictionaryBuildReport { name: spec.name.clone(), rebuilt: false, terms: 0, error: Some(e.to_string()) });
+            }
+        }
+    }
+    (dictionaries, reports)
+}
+
+/// 外部大字典识别器
+pub struct DictionaryRecognizer {
+    /// 识别器名称
+    name: String,
+    /// 是否启用
+    enabled: bool,
+    /// 识别器优先级
+    priority: i32,
+    /// 已映射的字典
+    dictionaries: Vec<MappedDictionary>,
+}
+
+impl DictionaryRecognizer {
+    /// 创建外部字典识别器
+    ///
+    /// # 参数
+    ///
+    /// - `name`: 识别器名称（默认 "dictionary_recognizer"）
+    /// - `priority`: 识别器优先级（默认 100，与 YAML 字面量字典一致）
+    /// - `dictionaries`: 已内存映射的字典
+    pub fn new(name: Option<&str>, priority: Option<i32>, dictionaries: Vec<MappedDictionary>) -> Self {
+        Self {
+            name: name.unwrap_or("dictionary_recognizer").to_string(),
+            enabled: true,
+            priority: priority.unwrap_or(100),
+            dictionaries,
+        }
+    }
+
+    /// 词条总数
+    pub fn entry_count(&self) -> usize {
+        self.dictionaries.iter().map(MappedDictionary::len).sum()
+    }
+}
+
+impl Recognizer for DictionaryRecognizer {
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn recognizer_type(&self) -> RecognizerType {
+        RecognizerType::Rule
+    }
+
+    fn supported_entities(&self) -> Vec<EntityType> {
+        self.dictionaries.iter().map(|d| d.entity_type.clone()).collect()
+    }
+
+    fn analyze(&self, context: &AnalysisContext) -> AnalysisResult {
+        let text = context.text;
+        // 折叠文本在所有忽略大小写的字典间共享
+        let folded = if self.dictionaries.iter().any(|d| d.case_insensitive) {
+            std::str::from_utf8(text).ok().map(fold_case)
+        } else {
+            None
+        };
+
+        let mut spans = Vec::new();
+        for dictionary in &self.dictionaries {
+            let ranges: Vec<(usize, usize)> = match (&folded, dictionary.case_insensitive) {
+                (Some((folded_text, offsets)), true) => dictionary
+                    .find_all(folded_text)
+                    .into_iter()
+                    .map(|(s, e)| (offsets[s], offsets[e]))
+                    .filter(|(s, e)| s < e)
+                    .collect(),
+                (None, true) => continue, // 非 UTF-8 文本无法折叠
+                _ => dictionary.find_all(text),
+            };
+            for (start, end) in ranges {
+                let mut span = EntitySpan::new(start, end, dictionary.entity_type.clone(), 1.0, self.name.clone())
+                    .with_metadata("dictionary", dictionary.name.clone());
+                span.mask.clone_from(&dictionary.mask);
+                spans.push(span);
+            }
+        }
+
+        AnalysisResult {
+            spans,
+            elapsed_us: 0,
+            recognizer: self.name.clone(),
+        }
+    }
+
+    fn priority(&self) -> i32 {
+        self.priority
+    }
+
+    fn is_enabled(&self) -> bool {
+        self.enabled
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn temp_dir(tag: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("safemask_dict_{}_{}", tag, uuid::Uuid::new_v4()));
+        fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    fn spec(name: &str, case_insensitive: bool, whole_word: bool) -> DictionarySpec {
+        DictionarySpec {
+            name: name.to_string(),
+            source: format!("{}.txt", name),
+            entity_type: "PERSON".to_string(),
+            mask: Some("<EMPLOYEE>".to_string()),
+            case_insensitive,
+            whole_word,
+            unicode_boundary: false,
+            enabled: true,
+        }
+    }
+
+    fn matches(recognizer: &DictionaryRecognizer, text: &str) -> Vec<String> {
+        recognizer
+            .analyze(&AnalysisContext::from_text(text.as_bytes()))
+            .spans
+            .iter()
+            .map(|s| text[s.start..s.end].to_string())
+            .collect()
+    }
+
+    #[test]
+    fn test_case_folding_and_word_boundary() {
+        let dir = temp_dir("match");
+        fs::write(dir.join("staff.txt"), "# 员工名册\nAlice Wong\nBob\n欧阳娜娜\n").unwrap();
+        let (dicts, reports) = prepare_dictionaries(&[spec("staff", true, true)], &dir, &dir.join("cache"), false);
+        assert!(reports[0].rebuilt && reports[0].error.is_none());
+        assert_eq!(reports[0].terms, 3);
+
+        let recognizer = DictionaryRecognizer::new(None, None, dicts);
+        assert_eq!(matches(&recognizer, "cc ALICE WONG, bob 和欧阳娜娜"), vec!["ALICE WONG", "bob", "欧阳娜娜"]);
+        // 整词：Bobby 不命中
+        assert!(matches(&recognizer, "Bobby").is_empty());
+        fs::remove_dir_all(dir).ok();
+    }
+
+    #[test]
+    fn test_unicode_boundary() {
+        let dir = temp_dir("unicode");
+        fs::write(dir.join("names.txt"), "Zoë\n欧阳娜娜\n").unwrap();
+        let unicode = DictionarySpec { unicode_boundary: true, ..spec("names", false, false) };
+        let text = "Zoëlle, Zoë 和欧阳娜娜";
+
+        let (dicts, _) = prepare_dictionaries(&[spec("names", false, true)], &dir, &dir.join("ascii"), false);
+        // ASCII 边界不认识 `ë`，`Zoëlle` 的前缀也会命中
+        assert_eq!(matches(&DictionaryRecognizer::new(None, None, dicts), text), vec!["Zoë", "Zoë", "欧阳娜娜"]);
+        let (dicts, _) = prepare_dictionaries(&[unicode], &dir, &dir.join("unicode"), false);
+        assert_eq!(matches(&DictionaryRecognizer::new(None, None, dicts), text), vec!["Zoë", "欧阳娜娜"]);
+        fs::remove_dir_all(dir).ok();
+    }
+
+    #[test]
+    fn test_case_sensitive_substring() {
+        let dir = temp_dir("substr");
+        fs::write(dir.join("codes.txt"), "PRJ-ORION\nPRJ-ORIONX\n").unwrap();
+        let (dicts, _) = prepare_dictionaries(&[spec("codes", false, false)], &dir, &dir.join("cache"), false);
+        let recognizer = DictionaryRecognizer::new(None, None, dicts);
+        // 最长命中
+        assert_eq!(matches(&recognizer, "xPRJ-ORIONXy prj-orion"), vec!["PRJ-ORIONX"]);
+        fs::remove_dir_all(dir).ok();
+    }
+
+    #[test]
+    fn test_incremental_rebuild() {
+        let dir = temp_dir("rebuild");
+        let cache = dir.join("cache");
+        let specs = [spec("a", false, true), spec("b", false, true)];
+        fs::write(dir.join("a.txt"), "alpha\n").unwrap();
+        fs::write(dir.join("b.txt"), "beta\n").unwrap();
+        prepare_dictionaries(&specs, &dir, &cache, false);
+
+        let (_, reports) = prepare_dictionaries(&specs, &dir, &cache, false);
+        assert!(reports.iter().all(|r| !r.rebuilt));
+
+        // 只有变化的词表重建
+        fs::write(dir.join("b.txt"), "beta\ngamma\n").unwrap();
+        let (dicts, reports) = prepare_dictionaries(&specs, &dir, &cache, false);
+        assert!(!reports[0].rebuilt);
+        assert!(reports[1].rebuilt);
+        assert_eq!(reports[1].terms, 2);
+        assert_eq!(matches(&DictionaryRecognizer::new(None, None, dicts), "alpha gamma"), vec!["alpha", "gamma"]);
+
+        let (_, reports) = prepare_dictionaries(&specs, &dir, &cache, true);
+        assert!(reports.iter().all(|r| r.rebuilt));
+        fs::remove_dir_all(dir).ok();
+    }
+
+    #[test]
+    fn test_missing_source_is_reported() {
+        let dir = temp_dir("missing");
+        let (dicts, reports) = prepare_dictionaries(&[spec("nope", false, true)], &dir, &dir.join("cache"), false);
+        assert!(dicts.is_empty());
+        assert!(reports[0].error.is_some());
+        fs::remove_dir_all(dir).ok();
+    }
+}
@@ -0,0 +1,550 @@
+//! 精确数据匹配（EDM）识别器
+//!
+//! 用真实客户记录（姓名、手机号、会员号等）构建加盐哈希索引，
+//! 识别时把输入切分为候选值并做同样的哈希，命中即确定为敏感数据。
+//! 索引只保存截断的 HMAC-SHA256 摘要，不落地明文。
+//!
+//! # 候选值
+//!
+//! - 以空白和标点切分的词，以及相邻（仅空格分隔）的 1..=`max_words` 个词组合
+//! - 连续 CJK 字符串中长度 `min_cjk..=max_cjk` 的子串（中文姓名没有分隔符）
+//!
+//! 值在哈希前统一归一化：纯数字类（仅含数字与 `+-(). `）只保留数字，
+//! 其余转小写并合并空白，因此 `138-0013-8000` 与 `13800138000` 视为同一值。
+//!
+//! # 索引文件格式（小端）
+//!
+//! ```text
+//! magic "SMEDM\0" | version u8 | reserved u8
+//! salt [u8; 16]
+//! max_words u8 | min_cjk u8 | max_cjk u8 | reserved u8
+//! column_count u16 | (name_len u16, name, type_len u16, type) * column_count
+//! entry_count u32  | (digest [u8; 12], column u16) * entry_count   按摘要升序
+//! ```
+
+use super::types::*;
+use super::Recognizer;
+use crate::common::errors::{AppError, AppResult};
+use hmac::{Hmac, Mac};
+use serde::{Deserialize, Serialize};
+use sha2::Sha256;
+use std::path::Path;
+use std::sync::Arc;
+
+type HmacSha256 = Hmac<Sha256>;
+
+const MAGIC: &[u8; 6] = b"SMEDM\0";
+const VERSION: u8 = 1;
+const SALT_LEN: usize = 16;
+const DIGEST_LEN: usize = 12;
+/// 词组合的最大词数上限
+const MAX_WORDS_CAP: usize = 6;
+/// CJK 子串的最大长度上限
+const MAX_CJK_CAP: usize = 8;
+
+/// 索引列定义
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct EdmColumn {
+    /// 列名（CSV 表头）
+    pub name: String,
+    /// 实体类型标签（如 `PERSON`、`phone`）
+    pub entity_type: String,
+}
+
+/// 构建索引时的列选择
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EdmColumnSpec {
+    /// CSV 表头中的列名
+    pub column: String,
+    /// 实体类型标签，为空时按列名推断
+    #[serde(default)]
+    pub entity_type: Option<String>,
+}
+
+/// 加盐哈希索引
+pub struct EdmIndex {
+    salt: [u8; SALT_LEN],
+    columns: Vec<EdmColumn>,
+    /// (摘要, 列序号)，按摘要升序
+    entries: Vec<([u8; DIGEST_LEN], u16)>,
+    max_words: usize,
+    min_cjk: usize,
+    max_cjk: usize,
+}
+
+/// 归一化候选值（索引构建与识别共用）
+pub fn normalize_value(value: &str) -> String {
+    let trimmed = value.trim();
+    let numeric = trimmed.chars().any(|c| c.is_ascii_digit())
+        && trimmed.chars().all(|c| c.is_ascii_digit() || matches!(c, '+' | '-' | '(' | ')' | '.' | ' '));
+    if numeric {
+        trimmed.chars().filter(char::is_ascii_digit).collect()
+    } else {
+        trimmed.split_whitespace().collect::<Vec<_>>().join(" ").to_lowercase()
+    }
+}
+
+impl EdmIndex {
+    // ─── 构建 ───
+
+    /// 从 CSV 构建索引
+    ///
+    /// `specs` 为空时索引全部列，实体类型按列名推断。
+    pub fn build_from_csv<R: std::io::Read>(reader: R, specs: &[EdmColumnSpec]) -> AppResult<Self> {
+        let mut csv_reader = csv::ReaderBuilder::new().flexible(true).trim(csv::Trim::All).from_reader(reader);
+        let headers = csv_reader
+            .headers()
+            .map_err(|e| AppError::Config(format!("CSV 表头读取失败: {}", e)))?
+            .clone();
+
+        let specs: Vec<EdmColumnSpec> = if specs.is_empty() {
+            headers
+                .iter()
+                .map(|h| EdmColumnSpec { column: h.to_string(), entity_type: None })
+                .collect()
+        } else {
+            specs.to_vec()
+        };
+        let mut selected = Vec::with_capacity(specs.len());
+        let mut columns = Vec::with_capacity(specs.len());
+        for spec in &specs {
+            let position = headers
+                .iter()
+                .position(|h| h.eq_ignore_ascii_case(&spec.column))
+                .ok_or_else(|| AppError::Config(format!("CSV 中不存在列: {}", spec.column)))?;
+            selected.push(position);
+            columns.push(EdmColumn {
+                name: spec.column.clone(),
+                entity_type: spec.entity_type.clone().unwrap_or_else(|| spec.column.clone()),
+            });
+        }
+        if columns.len() > u16::MAX as usize {
+            return Err(AppError::Config("EDM 索引列数过多".into()));
+        }
+
+        let mut index = Self {
+            salt: rand::random(),
+            columns,
+            entries: Vec::new(),
+            max_words: 1,
+            min_cjk: 0,
+            max_cjk: 0,
+        };
+        for record in csv_reader.records() {
+            let record = record.map_err(|e| AppError::Config(format!("CSV 解析失败: {}", e)))?;
+            for (column, &position) in selected.iter().enumerate() {
+                if let Some(value) = record.get(position) {
+                    index.insert(value, column as u16);
+                }
+            }
+        }
+        index.entries.sort_unstable();
+        index.entries.dedup();
+        Ok(index)
+    }
+
+    fn insert(&mut self, value: &str, column: u16) {
+        let normalized = normalize_value(value);
+        let char_count = normalized.chars().count();
+        if char_count < 2 {
+            return;
+        }
+        let words = normalized.split(' ').count();
+        self.max_words = self.max_words.max(words.min(MAX_WORDS_CAP));
+        if normalized.chars().all(is_cjk) && char_count <= MAX_CJK_CAP {
+            self.min_cjk = if self.min_cjk == 0 { char_count } else { self.min_cjk.min(char_count) };
+            self.max_cjk = self.max_cjk.max(char_count);
+        }
+        let digest = self.digest(&normalized);
+        self.entries.push((digest, column));
+    }
+
+    fn digest(&self, normalized: &str) -> [u8; DIGEST_LEN] {
+        let mut mac = HmacSha256::new_from_slice(&self.salt).expect("HMAC key");
+        mac.update(normalized.as_bytes());
+        let full = mac.finalize().into_bytes();
+        let mut digest = [0u8; DIGEST_LEN];
+        digest.copy_from_slice(&full[..DIGEST_LEN]);
+        digest
+    }
+
+    // ─── 查询 ───
+
+    /// 索引条目数
+    pub fn entry_count(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// 索引列
+    pub fn columns(&self) -> &[EdmColumn] {
+        &self.columns
+    }
+
+    /// 查询值，命中时返回所在列
+    pub fn lookup(&self, value: &str) -> Option<&EdmColumn> {
+        let normalized = normalize_value(value);
+        if normalized.chars().count() < 2 {
+            return None;
+        }
+        let digest = self.digest(&normalized);
+        let pos = self.entries.partition_point(|(d, _)| *d < digest);
+        self.entries
+            .get(pos)
+            .filter(|(d, _)| *d == digest)
+            .and_then(|(_, column)| self.columns.get(*column as usize))
+    }
+
+    // ─── 序列化 ───
+
+    /// 序列化为紧凑二进制格式
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut out = Vec::with_capacity(64 + self.entries.len() * (DIGEST_LEN + 2));
+        out.extend_from_slice(MAGIC);
+        out.extend_from_slice(&[VERSION, 0]);
+        out.extend_from_slice(&self.salt);
+        out.extend_from_slice(&[self.max_words as u8, self.min_cjk as u8, self.max_cjk as u8, 0]);
+        out.extend_from_slice(&(self.columns.len() as u16).to_le_bytes());
+        for column in &self.columns {
+            for field in [&column.name, &column.entity_type] {
+                let bytes = &field.as_bytes()[..field.len().min(u16::MAX as usize)];
+                out.extend_from_slice(&(bytes.len() as u16).to_le_bytes());
+                out.extend_from_slice(bytes);
+            }
+        }
+        out.extend_from_slice(&(self.entries.len() as u32).to_le_bytes());
+        for (digest, column) in &self.entries {
+            out.extend_from_slice(digest);
+            out.extend_from_slice(&column.to_le_bytes());
+        }
+        out
+    }
+
+    /// 从二进制格式解析
+    pub fn from_bytes(data: &[u8]) -> AppResult<Self> {
+        let invalid = || AppError::Config("EDM 索引文件已损坏或格式不符".into());
+        let mut reader = ByteReader { data, pos: 0 };
+
+        if reader.take(MAGIC.len()).ok_or_else(invalid)? != MAGIC {
+            return Err(invalid());
+        }
+        let header = reader.take(2).ok_or_else(invalid)?;
+        if header[0] != VERSION {
+            return Err(AppError::Config(format!("不支持的 EDM 索引版本: {}", header[0])));
+        }
+        let mut salt = [0u8; SALT_LEN];
+        salt.copy_from_slice(reader.take(SALT_LEN).ok_or_else(invalid)?);
+        let limits = reader.take(4).ok_or_else(invalid)?;
+        let (max_words, min_cjk, max_cjk) = (limits[0] as usize, limits[1] as usize, limits[2] as usize);
+
+        let column_count = reader.u16().ok_or_else(invalid)? as usize;
+        let mut columns = Vec::with_capacity(column_count);
+        for _ in 0..column_count {
+            let name = reader.string().ok_or_else(invalid)?;
+            let entity_type = reader.string().ok_or_else(invalid)?;
+            columns.push(EdmColumn { name, entity_type });
+        }
+
+        let entry_count = reader.u32().ok_or_else(invalid)? as usize;
+        let body = reader.take(entry_count.checked_mul(DIGEST_LEN + 2).ok_or_else(invalid)?).ok_or_else(invalid)?;
+        let mut entries = Vec::with_capacity(entry_count);
+        for chunk in body.chunks_exact(DIGEST_LEN + 2) {
+            let mut digest = [0u8; DIGEST_LEN];
+            digest.copy_from_slice(&chunk[..DIGEST_LEN]);
+            let column = u16::from_le_bytes([chunk[DIGEST_LEN], chunk[DIGEST_LEN + 1]]);
+            if column as usize >= columns.len() {
+                return Err(invalid());
+            }
+            entries.push((digest, column));
+        }
+        if !entries.is_sorted() {
+            return Err(invalid());
+        }
+
+        Ok(Self {
+            salt,
+            columns,
+            entries,
+            max_words: max_words.clamp(1, MAX_WORDS_CAP),
+            min_cjk,
+            max_cjk: max_cjk.min(MAX_CJK_CAP),
+        })
+    }
+
+    /// 写入索引文件
+    pub fn save(&self, path: &Path) -> AppResult<()> {
+        std::fs::write(path, self.to_bytes())?;
+        Ok(())
+    }
+
+    /// 读取索引文件
+    pub fn load(path: &Path) -> AppResult<Self> {
+        Self::from_bytes(&std::fs::read(path)?)
+    }
+}
+
+/// 顺序读取二进制字段
+struct ByteReader<'a> {
+    data: &'a [u8],
+    pos: usize,
+}
+
+impl<'a> ByteReader<'a> {
+    fn take(&mut self, len: usize) -> Option<&'a [u8]> {
+        let slice = self.data.get(self.pos..self.pos.checked_add(len)?)?;
+        self.pos += len;
+        Some(slice)
+    }
+
+    fn u16(&mut self) -> Option<u16> {
+        self.take(2).map(|b| u16::from_le_bytes([b[0], b[1]]))
+    }
+
+    fn u32(&mut self) -> Option<u32> {
+        self.take(4).map(|b| u32::from_le_bytes([b[0], b[1], b[2], b[3]]))
+    }
+
+    fn string(&mut self) -> Option<String> {
+        let len = self.u16()? as usize;
+        String::from_utf8(self.take(len)?.to_vec()).ok()
+    }
+}
+
+/// EDM 识别器
+pub struct EdmRecognizer {
+    /// 识别器名称
+    name: String,
+    /// 是否启用
+    enabled: bool,
+    /// 识别器优先级
+    priority: i32,
+    /// 哈希索引
+    index: Arc<EdmIndex>,
+}
+
+impl EdmRecognizer {
+    /// 创建 EDM 识别器
+    ///
+    /// # 参数
+    ///
+    /// - `name`: 识别器名称（默认 "edm_recognizer"）
+    /// - `priority`: 识别器优先级（默认 98，确定性命中优先于规则）
+    /// - `index`: 加盐哈希索引
+    pub fn new(name: Option<&str>, priority: Option<i32>, index: Arc<EdmIndex>) -> Self {
+        Self {
+            name: name.unwrap_or("edm_recognizer").to_string(),
+            enabled: true,
+            priority: priority.unwrap_or(98),
+            index,
+        }
+    }
+
+    // ─── 候选切分 ───
+
+    fn is_delimiter(c: char) -> bool {
+        c.is_whitespace()
+            || matches!(c,
+                ',' | ';' | ':' | '"' | '\'' | '(' | ')' | '[' | ']' | '{' | '}' | '<' | '>' | '|' | '='
+                | '，' | '。' | '；' | '：' | '、' | '（' | '）' | '【' | '】' | '《' | '》' | '“' | '”' | '‘' | '’')
+    }
+
+    /// 切分词，返回 (起, 止, 与下一个词之间是否仅有空格)
+    ///
+    /// CJK 与非 CJK 字符的交界同样视为词边界（如 `号码13800138000`）。
+    fn words(text: &str) -> Vec<(usize, usize, bool)> {
+        let mut words: Vec<(usize, usize, bool)> = Vec::new();
+        let mut start: Option<usize> = None;
+        let mut gap_spaces_only = true;
+        let mut prev_cjk = false;
+        let close = |words: &mut Vec<(usize, usize, bool)>, s: usize, i: usize, gap_spaces_only: bool| {
+            let end = s + text[s..i].trim_end_matches(['.', '!', '?']).len();
+            if end > s {
+                if let Some(prev) = words.last_mut() {
+                    prev.2 = gap_spaces_only;
+                }
+                words.push((s, end, false));
+            }
+        };
+        for (i, c) in text.char_indices().chain(std::iter::once((text.len(), ' '))) {
+            if Self::is_delimiter(c) {
+                if let Some(s) = start.take() {
+                    close(&mut words, s, i, gap_spaces_only);
+                    gap_spaces_only = true;
+                }
+                if !matches!(c, ' ' | '\t') {
+                    gap_spaces_only = false;
+                }
+                continue;
+            }
+            let cjk = is_cjk(c);
+            match start {
+                Some(s) if cjk != prev_cjk => {
+                    close(&mut words, s, i, gap_spaces_only);
+                    gap_spaces_only = false;
+                    start = Some(i);
+                }
+                None => start = Some(i),
+                _ => {}
+            }
+            prev_cjk = cjk;
+        }
+        words
+    }
+
+    fn candidates(&self, text: &str) -> Vec<(usize, usize)> {
+        let words = Self::words(text);
+        let mut candidates = Vec::new();
+        for i in 0..words.len() {
+            let mut end_index = i;
+            loop {
+                candidates.push((words[i].0, words[end_index].1));
+                if end_index + 1 - i >= self.index.max_words || !words[end_index].2 || end_index + 1 >= words.len() {
+                    break;
+                }
+                end_index += 1;
+            }
+
+            if self.index.max_cjk == 0 {
+                continue;
+            }
+            // CJK 子串
+            let (start, end, _) = words[i];
+            let chars: Vec<(usize, char)> = text[start..end].char_indices().map(|(o, c)| (start + o, c)).collect();
+            for a in 0..chars.len() {
+                if !is_cjk(chars[a].1) {
+                    continue;
+                }
+                for len in self.index.min_cjk.max(1)..=self.index.max_cjk {
+                    let b = a + len;
+                    if b > chars.len() || !is_cjk(chars[b - 1].1) {
+                        break;
+                    }
+                    let sub_end = chars.get(b).map_or(end, |(o, _)| *o);
+                    if (chars[a].0, sub_end) != (start, end) {
+                        candidates.push((chars[a].0, sub_end));
+                    }
+                }
+            }
+        }
+        candidates
+    }
+}
+
+impl Recognizer for EdmRecognizer {
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn recognizer_type(&self) -> RecognizerType {
+        RecognizerType::Rule
+    }
+
+    fn supported_entities(&self) -> Vec<EntityType> {
+        self.index
+            .columns()
+            .iter()
+            .map(|c| EntityType::from_label(&c.entity_type))
+            .collect()
+    }
+
+    fn analyze(&self, context: &AnalysisContext) -> AnalysisResult {
+        let Ok(text) = std::str::from_utf8(context.text) else {
+            return AnalysisResult::empty(&self.name);
+        };
+
+        let mut hits: Vec<(usize, usize, &EdmColumn)> = self
+            .candidates(text)
+            .into_iter()
+            .filter_map(|(s, e)| self.index.lookup(&text[s..e]).map(|column| (s, e, column)))
+            .collect();
+
+        // 最长优先，去除重叠
+        hits.sort_by(|a, b| (b.1 - b.0).cmp(&(a.1 - a.0)).then(a.0.cmp(&b.0)));
+        let mut spans: Vec<EntitySpan> = Vec::new();
+        for (start, end, column) in hits {
+            if spans.iter().any(|s| s.start < end && start < s.end) {
+                continue;
+            }
+            spans.push(
+                EntitySpan::new(start, end, EntityType::from_label(&column.entity_type), 1.0, self.name.clone())
+                    .with_metadata("edm_column", column.name.clone()),
+            );
+        }
+
+        spans.sort_by_key(|s| s.start);
+        AnalysisResult {
+            spans,
+            elapsed_us: 0,
+            recognizer: self.name.clone(),
+        }
+    }
+
+    fn priority(&self) -> i32 {
+        self.priority
+    }
+
+    fn is_enabled(&self) -> bool {
+        self.enabled
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const CSV: &str = "name,phone,member_id,note\n张三丰,138-0013-8000,VIP-00123,x\nJohn Smith,+1 (415) 555-0100,VIP-00456,y\n";
+
+    fn recognizer() -> EdmRecognizer {
+        let specs = vec![
+            EdmColumnSpec { column: "name".into(), entity_type: Some("PERSON".into()) },
+            EdmColumnSpec { column: "phone".into(), entity_type: None },
+            EdmColumnSpec { column: "member_id".into(), entity_type: Some("member_id".into()) },
+        ];
+        let index = EdmIndex::build_from_csv(CSV.as_bytes(), &specs).unwrap();
+        EdmRecognizer::new(None, None, Arc::new(index))
+    }
+
+    fn hits(text: &str) -> Vec<(String, String)> {
+        recognizer()
+            .analyze(&AnalysisContext::from_text(text.as_bytes()))
+            .spans
+            .iter()
+            .map(|s| (text[s.start..s.end].to_string(), s.meta("edm_column").unwrap().to_string()))
+            .collect()
+    }
+
+    #[test]
+    fn test_matches_report_column() {
+        assert_eq!(
+            hits("客户张三丰来电，号码13800138000，会员 VIP-00123."),
+            vec![
+                ("张三丰".to_string(), "name".to_string()),
+                ("13800138000".to_string(), "phone".to_string()),
+                ("VIP-00123".to_string(), "member_id".to_string()),
+            ]
+        );
+        assert_eq!(hits("Contact john  smith at 14155550100"), vec![
+            ("john  smith".to_string(), "name".to_string()),
+            ("14155550100".to_string(), "phone".to_string()),
+        ]);
+        // 不在名单中的值不命中
+        assert!(hits("张三 John Smyth VIP-00999").is_empty());
+    }
+
+    #[test]
+    fn test_roundtrip_and_no_plaintext() {
+        let index = EdmIndex::build_from_csv(CSV.as_bytes(), &[]).unwrap();
+        let bytes = index.to_bytes();
+        assert!(!bytes.windows("VIP-00123".len()).any(|w| w == b"VIP-00123"));
+
+        let loaded = EdmIndex::from_bytes(&bytes).unwrap();
+        assert_eq!(loaded.entry_count(), index.entry_count());
+        assert_eq!(loaded.lookup("vip-00456").map(|c| c.name.as_str()), Some("member_id"));
+        assert!(EdmIndex::from_bytes(&bytes[..bytes.len() - 1]).is_err());
+    }
+
+    #[test]
+    fn test_unknown_column_is_error() {
+        let specs = vec![EdmColumnSpec { column: "email".into(), entity_type: None }];
+        assert!(EdmIndex::build_from_csv(CSV.as_bytes(), &specs).is_err());
+    }
+}
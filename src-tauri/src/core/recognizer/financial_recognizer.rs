@@ -0,0 +1,352 @@
+//! 国际金融标识识别器
+//!
+//! 识别跨境场景常见的金融标识，并在实体上附加结构化元数据：
+//!
+//! - **IBAN**: 按国家长度表 + ISO 13616 mod-97 校验，支持 4 位分组的打印格式
+//! - **SWIFT/BIC**: 8/11 位结构 + 国家代码校验，需附近出现 "SWIFT"/"BIC" 等上下文词
+//! - **银行卡卡组织**: Luhn 校验通过后按 BIN 段判定 Visa / Mastercard / UnionPay / Amex / JCB
+//!
+//! # 元数据
+//!
+//! | key | 示例 | 说明 |
+//! |-----|------|------|
+//! | `card_brand` | `UNIONPAY` | 卡组织，掩码输出为 `<CARD:UNIONPAY>` |
+//! | `iban_country` / `bic_country` | `DE` | 账户所属国家 |
+//! | `cross_border` | `true` | 是否为境外账户（非 CN / 非银联），供策略路由区分处理 |
+
+use super::checksum_recognizer::ChecksumRecognizer;
+use super::types::*;
+use super::Recognizer;
+use regex::bytes::Regex;
+
+// ─────────────────────────────────────────────────────────────────────────────
+// 国家代码表
+// ─────────────────────────────────────────────────────────────────────────────
+
+/// IBAN 国家长度表（SWIFT IBAN Registry）
+const IBAN_LENGTHS: &[(&str, usize)] = &[
+    ("AD", 24), ("AE", 23), ("AL", 28), ("AT", 20), ("AZ", 28), ("BA", 20), ("BE", 16),
+    ("BG", 22), ("BH", 22), ("BR", 29), ("BY", 28), ("CH", 21), ("CR", 22), ("CY", 28),
+    ("CZ", 24), ("DE", 22), ("DK", 18), ("DO", 28), ("EE", 20), ("EG", 29), ("ES", 24),
+    ("FI", 18), ("FO", 18), ("FR", 27), ("GB", 22), ("GE", 22), ("GI", 23), ("GL", 18),
+    ("GR", 27), ("GT", 28), ("HR", 21), ("HU", 28), ("IE", 22), ("IL", 23), ("IQ", 23),
+    ("IS", 26), ("IT", 27), ("JO", 30), ("KW", 30), ("KZ", 20), ("LB", 28), ("LC", 32),
+    ("LI", 21), ("LT", 20), ("LU", 20), ("LV", 21), ("MC", 27), ("MD", 24), ("ME", 22),
+    ("MK", 19), ("MR", 27), ("MT", 31), ("MU", 30), ("NL", 18), ("NO", 15), ("PK", 24),
+    ("PL", 28), ("PS", 29), ("PT", 25), ("QA", 29), ("RO", 24), ("RS", 22), ("SA", 24),
+    ("SC", 31), ("SE", 24), ("SI", 19), ("SK", 24), ("SM", 27), ("ST", 25), ("SV", 28),
+    ("TL", 23), ("TN", 24), ("TR", 26), ("UA", 29), ("VA", 22), ("VG", 24), ("XK", 20),
+];
+
+/// 不使用 IBAN 但常见于 SWIFT/BIC 的国家代码
+const BIC_EXTRA_COUNTRIES: &[&str] = &[
+    "CN", "HK", "MO", "TW", "JP", "KR", "SG", "MY", "TH", "VN", "ID", "PH", "IN", "AU",
+    "NZ", "US", "CA", "MX", "AR", "CL", "CO", "PE", "RU", "ZA", "NG", "KE",
+];
+
+/// SWIFT/BIC 上下文关键词（小写比较）
+const BIC_CONTEXT_KEYWORDS: &[&str] = &["swift", "bic", "银行识别码", "银行代码", "国际代码"];
+
+/// 查询 IBAN 国家对应的总长度
+pub fn iban_length(country: &str) -> Option<usize> {
+    IBAN_LENGTHS.iter().find(|(c, _)| *c == country).map(|(_, len)| *len)
+}
+
+fn is_bic_country(country: &str) -> bool {
+    iban_length(country).is_some() || BIC_EXTRA_COUNTRIES.contains(&country)
+}
+
+// ─────────────────────────────────────────────────────────────────────────────
+// 卡组织 (CardBrand)
+// ─────────────────────────────────────────────────────────────────────────────
+
+/// 银行卡卡组织
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CardBrand {
+    Visa,
+    Mastercard,
+    UnionPay,
+    Amex,
+    Jcb,
+}
+
+impl CardBrand {
+    /// 根据 BIN 段与卡号长度判定卡组织
+    pub fn detect(digits: &str) -> Option<Self> {
+        let len = digits.len();
+        let prefix = |n: usize| digits.get(..n).and_then(|p| p.parse::<u32>().ok()).unwrap_or(0);
+
+        if (prefix(2) == 34 || prefix(2) == 37) && len == 15 {
+            Some(Self::Amex)
+        } else if (3528..=3589).contains(&prefix(4)) && (16..=19).contains(&len) {
+            Some(Self::Jcb)
+        } else if prefix(2) == 62 && (16..=19).contains(&len) {
+            Some(Self::UnionPay)
+        } else if ((51..=55).contains(&prefix(2)) || (2221..=2720).contains(&prefix(4))) && len == 16 {
+            Some(Self::Mastercard)
+        } else if prefix(1) == 4 && matches!(len, 13 | 16 | 19) {
+            Some(Self::Visa)
+        } else {
+            None
+        }
+    }
+
+    /// 卡组织标识（用于元数据与掩码）
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Visa => "VISA",
+            Self::Mastercard => "MASTERCARD",
+            Self::UnionPay => "UNIONPAY",
+            Self::Amex => "AMEX",
+            Self::Jcb => "JCB",
+        }
+    }
+}
+
+// ─────────────────────────────────────────────────────────────────────────────
+// 国际金融标识识别器
+// ─────────────────────────────────────────────────────────────────────────────
+
+/// 国际金融标识识别器
+///
+/// 产出 `EntityType::Iban`、`EntityType::SwiftCode` 与带卡组织元数据的 `EntityType::BankCard`。
+pub struct FinancialRecognizer {
+    /// 识别器名称
+    name: String,
+    /// 是否启用
+    enabled: bool,
+    /// 识别器优先级
+    priority: i32,
+    /// IBAN 候选（允许 4 位一组的空格分隔）
+    iban_re: Regex,
+    /// SWIFT/BIC 候选
+    bic_re: Regex,
+    /// 银行卡候选（允许空格/短横线分隔）
+    card_re: Regex,
+}
+
+impl Default for FinancialRecognizer {
+    fn default() -> Self {
+        Self::new(None, None)
+    }
+}
+
+impl FinancialRecognizer {
+    /// 创建国际金融标识识别器
+    ///
+    /// # 参数
+    ///
+    /// - `name`: 识别器名称（默认 "financial_recognizer"）
+    /// - `priority`: 识别器优先级（默认 95，校验通过的结果优先于正则粗匹配）
+    pub fn new(name: Option<&str>, priority: Option<i32>) -> Self {
+        Self {
+            name: name.unwrap_or("financial_recognizer").to_string(),
+            enabled: true,
+            priority: priority.unwrap_or(95),
+            iban_re: Regex::new(r"\b[A-Z]{2}\d{2}(?: ?[A-Z0-9]){11,30}\b").expect("invalid IBAN regex"),
+            bic_re: Regex::new(r"\b[A-Z]{4}[A-Z]{2}[A-Z0-9]{2}(?:[A-Z0-9]{3})?\b").expect("invalid BIC regex"),
+            card_re: Regex::new(r"\b\d(?:[ -]?\d){12,18}\b").expect("invalid card regex"),
+        }
+    }
+
+    /// 识别 IBAN：按国家长度截取，mod-97 校验
+    fn find_ibans(&self, text: &[u8], spans: &mut Vec<EntitySpan>) {
+        for m in self.iban_re.find_iter(text) {
+            let candidate = m.as_bytes();
+            let country = std::str::from_utf8(&candidate[..2]).unwrap_or_default();
+            let Some(expected) = iban_length(country) else { continue };
+
+            // 打印格式可能贴着后续单词，按国家长度截取有效部分
+            let mut taken = 0;
+            let mut end = 0;
+            for (i, b) in candidate.iter().enumerate() {
+                if *b != b' ' {
+                    taken += 1;
+                }
+                if taken == expected {
+                    end = i + 1;
+                    break;
+                }
+            }
+            if end == 0 || candidate.get(end).is_some_and(|b| b.is_ascii_alphanumeric()) {
+                continue;
+            }
+
+            let iban = std::str::from_utf8(&candidate[..end]).unwrap_or_default();
+            if !ChecksumRecognizer::validate_iban(iban) {
+                continue;
+            }
+
+            spans.push(
+                EntitySpan::with_mask(m.start(), m.start() + end, EntityType::Iban, 1.0, self.name.clone(), "<IBAN>")
+                    .with_metadata("iban_country", country)
+                    .with_metadata("cross_border", "true"),
+            );
+        }
+    }
+
+    /// 识别 SWIFT/BIC：结构 + 国家代码 + 上下文关键词
+    fn find_bics(&self, text: &[u8], spans: &mut Vec<EntitySpan>) {
+        for m in self.bic_re.find_iter(text) {
+            let code = m.as_bytes();
+            let country = std::str::from_utf8(&code[4..6]).unwrap_or_default();
+            if !is_bic_country(country) {
+                continue;
+            }
+
+            let window_start = m.start().saturating_sub(40);
+            let window = String::from_utf8_lossy(&text[window_start..m.start()]).to_lowercase();
+            if !BIC_CONTEXT_KEYWORDS.iter().any(|k| window.contains(k)) {
+                continue;
+            }
+
+            spans.push(
+                EntitySpan::with_mask(m.start(), m.end(), EntityType::SwiftCode, 0.95, self.name.clone(), "<SWIFT>")
+                    .with_metadata("bic_country", country)
+                    .with_metadata("cross_border", (country != "CN").to_string()),
+            );
+        }
+    }
+
+    /// 识别银行卡：Luhn 校验 + BIN 段判定卡组织
+    fn find_cards(&self, text: &[u8], spans: &mut Vec<EntitySpan>) {
+        for m in self.card_re.find_iter(text) {
+            let raw = std::str::from_utf8(m.as_bytes()).unwrap_or_default();
+            // 分隔符必须一致（"6222 0202 …" 或 "6222-0202-…"），避免吞并相邻数字
+            if raw.contains(' ') && raw.contains('-') {
+                continue;
+            }
+            let digits: String = raw.chars().filter(char::is_ascii_digit).collect();
+
+            // 18 位且通过身份证校验的号码交给身份证规则（如甘肃 62 开头的身份证号）
+            if digits.len() == 18 && ChecksumRecognizer::validate_chinese_id(&digits) {
+                continue;
+            }
+            if !ChecksumRecognizer::validate_bank_card(&digits) {
+                continue;
+            }
+            let Some(brand) = CardBrand::detect(&digits) else { continue };
+
+            spans.push(
+                EntitySpan::with_mask(
+                    m.start(),
+                    m.end(),
+                    EntityType::BankCard,
+                    1.0,
+                    self.name.clone(),
+                    format!("<CARD:{}>", brand.as_str()),
+                )
+                .with_metadata("card_brand", brand.as_str())
+                .with_metadata("cross_border", (brand != CardBrand::UnionPay).to_string()),
+            );
+        }
+    }
+}
+
+impl Recognizer for FinancialRecognizer {
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn recognizer_type(&self) -> RecognizerType {
+        RecognizerType::Rule
+    }
+
+    fn supported_entities(&self) -> Vec<EntityType> {
+        vec![EntityType::Iban, EntityType::SwiftCode, EntityType::BankCard]
+    }
+
+    fn analyze(&self, context: &AnalysisContext) -> AnalysisResult {
+        let mut spans = Vec::new();
+        self.find_ibans(context.text, &mut spans);
+        self.find_bics(context.text, &mut spans);
+        self.find_cards(context.text, &mut spans);
+
+        AnalysisResult {
+            spans,
+            elapsed_us: 0,
+            recognizer: self.name.clone(),
+        }
+    }
+
+    fn priority(&self) -> i32 {
+        self.priority
+    }
+
+    fn is_enabled(&self) -> bool {
+        self.enabled
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn analyze(text: &str) -> Vec<EntitySpan> {
+        FinancialRecognizer::default()
+            .analyze(&AnalysisContext::from_text(text.as_bytes()))
+            .spans
+    }
+
+    #[test]
+    fn test_iban_compact_and_printed() {
+        let text = "IBAN: DE89370400440532013000, GB82 WEST 1234 5698 7654 32 thanks";
+        let spans = analyze(text);
+        assert_eq!(spans.len(), 2);
+        assert_eq!(spans[0].text_str(text), "DE89370400440532013000");
+        assert_eq!(spans[0].meta("iban_country"), Some("DE"));
+        assert_eq!(spans[1].text_str(text), "GB82 WEST 1234 5698 7654 32");
+    }
+
+    #[test]
+    fn test_iban_rejects_bad_checksum_and_length() {
+        assert!(analyze("DE89370400440532013001").is_empty());
+        assert!(analyze("DE8937040044053201300").is_empty());
+    }
+
+    #[test]
+    fn test_bic_requires_context() {
+        let text = "SWIFT code: BKCHCNBJ110";
+        let spans = analyze(text);
+        assert_eq!(spans.len(), 1);
+        assert_eq!(spans[0].entity_type, EntityType::SwiftCode);
+        assert_eq!(spans[0].meta("cross_border"), Some("false"));
+
+        assert!(analyze("DEUTDEFF").is_empty());
+        assert!(analyze("BIC: PASSWORD").is_empty());
+    }
+
+    #[test]
+    fn test_card_brands() {
+        let cases = [
+            ("4111111111111111", "VISA"),
+            ("5555555555554444", "MASTERCARD"),
+            ("2223003122003222", "MASTERCARD"),
+            ("378282246310005", "AMEX"),
+            ("3530111333300000", "JCB"),
+            ("6212345678901232", "UNIONPAY"),
+        ];
+        for (card, brand) in cases {
+            let spans = analyze(card);
+            assert_eq!(spans.len(), 1, "card {}", card);
+            assert_eq!(spans[0].meta("card_brand"), Some(brand));
+            assert_eq!(spans[0].mask.as_deref(), Some(format!("<CARD:{}>", brand).as_str()));
+        }
+    }
+
+    #[test]
+    fn test_card_with_separators_and_invalid_luhn() {
+        let text = "卡号 4111 1111 1111 1111 已绑定";
+        let spans = analyze(text);
+        assert_eq!(spans.len(), 1);
+        assert_eq!(spans[0].text_str(text), "4111 1111 1111 1111");
+
+        assert!(analyze("4111111111111112").is_empty());
+    }
+
+    #[test]
+    fn test_chinese_id_not_mistaken_for_card() {
+        // 62 开头、通过身份证校验的 18 位号码不应被识别为银联卡
+        assert!(analyze("620102199003071109").iter().all(|s| s.entity_type != EntityType::BankCard));
+    }
+}
@@ -0,0 +1,554 @@
+//! 国际证件识别包（欧美）
+//!
+//! 面向英文/欧洲用户的国家标识符，每类号码都带结构或校验位验证：
+//!
+//! | 证件 | 实体类型 | 校验 |
+//! |------|----------|------|
+//! | 美国 SSN | `IdCard` | 排除 000/666/9xx 区号、00 组号、0000 序号及公开样例号 |
+//! | 美国 ITIN | `TaxId` | 9 开头，组号 50–65 / 70–88 / 90–92 / 94–99 |
+//! | 美国护照 | `Passport` | 9 位数字，需上下文 |
+//! | 英国 National Insurance | `IdCard` | 前缀字母规则 + 后缀 A–D |
+//! | 德国 Steuer-ID | `TaxId` | 数字重复规则 + ISO 7064 MOD 11,10，需上下文 |
+//! | 法国 NIR | `IdCard` | 出生月份 + 97 模校验码（支持科西嘉 2A/2B） |
+//! | 欧盟增值税号 | `TaxId` | AT/BE/DE/DK/FI/FR/IT/NL/PL/SE 各国校验算法 |
+//!
+//! 该识别器属于可选的"国际包"，默认不注册，
+//! 由 `HybridEngine::enable_international_pack` 按设置加载，纯中文用户不承担扫描开销。
+
+use super::types::*;
+use super::Recognizer;
+use regex::bytes::Regex;
+
+/// 上下文搜索窗口（字节，向前）
+const CONTEXT_WINDOW: usize = 40;
+
+/// 公开宣传/样例中广泛流传的 SSN，视为无效
+const SSN_BLACKLIST: &[&str] = &["078051120", "219099999", "123456789"];
+
+/// 英国 NINO 禁用前缀
+const NINO_BANNED_PREFIXES: &[&str] = &["BG", "GB", "KN", "NK", "NT", "TN", "ZZ"];
+
+// ─────────────────────────────────────────────────────────────────────────────
+// 证件种类
+// ─────────────────────────────────────────────────────────────────────────────
+
+/// 证件种类
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum IntlIdKind {
+    EuVat,
+    UkNino,
+    FrNir,
+    DeSteuerId,
+    UsItin,
+    UsSsn,
+    UsPassport,
+}
+
+impl IntlIdKind {
+    fn entity_type(self) -> EntityType {
+        match self {
+            Self::UsSsn | Self::UkNino | Self::FrNir => EntityType::IdCard,
+            Self::UsItin | Self::DeSteuerId | Self::EuVat => EntityType::TaxId,
+            Self::UsPassport => EntityType::Passport,
+        }
+    }
+
+    /// 上下文关键词（小写比较）
+    fn keywords(self) -> &'static [&'static str] {
+        match self {
+            Self::UsSsn => &["ssn", "social security"],
+            Self::UsItin => &["itin", "taxpayer"],
+            Self::UsPassport => &["passport"],
+            Self::UkNino => &["national insurance", "nino", "ni number", "ni no"],
+            Self::DeSteuerId => &["steuer", "idnr", "identifikationsnummer", "tax id"],
+            Self::FrNir => &["sécurité sociale", "securite sociale", "nir", "insee", "sécu"],
+            Self::EuVat => &["vat", "ust", "mwst", "tva", "iva", "btw", "moms", "alv", "nip"],
+        }
+    }
+
+    /// 无上下文时是否仍然产出
+    fn requires_context(self) -> bool {
+        matches!(self, Self::UsPassport | Self::DeSteuerId)
+    }
+
+    /// 元数据中的证件种类标签
+    fn label(self) -> &'static str {
+        match self {
+            Self::EuVat => "EU_VAT",
+            Self::UkNino => "UK_NINO",
+            Self::FrNir => "FR_NIR",
+            Self::DeSteuerId => "DE_STEUER_ID",
+            Self::UsItin => "US_ITIN",
+            Self::UsSsn => "US_SSN",
+            Self::UsPassport => "US_PASSPORT",
+        }
+    }
+}
+
+/// 编译后的证件模式
+struct IntlPattern {
+    kind: IntlIdKind,
+    re: Regex,
+}
+
+/// 国际证件识别器
+pub struct InternationalRecognizer {
+    /// 识别器名称
+    name: String,
+    /// 是否启用
+    enabled: bool,
+    /// 识别器优先级
+    priority: i32,
+    /// 按扫描顺序排列的模式（先长后短，先字母前缀后纯数字）
+    patterns: Vec<IntlPattern>,
+}
+
+impl Default for InternationalRecognizer {
+    fn default() -> Self {
+        Self::new(None, None)
+    }
+}
+
+impl InternationalRecognizer {
+    /// 创建国际证件识别器
+    ///
+    /// # 参数
+    ///
+    /// - `name`: 识别器名称（默认 "international_recognizer"）
+    /// - `priority`: 识别器优先级（默认 95）
+    pub fn new(name: Option<&str>, priority: Option<i32>) -> Self {
+        let sources: [(IntlIdKind, &str); 7] = [
+            (IntlIdKind::EuVat, r"(?:AT|BE|DE|DK|FI|FR|IT|NL|PL|SE) ?[0-9A-Z]{8,12}"),
+            (IntlIdKind::UkNino, r"[A-CEGHJ-PR-TW-Z][A-CEGHJ-NPR-TW-Z] ?\d{2} ?\d{2} ?\d{2} ?[A-D]"),
+            (IntlIdKind::FrNir, r"[12] ?\d{2} ?\d{2} ?(?:\d{2}|2[AB]) ?\d{3} ?\d{3} ?\d{2}"),
+            (IntlIdKind::DeSteuerId, r"[1-9]\d(?: ?\d{3}){3}"),
+            (IntlIdKind::UsItin, r"9\d{2}-?\d{2}-?\d{4}"),
+            (IntlIdKind::UsSsn, r"[0-8]\d{2}-?\d{2}-?\d{4}"),
+            (IntlIdKind::UsPassport, r"\d{9}"),
+        ];
+
+        Self {
+            name: name.unwrap_or("international_recognizer").to_string(),
+            enabled: true,
+            priority: priority.unwrap_or(95),
+            patterns: sources
+                .into_iter()
+                .map(|(kind, src)| IntlPattern {
+                    kind,
+                    re: Regex::new(src).expect("invalid international id regex"),
+                })
+                .collect(),
+        }
+    }
+
+    // ─── 校验函数 ───
+
+    /// 美国 SSN 结构校验（区号/组号/序号排除规则）
+    pub fn validate_ssn(ssn: &str) -> bool {
+        let Some(digits) = Self::dashed_digits(ssn, 9) else {
+            return false;
+        };
+        let area: u32 = digits[0..3].parse().unwrap_or(0);
+        let group = &digits[3..5];
+        let serial = &digits[5..9];
+        area != 0
+            && area != 666
+            && area < 900
+            && group != "00"
+            && serial != "0000"
+            && !SSN_BLACKLIST.contains(&digits.as_str())
+    }
+
+    /// 美国 ITIN 结构校验
+    pub fn validate_itin(itin: &str) -> bool {
+        let Some(digits) = Self::dashed_digits(itin, 9) else {
+            return false;
+        };
+        let group: u32 = digits[3..5].parse().unwrap_or(0);
+        digits.starts_with('9')
+            && matches!(group, 50..=65 | 70..=88 | 90..=92 | 94..=99)
+            && &digits[5..9] != "0000"
+    }
+
+    /// 英国 National Insurance Number 校验
+    pub fn validate_nino(nino: &str) -> bool {
+        let compact: String = nino.chars().filter(|c| *c != ' ').collect();
+        let bytes = compact.as_bytes();
+        if bytes.len() != 9 || !bytes[2..8].iter().all(u8::is_ascii_digit) {
+            return false;
+        }
+        let first_ok = !b"DFIQUV".contains(&bytes[0]);
+        let second_ok = !b"DFIOQUV".contains(&bytes[1]);
+        first_ok
+            && second_ok
+            && !NINO_BANNED_PREFIXES.contains(&&compact[0..2])
+            && (b'A'..=b'D').contains(&bytes[8])
+    }
+
+    /// 德国 Steuerliche Identifikationsnummer 校验
+    ///
+    /// 前 10 位中恰好一个数字出现 2 或 3 次（3 次时不得三连），其余至多一次；
+    /// 第 11 位为 ISO 7064 MOD 11,10 校验位。
+    pub fn validate_steuer_id(id: &str) -> bool {
+        let digits: Vec<u32> = id.chars().filter_map(|c| c.to_digit(10)).collect();
+        if digits.len() != 11 || digits[0] == 0 || id.chars().any(|c| !c.is_ascii_digit() && c != ' ') {
+            return false;
+        }
+        let body = &digits[..10];
+        let mut counts = [0usize; 10];
+        for &d in body {
+            counts[d as usize] += 1;
+        }
+        let repeated: Vec<usize> = counts.iter().copied().filter(|&c| c > 1).collect();
+        if repeated.len() != 1 || repeated[0] > 3 {
+            return false;
+        }
+        if repeated[0] == 3 && body.windows(3).any(|w| w[0] == w[1] && w[1] == w[2]) {
+            return false;
+        }
+        Self::iso7064_mod11_10(body) == digits[10]
+    }
+
+    /// 法国 NIR（社会保障号）校验
+    pub fn validate_nir(nir: &str) -> bool {
+        let compact: String = nir.chars().filter(|c| *c != ' ').collect();
+        if compact.len() != 15 {
+            return false;
+        }
+        let month: u32 = compact[3..5].parse().unwrap_or(0);
+        if !(1..=12).contains(&month) && month < 20 {
+            return false;
+        }
+        // 科西嘉省份代码 2A / 2B 计算校验码时分别替换为 19 / 18
+        let numeric = match &compact[5..7] {
+            "2A" => format!("{}19{}", &compact[..5], &compact[7..13]),
+            "2B" => format!("{}18{}", &compact[..5], &compact[7..13]),
+            _ => compact[..13].to_string(),
+        };
+        let (Ok(number), Ok(key)) = (numeric.parse::<u64>(), compact[13..].parse::<u64>()) else {
+            return false;
+        };
+        97 - number % 97 == key
+    }
+
+    /// 欧盟增值税号校验（按国家前缀分派）
+    pub fn validate_vat(vat: &str) -> bool {
+        let compact: String = vat.chars().filter(|c| *c != ' ').collect();
+        if compact.len() < 4 {
+            return false;
+        }
+        let (country, body) = compact.split_at(2);
+        let all_digits = |s: &str, len: usize| s.len() == len && s.bytes().all(|b| b.is_ascii_digit());
+        let digits: Vec<u32> = body.chars().filter_map(|c| c.to_digit(10)).collect();
+
+        match country {
+            "AT" => {
+                let Some(rest) = body.strip_prefix('U') else { return false };
+                if !all_digits(rest, 8) {
+                    return false;
+                }
+                let d: Vec<u32> = rest.chars().filter_map(|c| c.to_digit(10)).collect();
+                let sum: u32 = d[..7]
+                    .iter()
+                    .enumerate()
+                    .map(|(i, &x)| if i % 2 == 1 { (x * 2) / 10 + (x * 2) % 10 } else { x })
+                    .sum();
+                (10 - (sum + 4) % 10) % 10 == d[7]
+            }
+            "BE" => {
+                all_digits(body, 10) && matches!(digits[0], 0 | 1) && {
+                    let head: u64 = body[..8].parse().unwrap_or(0);
+                    let check: u64 = body[8..].parse().unwrap_or(0);
+                    97 - head % 97 == check
+                }
+            }
+            "DE" => all_digits(body, 9) && digits[0] != 0 && Self::iso7064_mod11_10(&digits[..8]) == digits[8],
+            "DK" => {
+                all_digits(body, 8) && {
+                    let weights = [2, 7, 6, 5, 4, 3, 2, 1];
+                    digits.iter().zip(weights).map(|(d, w)| d * w).sum::<u32>() % 11 == 0
+                }
+            }
+            "FI" => {
+                all_digits(body, 8) && {
+                    let weights = [7, 9, 10, 5, 8, 4, 2];
+                    let r = digits[..7].iter().zip(weights).map(|(d, w)| d * w).sum::<u32>() % 11;
+                    match r {
+                        0 => digits[7] == 0,
+                        1 => false,
+                        _ => digits[7] == 11 - r,
+                    }
+                }
+            }
+            "FR" => {
+                body.len() == 11 && all_digits(&body[2..], 9) && {
+                    let siren = &body[2..];
+                    match body[..2].parse::<u64>() {
+                        Ok(key) => (12 + 3 * (siren.parse::<u64>().unwrap_or(0) % 97)) % 97 == key,
+                        // 新式字母校验键无公开算法，退化为 SIREN 本身的 Luhn 校验
+                        Err(_) => body[..2].bytes().all(|b| b.is_ascii_alphanumeric()) && Self::luhn(siren),
+                    }
+                }
+            }
+            "IT" => all_digits(body, 11) && Self::luhn(body),
+            "NL" => {
+                let valid_shape = body.len() == 12
+                    && all_digits(&body[..9], 9)
+                    && &body[9..10] == "B"
+                    && all_digits(&body[10..], 2);
+                valid_shape && {
+                    // 2020 年起的个体经营者号码使用 MOD 97，旧号码使用 11 模加权
+                    let legacy = digits[..8].iter().zip((2..=9).rev()).map(|(d, w)| d * w).sum::<u32>() % 11 == digits[8];
+                    legacy || Self::mod97_alnum(&compact) == 1
+                }
+            }
+            "PL" => {
+                all_digits(body, 10) && {
+                    let weights = [6, 5, 7, 2, 3, 4, 5, 6, 7];
+                    let r = digits[..9].iter().zip(weights).map(|(d, w)| d * w).sum::<u32>() % 11;
+                    r != 10 && r == digits[9]
+                }
+            }
+            "SE" => all_digits(body, 12) && body.ends_with("01") && Self::luhn(&body[..10]),
+            _ => false,
+        }
+    }
+
+    /// ISO 7064 MOD 11,10 校验位
+    fn iso7064_mod11_10(digits: &[u32]) -> u32 {
+        let mut product = 10;
+        for &d in digits {
+            let mut sum = (d + product) % 10;
+            if sum == 0 {
+                sum = 10;
+            }
+            product = (sum * 2) % 11;
+        }
+        let check = 11 - product;
+        if check == 10 { 0 } else { check }
+    }
+
+    /// Luhn 校验（不限长度，银行卡版本限定 13–19 位）
+    fn luhn(digits: &str) -> bool {
+        let sum: u32 = digits
+            .chars()
+            .rev()
+            .filter_map(|c| c.to_digit(10))
+            .enumerate()
+            .map(|(i, d)| if i % 2 == 1 { if d * 2 > 9 { d * 2 - 9 } else { d * 2 } } else { d })
+            .sum();
+        sum.is_multiple_of(10)
+    }
+
+    /// 字母数字串的 MOD 97（字母按 A=10 … Z=35 展开）
+    fn mod97_alnum(s: &str) -> u32 {
+        s.chars().fold(0u32, |acc, c| match c.to_digit(36) {
+            Some(v) if v >= 10 => (acc * 100 + v) % 97,
+            Some(v) => (acc * 10 + v) % 97,
+            None => acc,
+        })
+    }
+
+    /// 取出数字并要求分隔符要么全无、要么位于 3-2-4 分组处
+    fn dashed_digits(candidate: &str, len: usize) -> Option<String> {
+        let digits: String = candidate.chars().filter(char::is_ascii_digit).collect();
+        let dashes = candidate.matches('-').count();
+        let grouped = dashes == 2 && candidate.as_bytes().get(3) == Some(&b'-') && candidate.as_bytes().get(6) == Some(&b'-');
+        (digits.len() == len && (dashes == 0 || grouped)).then_some(digits)
+    }
+
+    fn validate(kind: IntlIdKind, candidate: &str) -> bool {
+        match kind {
+            IntlIdKind::EuVat => Self::validate_vat(candidate),
+            IntlIdKind::UkNino => Self::validate_nino(candidate),
+            IntlIdKind::FrNir => Self::validate_nir(candidate),
+            IntlIdKind::DeSteuerId => Self::validate_steuer_id(candidate),
+            IntlIdKind::UsItin => Self::validate_itin(candidate),
+            IntlIdKind::UsSsn => Self::validate_ssn(candidate),
+            IntlIdKind::UsPassport => true,
+        }
+    }
+
+    /// 候选前后不能紧贴 ASCII 字母数字（避免从长串中间截取）
+    fn is_bounded(text: &[u8], start: usize, end: usize) -> bool {
+        let before = start == 0 || !text[start - 1].is_ascii_alphanumeric();
+        let after = end >= text.len() || !text[end].is_ascii_alphanumeric();
+        before && after
+    }
+
+    /// 校验候选并构造实体，不合格时返回 None
+    fn accept(&self, kind: IntlIdKind, text: &[u8], start: usize, end: usize, spans: &[EntitySpan]) -> Option<EntitySpan> {
+        if !Self::is_bounded(text, start, end) || spans.iter().any(|s| s.start < end && start < s.end) {
+            return None;
+        }
+
+        let candidate = std::str::from_utf8(&text[start..end]).unwrap_or_default();
+        if !Self::validate(kind, candidate) {
+            return None;
+        }
+
+        let with_context = Self::has_context(text, start, kind.keywords());
+        // 不带分隔符的 9 位 SSN / ITIN 与普通数字难以区分，需要上下文支撑
+        let bare_us_number = matches!(kind, IntlIdKind::UsSsn | IntlIdKind::UsItin) && !candidate.contains('-');
+        if !with_context && (kind.requires_context() || bare_us_number) {
+            return None;
+        }
+
+        let confidence = if with_context { 0.95 } else { 0.85 };
+        let mut span = EntitySpan::new(start, end, kind.entity_type(), confidence, self.name.clone())
+            .with_metadata("id_kind", kind.label());
+        if kind == IntlIdKind::EuVat {
+            span = span.with_metadata("vat_country", &candidate[..2]);
+        }
+        Some(span)
+    }
+
+    /// 前方窗口内是否出现上下文关键词
+    fn has_context(text: &[u8], start: usize, keywords: &[&str]) -> bool {
+        let window_start = start.saturating_sub(CONTEXT_WINDOW);
+        let window = String::from_utf8_lossy(&text[window_start..start]).to_lowercase();
+        keywords.iter().any(|k| window.contains(k))
+    }
+}
+
+impl Recognizer for InternationalRecognizer {
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn recognizer_type(&self) -> RecognizerType {
+        RecognizerType::Rule
+    }
+
+    fn supported_entities(&self) -> Vec<EntityType> {
+        vec![EntityType::IdCard, EntityType::TaxId, EntityType::Passport]
+    }
+
+    fn analyze(&self, context: &AnalysisContext) -> AnalysisResult {
+        let text = context.text;
+        let mut spans: Vec<EntitySpan> = Vec::new();
+
+        for pattern in &self.patterns {
+            // 候选被拒绝时只前进一个字节重新匹配，避免 "VAT DE…" 中的 "AT DE…" 吞掉真实号码
+            let mut pos = 0;
+            while let Some(m) = pattern.re.find_at(text, pos) {
+                match self.accept(pattern.kind, text, m.start(), m.end(), &spans) {
+                    Some(span) => {
+                        pos = m.end();
+                        spans.push(span);
+                    }
+                    None => pos = m.start() + 1,
+                }
+            }
+        }
+
+        spans.sort_by_key(|s| s.start);
+        AnalysisResult {
+            spans,
+            elapsed_us: 0,
+            recognizer: self.name.clone(),
+        }
+    }
+
+    fn priority(&self) -> i32 {
+        self.priority
+    }
+
+    fn is_enabled(&self) -> bool {
+        self.enabled
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn detect(text: &str) -> Vec<(String, String)> {
+        InternationalRecognizer::default()
+            .analyze(&AnalysisContext::from_text(text.as_bytes()))
+            .spans
+            .iter()
+            .map(|s| (s.text_str(text).to_string(), s.meta("id_kind").unwrap_or_default().to_string()))
+            .collect()
+    }
+
+    #[test]
+    fn test_ssn_exclusions() {
+        assert!(InternationalRecognizer::validate_ssn("536-22-1234"));
+        for invalid in ["000-12-3456", "666-12-3456", "912-70-1234", "536-00-1234", "536-22-0000", "078-05-1120", "53-622-1234"] {
+            assert!(!InternationalRecognizer::validate_ssn(invalid), "{}", invalid);
+        }
+
+        assert_eq!(detect("SSN: 536-22-1234"), vec![("536-22-1234".to_string(), "US_SSN".to_string())]);
+        assert!(detect("order 536221234").is_empty());
+        assert_eq!(detect("ssn 536221234").len(), 1);
+    }
+
+    #[test]
+    fn test_itin() {
+        assert!(InternationalRecognizer::validate_itin("912-70-1234"));
+        assert!(!InternationalRecognizer::validate_itin("912-89-1234"));
+        assert!(!InternationalRecognizer::validate_itin("912-93-1234"));
+        assert_eq!(detect("ITIN 912-94-1234")[0].1, "US_ITIN");
+    }
+
+    #[test]
+    fn test_uk_nino() {
+        assert!(InternationalRecognizer::validate_nino("AB 12 34 56 C"));
+        assert!(!InternationalRecognizer::validate_nino("GB123456A"));
+        assert!(!InternationalRecognizer::validate_nino("DA123456A"));
+        assert_eq!(detect("NI number: AB123456C")[0].1, "UK_NINO");
+    }
+
+    #[test]
+    fn test_de_steuer_id() {
+        assert!(InternationalRecognizer::validate_steuer_id("86095742719"));
+        assert!(!InternationalRecognizer::validate_steuer_id("86095742718"));
+        // 前 10 位无重复数字，不符合规则
+        assert!(!InternationalRecognizer::validate_steuer_id("12345678903"));
+        assert!(detect("86095742719").is_empty());
+        assert_eq!(detect("Steuer-ID: 86 095 742 719")[0].1, "DE_STEUER_ID");
+    }
+
+    #[test]
+    fn test_fr_nir() {
+        assert!(InternationalRecognizer::validate_nir("1 85 05 78 006 084 91"));
+        assert!(!InternationalRecognizer::validate_nir("1 85 05 78 006 084 92"));
+        assert!(!InternationalRecognizer::validate_nir("1 85 15 78 006 084 91"));
+        assert_eq!(detect("NIR 185057800608491")[0].1, "FR_NIR");
+    }
+
+    #[test]
+    fn test_eu_vat_checksums() {
+        for valid in [
+            "ATU13585627",
+            "BE0417497106",
+            "DE136695976",
+            "DK13585628",
+            "FI20774740",
+            "FR83404833048",
+            "IT00743110157",
+            "NL004495445B01",
+            "PL5260250274",
+            "SE556188840401",
+        ] {
+            assert!(InternationalRecognizer::validate_vat(valid), "{}", valid);
+        }
+        for invalid in ["ATU13585628", "DE136695975", "IT00743110158", "PL5260250275", "XX12345678"] {
+            assert!(!InternationalRecognizer::validate_vat(invalid), "{}", invalid);
+        }
+
+        let spans = InternationalRecognizer::default()
+            .analyze(&AnalysisContext::from_text("USt-IdNr. DE136695976".as_bytes()))
+            .spans;
+        assert_eq!(spans.len(), 1);
+        assert_eq!(spans[0].entity_type, EntityType::TaxId);
+        assert_eq!(spans[0].meta("vat_country"), Some("DE"));
+    }
+
+    #[test]
+    fn test_us_passport_requires_context() {
+        assert!(detect("tracking 123456780").is_empty());
+        assert_eq!(detect("Passport No. 123456780")[0].1, "US_PASSPORT");
+    }
+}
@@ -0,0 +1,434 @@
+//! 日韩证件与联系方式识别器
+//!
+//! 面向日本、韩国用户的常见 PII：
+//!
+//! - **マイナンバー (My Number)**: 12 位，末位校验数字；需上下文关键词
+//! - **주민등록번호 (RRN)**: `YYMMDD-GNNNNNC`，出生日期合法性 + 旧版校验位
+//!   （2020 年 10 月后签发的号码不再含校验位，仅在有上下文时接受）
+//! - **电话**: 日本 `090-1234-5678` / `03-1234-5678` / `+81`，韩国 `010-1234-5678` / `02-123-4567` / `+82`；
+//!   不带分隔符的国内号码需要上下文关键词（避免把 `0123456789` 这类订单号当成电话）
+//! - **邮编**: 日本 `〒123-4567`，韩国 5 位（需上下文）
+//!
+//! 证件号产出 `EntityType::IdCard` 并在 `id_kind` 元数据中标明种类，
+//! 电话附带 `phone_region` 元数据。
+
+use super::types::*;
+use super::Recognizer;
+use regex::bytes::Regex;
+
+/// 上下文搜索窗口（字节，向前）
+const CONTEXT_WINDOW: usize = 40;
+
+/// My Number 上下文关键词
+const MY_NUMBER_KEYWORDS: &[&str] = &["マイナンバー", "個人番号", "my number", "mynumber"];
+/// RRN 上下文关键词
+const RRN_KEYWORDS: &[&str] = &["주민등록번호", "주민번호", "rrn", "resident registration"];
+/// 日本邮编上下文关键词
+const JP_POSTAL_KEYWORDS: &[&str] = &["郵便番号", "〒", "postal", "zip"];
+/// 韩国邮编上下文关键词
+const KR_POSTAL_KEYWORDS: &[&str] = &["우편번호", "postal", "zip"];
+/// 无分隔符电话的上下文关键词
+const PHONE_KEYWORDS: &[&str] = &["電話", "携帯", "tel", "phone", "전화", "연락처", "휴대폰", "핸드폰"];
+
+/// 日韩识别器
+pub struct JapanKoreaRecognizer {
+    /// 识别器名称
+    name: String,
+    /// 是否启用
+    enabled: bool,
+    /// 识别器优先级
+    priority: i32,
+    /// My Number 候选（可含空格/短横线分组）
+    my_number_re: Regex,
+    /// RRN 候选
+    rrn_re: Regex,
+    /// 日本电话候选
+    jp_phone_re: Regex,
+    /// 韩国电话候选
+    kr_phone_re: Regex,
+    /// 日本邮编候选
+    jp_postal_re: Regex,
+    /// 韩国邮编候选
+    kr_postal_re: Regex,
+}
+
+impl Default for JapanKoreaRecognizer {
+    fn default() -> Self {
+        Self::new(None, None)
+    }
+}
+
+impl JapanKoreaRecognizer {
+    /// 创建日韩识别器
+    ///
+    /// # 参数
+    ///
+    /// - `name`: 识别器名称（默认 "jp_kr_recognizer"）
+    /// - `priority`: 识别器优先级（默认 95）
+    pub fn new(name: Option<&str>, priority: Option<i32>) -> Self {
+        Self {
+            name: name.unwrap_or("jp_kr_recognizer").to_string(),
+            enabled: true,
+            priority: priority.unwrap_or(95),
+            my_number_re: Regex::new(r"\d{4}[- ]?\d{4}[- ]?\d{4}").expect("invalid My Number regex"),
+            rrn_re: Regex::new(r"\d{6}-?\d{7}").expect("invalid RRN regex"),
+            jp_phone_re: Regex::new(r"(?:\+81[- ]?|0)\d{1,4}[- ]?\d{1,4}[- ]?\d{4}").expect("invalid JP phone regex"),
+            kr_phone_re: Regex::new(r"(?:\+82[- ]?|0)(?:1[016789]|2|[3-6][1-5])[- ]?\d{3,4}[- ]?\d{4}")
+                .expect("invalid KR phone regex"),
+            jp_postal_re: Regex::new(r"\d{3}-\d{4}").expect("invalid JP postal regex"),
+            kr_postal_re: Regex::new(r"\d{5}").expect("invalid KR postal regex"),
+        }
+    }
+
+    // ─── 校验函数 ───
+
+    /// 验证 My Number 校验位
+    ///
+    /// Pₙ 为校验位左侧第 n 位数字，Qₙ = n + 1 (1 ≤ n ≤ 6) 或 n - 5 (7 ≤ n ≤ 11)；
+    /// 余数 r = Σ Pₙ × Qₙ mod 11，r ≤ 1 时校验位为 0，否则为 11 - r。
+    pub fn validate_my_number(number: &str) -> bool {
+        let digits: Vec<u32> = number.chars().filter_map(|c| c.to_digit(10)).collect();
+        if digits.len() != 12 || number.chars().any(|c| !c.is_ascii_digit() && c != '-' && c != ' ') {
+            return false;
+        }
+        let sum: u32 = (1..=11)
+            .map(|n| {
+                let p = digits[11 - n];
+                let q = if n <= 6 { n as u32 + 1 } else { n as u32 - 5 };
+                p * q
+            })
+            .sum();
+        let r = sum % 11;
+        let expected = if r <= 1 { 0 } else { 11 - r };
+        digits[11] == expected
+    }
+
+    /// 验证 RRN 出生日期是否合法（第 7 位决定世纪）
+    pub fn rrn_date_plausible(rrn: &str) -> bool {
+        let digits: Vec<u32> = rrn.chars().filter_map(|c| c.to_digit(10)).collect();
+        if digits.len() != 13 {
+            return false;
+        }
+        let yy = digits[0] * 10 + digits[1];
+        let month = digits[2] * 10 + digits[3];
+        let day = digits[4] * 10 + digits[5];
+        let century = match digits[6] {
+            1 | 2 | 5 | 6 => 1900,
+            3 | 4 | 7 | 8 => 2000,
+            9 | 0 => 1800,
+            _ => return false,
+        };
+        let year = century + yy;
+        let leap = (year.is_multiple_of(4) && !year.is_multiple_of(100)) || year.is_multiple_of(400);
+        let max_day = match month {
+            1 | 3 | 5 | 7 | 8 | 10 | 12 => 31,
+            4 | 6 | 9 | 11 => 30,
+            2 if leap => 29,
+            2 => 28,
+            _ => return false,
+        };
+        (1..=max_day).contains(&day)
+    }
+
+    /// 验证 RRN 旧版校验位（权重 2..9,2..5，校验位 = (11 - Σ mod 11) mod 10）
+    pub fn validate_rrn_checksum(rrn: &str) -> bool {
+        let digits: Vec<u32> = rrn.chars().filter_map(|c| c.to_digit(10)).collect();
+        if digits.len() != 13 {
+            return false;
+        }
+        let weights = [2, 3, 4, 5, 6, 7, 8, 9, 2, 3, 4, 5];
+        let sum: u32 = digits.iter().zip(weights.iter()).map(|(d, w)| d * w).sum();
+        (11 - sum % 11) % 10 == digits[12]
+    }
+
+    /// RRN 出生年份是否晚于 2020 年（此后签发的号码不含校验位）
+    fn rrn_born_after_2020(rrn: &str) -> bool {
+        let digits: Vec<u32> = rrn.chars().filter_map(|c| c.to_digit(10)).collect();
+        matches!(digits.get(6), Some(3 | 4 | 7 | 8)) && digits[0] * 10 + digits[1] >= 20
+    }
+
+    /// 电话号码分隔符必须一致：全部省略或全部存在（"010-12345678" 这类北京座机不算）
+    fn consistent_separators(candidate: &str, groups: usize) -> bool {
+        let body = candidate.trim_start_matches('+');
+        let separators = body.chars().filter(|c| *c == '-' || *c == ' ').count();
+        separators == 0 || separators == groups - 1 || (candidate.starts_with('+') && separators == groups)
+    }
+
+    /// 日本电话：国内号码（含前导 0）10 位，或以 050/070/080/090/020 开头的 11 位
+    fn is_jp_phone(candidate: &str) -> bool {
+        let digits: String = candidate.chars().filter(char::is_ascii_digit).collect();
+        let national = match digits.strip_prefix("81") {
+            Some(rest) if candidate.starts_with('+') => format!("0{}", rest),
+            _ => digits,
+        };
+        let valid_len = match national.len() {
+            10 => !national.starts_with("00"),
+            11 => ["050", "070", "080", "090", "020"].iter().any(|p| national.starts_with(p)),
+            _ => false,
+        };
+        valid_len && Self::consistent_separators(candidate, 3)
+    }
+
+    /// 韩国电话：国内号码 9~11 位
+    fn is_kr_phone(candidate: &str) -> bool {
+        let digits = candidate.chars().filter(char::is_ascii_digit).count();
+        let national = if candidate.starts_with('+') { digits - 1 } else { digits };
+        (9..=11).contains(&national) && Self::consistent_separators(candidate, 3)
+    }
+
+    // ─── 扫描辅助 ───
+
+    /// 候选前后不能紧贴 ASCII 数字/字母
+    fn is_bounded(text: &[u8], start: usize, end: usize) -> bool {
+        let before = start == 0 || !text[start - 1].is_ascii_alphanumeric();
+        let after = end >= text.len() || !text[end].is_ascii_alphanumeric();
+        before && after
+    }
+
+    /// 前方窗口内是否出现关键词
+    fn has_context(text: &[u8], start: usize, keywords: &[&str]) -> bool {
+        let window_start = start.saturating_sub(CONTEXT_WINDOW);
+        let window = String::from_utf8_lossy(&text[window_start..start]).to_lowercase();
+        keywords.iter().any(|k| window.contains(k))
+    }
+
+    /// 文本是否包含韩文字母（用于日韩电话歧义时的优先级判定）
+    fn contains_hangul(text: &[u8]) -> bool {
+        String::from_utf8_lossy(text)
+            .chars()
+            .any(|c| matches!(c, '\u{AC00}'..='\u{D7A3}' | '\u{1100}'..='\u{11FF}'))
+    }
+
+    /// 加入不与已有实体重叠的跨度
+    fn push_unique(spans: &mut Vec<EntitySpan>, span: EntitySpan) {
+        if !spans.iter().any(|s| s.overlaps_with(&span)) {
+            spans.push(span);
+        }
+    }
+
+    fn find_ids(&self, text: &[u8], spans: &mut Vec<EntitySpan>) {
+        for m in self.rrn_re.find_iter(text) {
+            if !Self::is_bounded(text, m.start(), m.end()) {
+                continue;
+            }
+            let candidate = std::str::from_utf8(m.as_bytes()).unwrap_or_default();
+            if !Self::rrn_date_plausible(candidate) {
+                continue;
+            }
+            let with_context = Self::has_context(text, m.start(), RRN_KEYWORDS);
+            let checksum_ok = Self::validate_rrn_checksum(candidate);
+            let unchecked_ok = with_context && Self::rrn_born_after_2020(candidate);
+            if !(checksum_ok || unchecked_ok) {
+                continue;
+            }
+            // 无连字符的 13 位数字需要上下文，避免与订单号等混淆
+            if !candidate.contains('-') && !with_context {
+                continue;
+            }
+            Self::push_unique(
+                spans,
+                EntitySpan::new(m.start(), m.end(), EntityType::IdCard, if with_context { 1.0 } else { 0.9 }, self.name.clone())
+                    .with_metadata("id_kind", "KR_RRN"),
+            );
+        }
+
+        for m in self.my_number_re.find_iter(text) {
+            if !Self::is_bounded(text, m.start(), m.end()) {
+                continue;
+            }
+            let candidate = std::str::from_utf8(m.as_bytes()).unwrap_or_default();
+            if !Self::validate_my_number(candidate) || !Self::has_context(text, m.start(), MY_NUMBER_KEYWORDS) {
+                continue;
+            }
+            Self::push_unique(
+                spans,
+                EntitySpan::new(m.start(), m.end(), EntityType::IdCard, 1.0, self.name.clone())
+                    .with_metadata("id_kind", "JP_MY_NUMBER"),
+            );
+        }
+    }
+
+    fn find_phones(&self, text: &[u8], spans: &mut Vec<EntitySpan>) {
+        let jp = (&self.jp_phone_re, "JP", Self::is_jp_phone as fn(&str) -> bool);
+        let kr = (&self.kr_phone_re, "KR", Self::is_kr_phone as fn(&str) -> bool);
+        let order = if Self::contains_hangul(text) { [kr, jp] } else { [jp, kr] };
+
+        for (re, region, validate) in order {
+            for m in re.find_iter(text) {
+                let start = m.start();
+                // "+81" 前缀本身以非字母数字开头，只检查前一字节
+                if !Self::is_bounded(text, start, m.end()) {
+                    continue;
+                }
+                let candidate = std::str::from_utf8(m.as_bytes()).unwrap_or_default();
+                if !validate(candidate) {
+                    continue;
+                }
+                // 无分隔符且无国际区号的纯数字需要上下文
+                let unseparated = !candidate.contains(['-', ' ']) && !candidate.starts_with('+');
+                if unseparated && !Self::has_context(text, start, PHONE_KEYWORDS) {
+                    continue;
+                }
+                Self::push_unique(
+                    spans,
+                    EntitySpan::new(start, m.end(), EntityType::Phone, 0.9, self.name.clone())
+                        .with_metadata("phone_region", region),
+                );
+            }
+        }
+    }
+
+    fn find_postal_codes(&self, text: &[u8], spans: &mut Vec<EntitySpan>) {
+        for m in self.jp_postal_re.find_iter(text) {
+            if !Self::is_bounded(text, m.start(), m.end()) {
+                continue;
+            }
+            let after_mark = text[..m.start()].ends_with("〒".as_bytes())
+                || text[..m.start()].ends_with("〒 ".as_bytes());
+            if !after_mark && !Self::has_context(text, m.start(), JP_POSTAL_KEYWORDS) {
+                continue;
+            }
+            Self::push_unique(
+                spans,
+                EntitySpan::new(m.start(), m.end(), EntityType::PostalCode, 0.95, self.name.clone())
+                    .with_metadata("postal_region", "JP"),
+            );
+        }
+
+        for m in self.kr_postal_re.find_iter(text) {
+            if !Self::is_bounded(text, m.start(), m.end()) || !Self::has_context(text, m.start(), KR_POSTAL_KEYWORDS) {
+                continue;
+            }
+            Self::push_unique(
+                spans,
+                EntitySpan::new(m.start(), m.end(), EntityType::PostalCode, 0.9, self.name.clone())
+                    .with_metadata("postal_region", "KR"),
+            );
+        }
+    }
+}
+
+impl Recognizer for JapanKoreaRecognizer {
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn recognizer_type(&self) -> RecognizerType {
+        RecognizerType::Rule
+    }
+
+    fn supported_entities(&self) -> Vec<EntityType> {
+        vec![EntityType::IdCard, EntityType::Phone, EntityType::PostalCode]
+    }
+
+    fn analyze(&self, context: &AnalysisContext) -> AnalysisResult {
+        let text = context.text;
+        let mut spans = Vec::new();
+        self.find_ids(text, &mut spans);
+        self.find_phones(text, &mut spans);
+        self.find_postal_codes(text, &mut spans);
+        spans.sort_by_key(|s| s.start);
+
+        AnalysisResult {
+            spans,
+            elapsed_us: 0,
+            recognizer: self.name.clone(),
+        }
+    }
+
+    fn priority(&self) -> i32 {
+        self.priority
+    }
+
+    fn is_enabled(&self) -> bool {
+        self.enabled
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn detect(text: &str) -> Vec<(String, EntityType, Option<String>)> {
+        JapanKoreaRecognizer::default()
+            .analyze(&AnalysisContext::from_text(text.as_bytes()))
+            .spans
+            .iter()
+            .map(|s| {
+                let kind = s.meta("id_kind").or(s.meta("phone_region")).or(s.meta("postal_region"));
+                (s.text_str(text).to_string(), s.entity_type.clone(), kind.map(str::to_string))
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_my_number_check_digit() {
+        assert!(JapanKoreaRecognizer::validate_my_number("123456789018"));
+        assert!(JapanKoreaRecognizer::validate_my_number("1234 5678 9018"));
+        assert!(!JapanKoreaRecognizer::validate_my_number("123456789019"));
+
+        let found = detect("マイナンバー：1234-5678-9018");
+        assert_eq!(found, vec![("1234-5678-9018".to_string(), EntityType::IdCard, Some("JP_MY_NUMBER".to_string()))]);
+        assert!(detect("注文番号 123456789018").is_empty());
+    }
+
+    #[test]
+    fn test_rrn_checksum_and_date() {
+        assert!(JapanKoreaRecognizer::validate_rrn_checksum("900101-1234568"));
+        assert!(!JapanKoreaRecognizer::validate_rrn_checksum("900101-1234567"));
+        assert!(JapanKoreaRecognizer::rrn_date_plausible("000229-3123456")); // 2000 年闰年
+        assert!(!JapanKoreaRecognizer::rrn_date_plausible("020229-3123459")); // 2002 年非闰年
+        assert!(!JapanKoreaRecognizer::rrn_date_plausible("901301-1234568")); // 13 月
+
+        assert_eq!(detect("RRN 900101-1234568")[0].2.as_deref(), Some("KR_RRN"));
+        assert!(detect("020229-3123459").is_empty());
+    }
+
+    #[test]
+    fn test_rrn_without_checksum_after_2020() {
+        assert!(!JapanKoreaRecognizer::validate_rrn_checksum("210315-3123450"));
+        assert!(detect("210315-3123450").is_empty());
+        assert_eq!(detect("주민등록번호: 210315-3123450").len(), 1);
+    }
+
+    #[test]
+    fn test_jp_phones() {
+        for phone in ["090-1234-5678", "03-1234-5678", "+81 90-1234-5678", "09012345678"] {
+            let found = detect(&format!("電話 {}", phone));
+            assert_eq!(found.len(), 1, "phone {}", phone);
+            assert_eq!(found[0].0, phone);
+            assert_eq!(found[0].1, EntityType::Phone);
+        }
+    }
+
+    #[test]
+    fn test_kr_phones() {
+        for phone in ["010-1234-5678", "02-123-4567", "+82 10-1234-5678"] {
+            let found = detect(&format!("전화 {}", phone));
+            assert_eq!(found.len(), 1, "phone {}", phone);
+            assert_eq!(found[0].0, phone);
+            assert_eq!(found[0].2.as_deref(), Some("KR"));
+        }
+    }
+
+    #[test]
+    fn test_beijing_landline_not_matched() {
+        assert!(detect("北京座机 010-12345678").is_empty());
+        assert!(detect("上海座机 021-1234567").is_empty());
+    }
+
+    #[test]
+    fn test_unseparated_phone_needs_context() {
+        assert!(detect("注文番号 0123456789").is_empty());
+        assert!(detect("ID 0312345678").is_empty());
+        assert_eq!(detect("TEL:0312345678")[0].0, "0312345678");
+        assert_eq!(detect("+819012345678")[0].0, "+819012345678");
+    }
+
+    #[test]
+    fn test_postal_codes() {
+        assert_eq!(detect("〒100-0001 東京都千代田区")[0].0, "100-0001");
+        assert_eq!(detect("우편번호 06236")[0].0, "06236");
+        assert!(detect("共 12345 件").is_empty());
+    }
+}
@@ -27,11 +27,24 @@
 
 pub mod types;
 pub mod registry;
+pub mod normalizer;
 pub mod regex_recognizer;
 pub mod aho_corasick_recognizer;
 pub mod ner_recognizer;
 pub mod context_enhancer;
 pub mod checksum_recognizer;
+pub mod chinese_name_recognizer;
+pub mod network_recognizer;
+pub mod financial_recognizer;
+pub mod cn_identifier_recognizer;
+pub mod jp_kr_recognizer;
+pub mod international_recognizer;
+pub mod crypto_recognizer;
+pub mod cloud_credential_recognizer;
+pub mod connection_string_recognizer;
+pub mod http_credential_recognizer;
+pub mod edm_recognizer;
+pub mod dictionary_recognizer;
 
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
@@ -39,9 +52,22 @@ use std::collections::HashMap;
 // 重新导出核心类型
 pub use types::*;
 pub use registry::RecognizerRegistry;
+pub use normalizer::{NormalizedText, TextNormalizer};
 pub use regex_recognizer::RegexRecognizer;
 pub use aho_corasick_recognizer::AhoCorasickRecognizer;
 pub use ner_recognizer::NerRecognizer;
+pub use chinese_name_recognizer::ChineseNameRecognizer;
+pub use network_recognizer::{NetworkFilter, NetworkRecognizer};
+pub use financial_recognizer::{CardBrand, FinancialRecognizer};
+pub use cn_identifier_recognizer::CnIdentifierRecognizer;
+pub use jp_kr_recognizer::JapanKoreaRecognizer;
+pub use international_recognizer::InternationalRecognizer;
+pub use crypto_recognizer::CryptoWalletRecognizer;
+pub use cloud_credential_recognizer::CloudCredentialRecognizer;
+pub use connection_string_recognizer::{ConnectionStringOptions, ConnectionStringRecognizer};
+pub use http_credential_recognizer::HttpCredentialRecognizer;
+pub use edm_recognizer::{EdmColumn, EdmColumnSpec, EdmIndex, EdmRecognizer};
+pub use dictionary_recognizer::{DictionaryBuildReport, DictionaryRecognizer, DictionarySpec, MappedDictionary};
 
 
 // ─────────────────────────────────────────────────────────────────────────────
@@ -0,0 +1,420 @@
+//! 网络地址识别器
+//!
+//! 基于真实解析（`std::net`）而非正则的网络地址识别，弥补正则难以表达 IPv6 的缺陷。
+//!
+//! # 支持的格式
+//!
+//! - **IPv6**: 完整/压缩 (`::`) 形式、内嵌 IPv4 (`::ffff:1.2.3.4`)、Zone ID (`fe80::1%eth0`)、
+//!   前缀长度 (`2001:db8::/32`)、带端口 (`[::1]:443`)
+//! - **IPv4 CIDR**: `10.0.0.0/8`（裸 IPv4 仍由 `rules/network/ip.yaml` 的正则规则负责）
+//! - **MAC 地址**: 冒号 `00:1A:2B:3C:4D:5E`、短横线 `00-1A-2B-3C-4D-5E`、点分 `001a.2b3c.4d5e`
+//!
+//! # 保留地址过滤
+//!
+//! 通过 `NetworkFilter` 可选跳过私有、回环、文档示例地址段，
+//! 过滤开关为原子标志，运行时修改无需重建引擎。
+
+use super::types::*;
+use super::Recognizer;
+use std::net::{Ipv4Addr, Ipv6Addr};
+use std::str::FromStr;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, Ordering};
+
+// ─────────────────────────────────────────────────────────────────────────────
+// 保留地址过滤 (NetworkFilter)
+// ─────────────────────────────────────────────────────────────────────────────
+
+/// 保留地址段过滤开关
+///
+/// 由 `HybridEngine` 持有并与识别器共享，设置页修改后即时生效。
+#[derive(Debug, Default)]
+pub struct NetworkFilter {
+    /// 跳过私有地址（10/8、172.16/12、192.168/16、169.254/16、fc00::/7、fe80::/10）
+    pub skip_private: AtomicBool,
+    /// 跳过回环/未指定地址（127/8、::1、::）
+    pub skip_loopback: AtomicBool,
+    /// 跳过文档示例地址（192.0.2/24、198.51.100/24、203.0.113/24、2001:db8::/32）
+    pub skip_documentation: AtomicBool,
+}
+
+impl NetworkFilter {
+    /// 批量更新过滤开关
+    pub fn set(&self, skip_private: bool, skip_loopback: bool, skip_documentation: bool) {
+        self.skip_private.store(skip_private, Ordering::Relaxed);
+        self.skip_loopback.store(skip_loopback, Ordering::Relaxed);
+        self.skip_documentation.store(skip_documentation, Ordering::Relaxed);
+    }
+
+    /// IPv4 地址是否应被跳过
+    fn skips_v4(&self, ip: &Ipv4Addr) -> bool {
+        let o = ip.octets();
+        (self.skip_private.load(Ordering::Relaxed) && (ip.is_private() || ip.is_link_local()))
+            || (self.skip_loopback.load(Ordering::Relaxed) && (ip.is_loopback() || ip.is_unspecified()))
+            || (self.skip_documentation.load(Ordering::Relaxed)
+                && matches!((o[0], o[1], o[2]), (192, 0, 2) | (198, 51, 100) | (203, 0, 113)))
+    }
+
+    /// IPv6 地址是否应被跳过
+    fn skips_v6(&self, ip: &Ipv6Addr) -> bool {
+        let seg = ip.segments();
+        (self.skip_private.load(Ordering::Relaxed)
+            && ((seg[0] & 0xfe00) == 0xfc00 || (seg[0] & 0xffc0) == 0xfe80))
+            || (self.skip_loopback.load(Ordering::Relaxed) && (ip.is_loopback() || ip.is_unspecified()))
+            || (self.skip_documentation.load(Ordering::Relaxed) && seg[0] == 0x2001 && seg[1] == 0x0db8)
+    }
+}
+
+// ─────────────────────────────────────────────────────────────────────────────
+// 网络地址识别器
+// ─────────────────────────────────────────────────────────────────────────────
+
+/// 识别出的网络地址种类
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum NetworkKind {
+    Ipv6,
+    Ipv6Port,
+    Ipv4Cidr,
+    Mac,
+}
+
+impl NetworkKind {
+    fn entity_type(self) -> EntityType {
+        match self {
+            Self::Mac => EntityType::MacAddress,
+            _ => EntityType::IpAddress,
+        }
+    }
+
+    fn mask(self) -> Option<&'static str> {
+        match self {
+            Self::Ipv6 => Some("<IPv6>"),
+            Self::Ipv6Port => Some("<IPv6_Port>"),
+            Self::Ipv4Cidr => Some("<IPv4_CIDR>"),
+            Self::Mac => None,
+        }
+    }
+
+    fn rule_name(self) -> &'static str {
+        match self {
+            Self::Ipv6 => "ipv6",
+            Self::Ipv6Port => "ipv6_port",
+            Self::Ipv4Cidr => "ipv4_cidr",
+            Self::Mac => "mac",
+        }
+    }
+}
+
+/// 网络地址识别器
+///
+/// 产出 `EntityType::IpAddress`（IPv6、IPv4 CIDR）与 `EntityType::MacAddress` 实体。
+pub struct NetworkRecognizer {
+    /// 识别器名称
+    name: String,
+    /// 是否启用
+    enabled: bool,
+    /// 识别器优先级
+    priority: i32,
+    /// 保留地址过滤开关
+    filter: Arc<NetworkFilter>,
+}
+
+impl Default for NetworkRecognizer {
+    fn default() -> Self {
+        Self::new(None, None, None)
+    }
+}
+
+impl NetworkRecognizer {
+    /// 创建网络地址识别器
+    ///
+    /// # 参数
+    ///
+    /// - `name`: 识别器名称（默认 "network_recognizer"）
+    /// - `priority`: 识别器优先级（默认 95，高于正则引擎，解析结果可雕刻正则的粗匹配）
+    /// - `filter`: 共享的保留地址过滤开关（默认全部关闭）
+    pub fn new(name: Option<&str>, priority: Option<i32>, filter: Option<Arc<NetworkFilter>>) -> Self {
+        Self {
+            name: name.unwrap_or("network_recognizer").to_string(),
+            enabled: true,
+            priority: priority.unwrap_or(95),
+            filter: filter.unwrap_or_default(),
+        }
+    }
+
+    /// 候选字符集：十六进制、分隔符、Zone ID 字符、方括号
+    fn is_token_byte(b: u8) -> bool {
+        b.is_ascii_alphanumeric() || matches!(b, b':' | b'.' | b'-' | b'/' | b'%' | b'_' | b'[' | b']')
+    }
+
+    /// 在字节流中扫描网络地址，返回 (start, end, kind)
+    ///
+    /// 以极大 token 为单位判定，不在失败的 token 内部重新起步，
+    /// 避免 "1:2:3:4:5:6:7:8:9" 的后缀被误判为合法 IPv6。
+    fn scan(&self, text: &[u8]) -> Vec<(usize, usize, NetworkKind)> {
+        let mut found = Vec::new();
+        let mut i = 0;
+
+        while i < text.len() {
+            if !Self::is_token_byte(text[i]) {
+                i += 1;
+                continue;
+            }
+
+            let mut end = i;
+            while end < text.len() && Self::is_token_byte(text[end]) {
+                end += 1;
+            }
+            let run_end = end;
+            // 去除句末标点（"地址是 fe80::1." / "mac: 00:1a:…:5e,"）
+            while end > i && matches!(text[end - 1], b'.' | b':' | b'-' | b'/' | b'%' | b'_') {
+                end -= 1;
+            }
+
+            // token 只含 ASCII，可安全转为 &str
+            let token = std::str::from_utf8(&text[i..end]).unwrap_or_default();
+            if let Some((offset, len, kind)) = self.classify_with_label(token) {
+                found.push((i + offset, i + offset + len, kind));
+            }
+            i = run_end;
+        }
+
+        found
+    }
+
+    /// 判定 token；整体不合法时依次尝试剥离 "host:"、"http://" 一类前缀，
+    /// 以及从 URL 中的 `[` 处起步（"http://[::1]:443/index"）
+    fn classify_with_label(&self, token: &str) -> Option<(usize, usize, NetworkKind)> {
+        let mut offsets = vec![0];
+        let label_len = token.bytes().take_while(|b| b.is_ascii_alphabetic() || *b == b'_').count();
+        if label_len > 0 && token.as_bytes().get(label_len) == Some(&b':') {
+            let after = label_len + 1;
+            offsets.push(after + token[after..].bytes().take_while(|b| *b == b'/').count());
+        }
+        if let Some(bracket) = token.find('[') {
+            offsets.push(bracket);
+        }
+
+        offsets.into_iter().find_map(|offset| {
+            self.classify(&token[offset..]).map(|(len, kind)| (offset, len, kind))
+        })
+    }
+
+    /// 判定 token 的类型，返回 (匹配长度, 类型)；被过滤或不合法时返回 `None`
+    fn classify(&self, token: &str) -> Option<(usize, NetworkKind)> {
+        if token.is_empty() {
+            return None;
+        }
+        if token.starts_with('[') {
+            return self.classify_bracketed(token);
+        }
+        if is_mac(token) {
+            return Some((token.len(), NetworkKind::Mac));
+        }
+        if token.contains(':') {
+            let ip = parse_ipv6_with_suffix(token)?;
+            return (!self.filter.skips_v6(&ip)).then_some((token.len(), NetworkKind::Ipv6));
+        }
+        let ip = parse_ipv4_cidr(token)?;
+        (!self.filter.skips_v4(&ip)).then_some((token.len(), NetworkKind::Ipv4Cidr))
+    }
+
+    /// 解析 `[IPv6]` 或 `[IPv6]:port`，允许后面跟路径等其它字符
+    fn classify_bracketed(&self, token: &str) -> Option<(usize, NetworkKind)> {
+        let close = token.find(']')?;
+        let inner = &token[1..close];
+        let addr = inner.split_once('%').map_or(inner, |(a, _)| a);
+        let ip = Ipv6Addr::from_str(addr).ok()?;
+        if self.filter.skips_v6(&ip) {
+            return None;
+        }
+
+        let rest = &token[close + 1..];
+        if let Some(port) = rest.strip_prefix(':') {
+            let digits = port.bytes().take_while(u8::is_ascii_digit).count();
+            if (1..=5).contains(&digits) && port[..digits].parse::<u32>().is_ok_and(|p| p <= 65535) {
+                return Some((close + 2 + digits, NetworkKind::Ipv6Port));
+            }
+        }
+        Some((close + 1, NetworkKind::Ipv6))
+    }
+}
+
+// ─────────────────────────────────────────────────────────────────────────────
+// 解析辅助函数
+// ─────────────────────────────────────────────────────────────────────────────
+
+/// 解析带可选 Zone ID 与前缀长度的 IPv6（`fe80::1%eth0`、`2001:db8::/32`）
+fn parse_ipv6_with_suffix(token: &str) -> Option<Ipv6Addr> {
+    let (addr, prefix) = match token.split_once('/') {
+        Some((a, p)) => (a, Some(p)),
+        None => (token, None),
+    };
+    if let Some(p) = prefix
+        && !p.parse::<u8>().is_ok_and(|n| n <= 128)
+    {
+        return None;
+    }
+    let addr = match addr.split_once('%') {
+        Some((a, zone)) => {
+            if zone.is_empty() || !zone.bytes().all(|b| b.is_ascii_alphanumeric() || matches!(b, b'_' | b'-' | b'.')) {
+                return None;
+            }
+            a
+        }
+        None => addr,
+    };
+
+    // 排除 "ab::cd" 一类代码标识符：至少含一个数字，或至少三组
+    let groups = addr.split(':').filter(|g| !g.is_empty()).count();
+    if groups == 0 || (!addr.bytes().any(|b| b.is_ascii_digit()) && groups < 3) {
+        return None;
+    }
+    Ipv6Addr::from_str(addr).ok()
+}
+
+/// 解析 IPv4 CIDR（`a.b.c.d/n`，n ≤ 32）
+fn parse_ipv4_cidr(token: &str) -> Option<Ipv4Addr> {
+    let (addr, prefix) = token.split_once('/')?;
+    if !prefix.parse::<u8>().is_ok_and(|n| n <= 32) {
+        return None;
+    }
+    Ipv4Addr::from_str(addr).ok()
+}
+
+/// 判断是否为 MAC 地址（冒号/短横线 6 组，或 Cisco 点分 3 组）
+fn is_mac(token: &str) -> bool {
+    let b = token.as_bytes();
+    match b.len() {
+        17 => {
+            let sep = b[2];
+            (sep == b':' || sep == b'-')
+                && b.chunks(3).all(|c| {
+                    c[0].is_ascii_hexdigit() && c[1].is_ascii_hexdigit() && (c.len() == 2 || c[2] == sep)
+                })
+        }
+        14 => b.chunks(5).all(|c| {
+            c[..4].iter().all(u8::is_ascii_hexdigit) && (c.len() == 4 || c[4] == b'.')
+        }),
+        _ => false,
+    }
+}
+
+impl Recognizer for NetworkRecognizer {
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn recognizer_type(&self) -> RecognizerType {
+        RecognizerType::Rule
+    }
+
+    fn supported_entities(&self) -> Vec<EntityType> {
+        vec![EntityType::IpAddress, EntityType::MacAddress]
+    }
+
+    fn analyze(&self, context: &AnalysisContext) -> AnalysisResult {
+        let spans = self
+            .scan(context.text)
+            .into_iter()
+            .map(|(start, end, kind)| {
+                let mut span = EntitySpan::new(start, end, kind.entity_type(), 1.0, self.name.clone());
+                span.mask = kind.mask().map(str::to_string);
+                span.context = Some(format!("rule:{}", kind.rule_name()));
+                span
+            })
+            .collect();
+
+        AnalysisResult {
+            spans,
+            elapsed_us: 0,
+            recognizer: self.name.clone(),
+        }
+    }
+
+    fn priority(&self) -> i32 {
+        self.priority
+    }
+
+    fn is_enabled(&self) -> bool {
+        self.enabled
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn detect(recognizer: &NetworkRecognizer, text: &str) -> Vec<(String, EntityType)> {
+        recognizer
+            .analyze(&AnalysisContext::from_text(text.as_bytes()))
+            .spans
+            .iter()
+            .map(|s| (s.text_str(text).to_string(), s.entity_type.clone()))
+            .collect()
+    }
+
+    fn texts(text: &str) -> Vec<String> {
+        detect(&NetworkRecognizer::default(), text).into_iter().map(|(t, _)| t).collect()
+    }
+
+    #[test]
+    fn test_ipv6_forms() {
+        assert_eq!(texts("server 2001:0db8:85a3:0000:0000:8a2e:0370:7334 up"), vec!["2001:0db8:85a3:0000:0000:8a2e:0370:7334"]);
+        assert_eq!(texts("地址fe80::1%eth0，已连接"), vec!["fe80::1%eth0"]);
+        assert_eq!(texts("loopback ::1."), vec!["::1"]);
+        assert_eq!(texts("mapped ::ffff:192.168.1.1"), vec!["::ffff:192.168.1.1"]);
+        assert_eq!(texts("route 2001:db8::/32"), vec!["2001:db8::/32"]);
+    }
+
+    #[test]
+    fn test_ipv6_bracketed_port() {
+        let recognizer = NetworkRecognizer::default();
+        let spans = recognizer.analyze(&AnalysisContext::from_text(b"GET http://[::1]:443/index"));
+        assert_eq!(spans.spans.len(), 1);
+        assert_eq!(spans.spans[0].mask.as_deref(), Some("<IPv6_Port>"));
+        assert_eq!(texts("http://[2001:db8::1]:8080/a"), vec!["[2001:db8::1]:8080"]);
+    }
+
+    #[test]
+    fn test_label_prefix() {
+        assert_eq!(texts("host:fe80::1 ip:10.0.0.0/8"), vec!["fe80::1", "10.0.0.0/8"]);
+        assert_eq!(texts("mac:00:1a:2b:3c:4d:5e"), vec!["00:1a:2b:3c:4d:5e"]);
+    }
+
+    #[test]
+    fn test_ipv6_rejects_non_addresses() {
+        assert!(texts("std::vector and ab::cd").is_empty());
+        assert!(texts("time 12:30:45").is_empty());
+        assert!(texts("1:2:3:4:5:6:7:8:9").is_empty());
+    }
+
+    #[test]
+    fn test_ipv4_cidr() {
+        assert_eq!(texts("allow 10.0.0.0/8;"), vec!["10.0.0.0/8"]);
+        assert!(texts("bad 10.0.0.0/33").is_empty());
+        assert!(texts("plain 10.0.0.1 only").is_empty());
+    }
+
+    #[test]
+    fn test_mac_notations() {
+        let recognizer = NetworkRecognizer::default();
+        for mac in ["00:1A:2B:3C:4D:5E", "00-1a-2b-3c-4d-5e", "001a.2b3c.4d5e"] {
+            let text = format!("mac={}", mac);
+            assert_eq!(detect(&recognizer, &text), vec![(mac.to_string(), EntityType::MacAddress)]);
+        }
+        assert!(texts("uuid 123e4567-e89b-12d3-a456-426614174000").is_empty());
+    }
+
+    #[test]
+    fn test_filter_reserved_ranges() {
+        let filter = Arc::new(NetworkFilter::default());
+        let recognizer = NetworkRecognizer::new(None, None, Some(filter.clone()));
+        let text = "::1 fe80::1 2001:db8::1 2400:3200::1 192.168.0.0/16 203.0.113.0/24 8.8.8.0/24";
+        assert_eq!(detect(&recognizer, text).len(), 7);
+
+        filter.set(true, true, true);
+        let remaining: Vec<String> = detect(&recognizer, text).into_iter().map(|(t, _)| t).collect();
+        assert_eq!(remaining, vec!["2400:3200::1", "8.8.8.0/24"]);
+    }
+}
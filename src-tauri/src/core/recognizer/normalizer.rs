@@ -0,0 +1,341 @@
+//! 文本归一化层
+//!
+//! 针对刻意规避脱敏的写法，在识别前把文本还原为"标准形式"：
+//!
+//! - 全角数字与 `＠` → 半角（`１３８` → `138`）；全角标点（`，` `：` `（`）不改写，
+//!   否则几乎每段中文都会触发二次识别
+//! - 中文数字串 → 阿拉伯数字（`一三八零零…` → `13800…`，含口语"幺"）
+//! - 数字串内部的空格/连字符去除（`138 0013 8000` → `13800138000`）
+//! - 口语化邮箱（`zhangsan at example dot com`、`[at]` / `(dot)`）→ `@` / `.`
+//!
+//! 归一化结果附带逐字节偏移映射，识别器在归一化文本上的命中可以精确映射回原文字节区间；
+//! 同时记录被改写的区间，二次识别只需在这些区间附近的窗口内进行。
+//! 数字串仅在足够长（≥ 10 位）且分隔符一致时才合并，避免日期、时间等普通数字被误拼接。
+
+use regex::Regex;
+use std::ops::Range;
+
+/// 数字串合并的最少位数（低于此值保持原样）
+const MIN_RUN_DIGITS: usize = 10;
+
+/// 数字串中单组的最大位数（超过说明是独立长数字，如时间戳）
+const MAX_GROUP_DIGITS: usize = 8;
+
+/// 归一化后的文本及其到原文的偏移映射
+#[derive(Debug, Clone)]
+pub struct NormalizedText {
+    /// 归一化后的文本（UTF-8）
+    pub text: Vec<u8>,
+    /// 每个归一化字节对应的原文字节区间 `[start, end)`
+    ranges: Vec<(usize, usize)>,
+    /// 归一化文本中被改写（替换、或紧跟在被删除内容之后）的区间，升序且互不相交
+    rewritten: Vec<Range<usize>>,
+}
+
+impl NormalizedText {
+    fn new(original: &[u8], out: OffsetBuilder) -> Self {
+        // 映射到同一原文区间的连续字节为一组；内容与原文不同、或与前一组之间有缺口即为改写
+        let mut rewritten: Vec<Range<usize>> = Vec::new();
+        let mut prev_end = 0;
+        let mut i = 0;
+        while i < out.ranges.len() {
+            let (start, end) = out.ranges[i];
+            let mut j = i + 1;
+            while j < out.ranges.len() && out.ranges[j] == (start, end) {
+                j += 1;
+            }
+            if start != prev_end || out.text[i..j] != original[start..end] {
+                match rewritten.last_mut() {
+                    Some(last) if last.end == i => last.end = j,
+                    _ => rewritten.push(i..j),
+                }
+            }
+            prev_end = end;
+            i = j;
+        }
+        Self { text: out.text, ranges: out.ranges, rewritten }
+    }
+
+    /// 改写区间向两侧各扩展 `margin` 字节（对齐到字符边界）后的识别窗口，重叠的窗口合并
+    ///
+    /// 返回（窗口, 窗口内的改写区间），均为归一化文本偏移。
+    pub fn rewritten_windows(&self, margin: usize) -> Vec<(Range<usize>, Range<usize>)> {
+        let is_boundary = |i: usize| i == 0 || i >= self.text.len() || (self.text[i] & 0xC0) != 0x80;
+        let mut windows: Vec<(Range<usize>, Range<usize>)> = Vec::new();
+        for core in &self.rewritten {
+            let mut start = core.start.saturating_sub(margin);
+            while !is_boundary(start) {
+                start -= 1;
+            }
+            let mut end = (core.end + margin).min(self.text.len());
+            while !is_boundary(end) {
+                end += 1;
+            }
+            match windows.last_mut() {
+                Some((window, merged)) if window.end >= start => {
+                    window.end = end;
+                    merged.end = core.end;
+                }
+                _ => windows.push((start..end, core.clone())),
+            }
+        }
+        windows
+    }
+
+    /// 将归一化文本上的区间映射回原文区间
+    pub fn map_range(&self, start: usize, end: usize) -> Option<(usize, usize)> {
+        if start >= end || end > self.ranges.len() {
+            return None;
+        }
+        Some((self.ranges[start].0, self.ranges[end - 1].1))
+    }
+}
+
+/// 带偏移映射的输出缓冲
+#[derive(Default)]
+struct OffsetBuilder {
+    text: Vec<u8>,
+    ranges: Vec<(usize, usize)>,
+}
+
+impl OffsetBuilder {
+    /// 追加字节，全部映射到同一原文区间
+    fn push(&mut self, bytes: &[u8], range: (usize, usize)) {
+        self.text.extend_from_slice(bytes);
+        self.ranges.extend(std::iter::repeat_n(range, bytes.len()));
+    }
+
+    fn push_char(&mut self, c: char, range: (usize, usize)) {
+        let mut buf = [0u8; 4];
+        self.push(c.encode_utf8(&mut buf).as_bytes(), range);
+    }
+}
+
+/// 文本归一化器
+pub struct TextNormalizer {
+    /// 口语化邮箱：`local at domain dot tld`
+    spoken_email_re: Regex,
+    /// 口语化邮箱域名中的分隔符
+    spoken_dot_re: Regex,
+}
+
+impl Default for TextNormalizer {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl TextNormalizer {
+    /// 创建归一化器
+    pub fn new() -> Self {
+        Self {
+            spoken_email_re: Regex::new(
+                r"(?i)\b([a-z0-9._%+-]+)(\s+at\s+|\s*[\[({<]\s*at\s*[\])}>]\s*)((?:[a-z0-9-]+(?:\s+dot\s+|\s*[\[({<]\s*dot\s*[\])}>]\s*|\.))+[a-z]{2,10})\b",
+            )
+            .expect("invalid spoken email regex"),
+            spoken_dot_re: Regex::new(r"(?i)\s+dot\s+|\s*[\[({<]\s*dot\s*[\])}>]\s*|\.").expect("invalid spoken dot regex"),
+        }
+    }
+
+    /// 归一化文本
+    ///
+    /// 文本无需改写（或不是合法 UTF-8）时返回 `None`，调用方可跳过二次识别。
+    pub fn normalize(&self, text: &[u8]) -> Option<NormalizedText> {
+        let input = std::str::from_utf8(text).ok()?;
+        let stage1 = Self::normalize_chars(input);
+        let stage1_text = std::str::from_utf8(&stage1.text).ok()?;
+        let out = self.normalize_spoken_email(stage1_text, &stage1);
+        if out.text == text {
+            return None;
+        }
+        Some(NormalizedText::new(text, out))
+    }
+
+    // ─── 字符级：全角 / 中文数字 / 数字分隔符 ───
+
+    /// 只改写影响命中的全角字符：数字与 `＠`
+    fn fullwidth_to_halfwidth(c: char) -> char {
+        match c {
+            '\u{FF10}'..='\u{FF19}' | '\u{FF20}' => char::from_u32(c as u32 - 0xFEE0).unwrap_or(c),
+            _ => c,
+        }
+    }
+
+    fn numeral_value(c: char) -> Option<char> {
+        match c {
+            '0'..='9' => Some(c),
+            '零' | '〇' => Some('0'),
+            '一' | '幺' => Some('1'),
+            '二' => Some('2'),
+            '三' => Some('3'),
+            '四' => Some('4'),
+            '五' => Some('5'),
+            '六' => Some('6'),
+            '七' => Some('7'),
+            '八' => Some('8'),
+            '九' => Some('9'),
+            _ => None,
+        }
+    }
+
+    fn normalize_chars(input: &str) -> OffsetBuilder {
+        let chars: Vec<(char, usize, usize)> = input
+            .char_indices()
+            .map(|(i, c)| (Self::fullwidth_to_halfwidth(c), i, i + c.len_utf8()))
+            .collect();
+
+        let mut out = OffsetBuilder::default();
+        let mut i = 0;
+        while i < chars.len() {
+            if Self::numeral_value(chars[i].0).is_none() {
+                let (c, start, end) = chars[i];
+                out.push_char(c, (start, end));
+                i += 1;
+                continue;
+            }
+
+            // 扫描数字串：数字组之间允许单个、且全串一致的空格或连字符
+            let mut j = i;
+            let mut digits = 0;
+            let mut group = 0;
+            let mut max_group = 0;
+            let mut separator: Option<char> = None;
+            while j < chars.len() {
+                let c = chars[j].0;
+                if Self::numeral_value(c).is_some() {
+                    digits += 1;
+                    group += 1;
+                    max_group = max_group.max(group);
+                    j += 1;
+                    continue;
+                }
+                let next_is_digit = chars.get(j + 1).is_some_and(|n| Self::numeral_value(n.0).is_some());
+                if matches!(c, ' ' | '-') && next_is_digit && separator.is_none_or(|s| s == c) {
+                    separator = Some(c);
+                    group = 0;
+                    j += 1;
+                    continue;
+                }
+                break;
+            }
+
+            let merge = digits >= MIN_RUN_DIGITS && (separator.is_none() || max_group <= MAX_GROUP_DIGITS);
+            for &(c, start, end) in &chars[i..j] {
+                match Self::numeral_value(c) {
+                    Some(d) if merge => out.push_char(d, (start, end)),
+                    _ if merge => {} // 丢弃分隔符
+                    _ => out.push_char(c, (start, end)),
+                }
+            }
+            i = j;
+        }
+        out
+    }
+
+    // ─── 口语化邮箱 ───
+
+    fn normalize_spoken_email(&self, text: &str, stage1: &OffsetBuilder) -> OffsetBuilder {
+        let range_of = |start: usize, end: usize| (stage1.ranges[start].0, stage1.ranges[end - 1].1);
+        let copy = |out: &mut OffsetBuilder, start: usize, end: usize| {
+            out.text.extend_from_slice(&text.as_bytes()[start..end]);
+            out.ranges.extend_from_slice(&stage1.ranges[start..end]);
+        };
+
+        let mut out = OffsetBuilder::default();
+        let mut last = 0;
+        for caps in self.spoken_email_re.captures_iter(text) {
+            let (Some(whole), Some(local), Some(at), Some(domain)) = (caps.get(0), caps.get(1), caps.get(2), caps.get(3))
+            else {
+                continue;
+            };
+            // 裸 " at " 很常见（look at example.com），要求域名里至少有一个口语化的 dot
+            let bracketed_at = at.as_str().contains(['[', '(', '{', '<']);
+            let spoken_dot = domain.as_str().contains(|c: char| c != '.' && !c.is_ascii_alphanumeric() && c != '-');
+            if !bracketed_at && !spoken_dot {
+                continue;
+            }
+
+            copy(&mut out, last, whole.start());
+            copy(&mut out, local.start(), local.end());
+            out.push(b"@", range_of(at.start(), at.end()));
+            let mut seg_start = domain.start();
+            for sep in self.spoken_dot_re.find_iter(domain.as_str()) {
+                let (sep_start, sep_end) = (domain.start() + sep.start(), domain.start() + sep.end());
+                copy(&mut out, seg_start, sep_start);
+                out.push(b".", range_of(sep_start, sep_end));
+                seg_start = sep_end;
+            }
+            copy(&mut out, seg_start, domain.end());
+            last = whole.end();
+        }
+        copy(&mut out, last, text.len());
+        out
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn normalize(text: &str) -> Option<String> {
+        TextNormalizer::new()
+            .normalize(text.as_bytes())
+            .map(|n| String::from_utf8(n.text).unwrap())
+    }
+
+    #[test]
+    fn test_fullwidth_and_chinese_numerals() {
+        assert_eq!(normalize("电话１３８００１３８０００").as_deref(), Some("电话13800138000"));
+        assert_eq!(normalize("电话一三八零零一三八零零零").as_deref(), Some("电话13800138000"));
+        // 短中文数字、全角标点不受影响
+        assert_eq!(normalize("一个人"), None);
+        assert_eq!(normalize("你好，世界：（测试）！"), None);
+        assert_eq!(normalize("邮箱：ｚｓ＠ｘ．ｃｏｍ").as_deref(), Some("邮箱：ｚｓ@ｘ．ｃｏｍ"));
+    }
+
+    #[test]
+    fn test_digit_separators() {
+        assert_eq!(normalize("call 138 0013 8000").as_deref(), Some("call 13800138000"));
+        assert_eq!(normalize("card 6222-0202-0000-1234").as_deref(), Some("card 6222020200001234"));
+        // 日期时间、短数字、混用分隔符保持原样
+        assert_eq!(normalize("2024-01-15 10:30"), None);
+        assert_eq!(normalize("1700000000 1700000001"), None);
+    }
+
+    #[test]
+    fn test_spoken_email() {
+        assert_eq!(normalize("zhangsan at example dot com").as_deref(), Some("zhangsan@example.com"));
+        assert_eq!(normalize("bob [at] mail (dot) co.uk").as_deref(), Some("bob@mail.co.uk"));
+        assert_eq!(normalize("take a look at github.com"), None);
+    }
+
+    #[test]
+    fn test_offset_map_points_to_original() {
+        let text = "邮箱 zhangsan at example dot com 手机 一三八 零零一三 八零零零";
+        let normalized = TextNormalizer::new().normalize(text.as_bytes()).unwrap();
+        let norm_text = std::str::from_utf8(&normalized.text).unwrap();
+
+        let email = norm_text.find("zhangsan@example.com").unwrap();
+        let (s, e) = normalized.map_range(email, email + "zhangsan@example.com".len()).unwrap();
+        assert_eq!(&text[s..e], "zhangsan at example dot com");
+
+        let phone = norm_text.find("13800138000").unwrap();
+        let (s, e) = normalized.map_range(phone, phone + 11).unwrap();
+        assert_eq!(&text[s..e], "一三八 零零一三 八零零零");
+    }
+
+    #[test]
+    fn test_rewritten_windows() {
+        let text = format!("{}call 138 0013 8000 {}", "x".repeat(100), "y".repeat(100));
+        let normalized = TextNormalizer::new().normalize(text.as_bytes()).unwrap();
+        let norm_text = std::str::from_utf8(&normalized.text).unwrap();
+        let phone = norm_text.find("13800138000").unwrap();
+
+        let windows = normalized.rewritten_windows(8);
+        assert_eq!(windows.len(), 1);
+        let (window, core) = &windows[0];
+        // 删除分隔符后的首个数字起算
+        assert_eq!(*core, phone + 3..phone + 8);
+        assert_eq!(&norm_text[window.clone()], "call 13800138000 yyyy");
+    }
+}
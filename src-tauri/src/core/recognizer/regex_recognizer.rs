@@ -7,6 +7,7 @@
 //! - 操作在字节流 (`&[u8]`) 上，跳过 UTF-8 验证，性能更高
 //! - 支持优先级排序，高优先级规则先匹配
 //! - 使用 `SmallVec` 优化栈上小数组，减少堆分配
+//! - 与字典识别器共用 `case_insensitive` / `whole_word` / `unicode_boundary` 规则开关
 
 use super::types::*;
 use super::Recognizer;
@@ -26,6 +27,8 @@ struct CompiledRule {
     priority: i32,
     /// 关联的实体类型
     entity_type: EntityType,
+    /// 词边界要求
+    boundary: WordBoundary,
 }
 
 /// 正则识别器
@@ -70,6 +73,7 @@ impl RegexRecognizer {
 
             match regex::bytes::RegexBuilder::new(&rule.pattern)
                 .unicode(false)
+                .case_insensitive(rule.case_insensitive)
                 .build()
             {
                 Ok(re) => {
@@ -80,12 +84,14 @@ impl RegexRecognizer {
                         mask: rule.mask.clone(),
                         priority: rule.priority,
                         entity_type,
+                        boundary: rule.word_boundary(),
                     });
                 }
                 Err(_) => {
                     // 如果 unicode(false) 编译失败（如正则含 Unicode 字符类），尝试 unicode(true)
                     match regex::bytes::RegexBuilder::new(&rule.pattern)
                         .unicode(true)
+                        .case_insensitive(rule.case_insensitive)
                         .build()
                     {
                         Ok(re) => {
@@ -96,6 +102,7 @@ impl RegexRecognizer {
                                 mask: rule.mask.clone(),
                                 priority: rule.priority,
                                 entity_type,
+                                boundary: rule.word_boundary(),
                             });
                             debug!("  → 回退 unicode(true) 编译成功 '{}'", rule.name);
                         }
@@ -151,16 +158,17 @@ impl Recognizer for RegexRecognizer {
         let mut spans: SmallVec<[EntitySpan; 16]> = SmallVec::new();
 
         for rule in &self.rules {
-            for mat in rule.re.find_iter(input) {
+            for (start, end) in bounded_matches(&rule.re, input, rule.boundary) {
                 spans.push(EntitySpan {
-                    start: mat.start(),
-                    end: mat.end(),
+                    start,
+                    end,
                     entity_type: rule.entity_type.clone(),
                     confidence: 1.0, // 正则匹配是确定性的
                     source: self.name.clone(),
                     context: Some(format!("rule:{}", rule.name)),
                     mask: Some(rule.mask.clone()),
                     priority: 0,
+                    metadata: Default::default(),
                 });
             }
         }
@@ -193,6 +201,35 @@ fn is_literal(pattern: &str) -> bool {
     !pattern.chars().any(|c| meta.contains(&c))
 }
 
+/// 查找满足词边界要求的匹配
+///
+/// 命中不在词边界上时，从下一个字符重新搜索，而不是整段跳过，
+/// 以免 `Linux Li` 中第一处失败的命中挡住后面合法的 `Li`。
+fn bounded_matches(re: &Regex, input: &[u8], boundary: WordBoundary) -> Vec<(usize, usize)> {
+    if boundary == WordBoundary::None {
+        return re.find_iter(input).map(|m| (m.start(), m.end())).collect();
+    }
+
+    let mut matches = Vec::new();
+    let mut pos = 0;
+    while pos <= input.len() {
+        let Some(mat) = re.find_at(input, pos) else {
+            break;
+        };
+        if mat.start() < mat.end() && boundary.is_bounded(input, mat.start(), mat.end()) {
+            matches.push((mat.start(), mat.end()));
+            pos = mat.end();
+        } else {
+            // 跳到下一个 UTF-8 字符起点
+            pos = mat.start() + 1;
+            while pos < input.len() && (input[pos] & 0xC0) == 0x80 {
+                pos += 1;
+            }
+        }
+    }
+    matches
+}
+
 /// 根据规则名称和模式猜测实体类型
 fn guess_entity_type(name: &str, pattern: &str) -> EntityType {
     let name_lower = name.to_lowercase();
@@ -5,10 +5,16 @@
 //! - 按优先级排序执行
 //! - 支持动态注册/注销
 //! - 分离依赖型和非依赖型识别器的执行顺序
+//! - 在归一化文本上二次识别，捕获全角、中文数字、口语化邮箱等变体
 
+use super::normalizer::TextNormalizer;
 use super::types::*;
 use super::Recognizer;
 use log::{debug, info, warn};
+use std::collections::HashSet;
+
+/// 二次识别窗口在改写区间两侧的扩展字节数（覆盖识别器的上下文关键词窗口）
+const NORMALIZED_WINDOW_MARGIN: usize = 64;
 
 /// 识别器注册表
 ///
@@ -24,6 +30,8 @@ pub struct RecognizerRegistry {
     recognizers: Vec<RegisteredRecognizer>,
     /// 注册表配置
     config: RegistryConfig,
+    /// 文本归一化器
+    normalizer: TextNormalizer,
 }
 
 /// 已注册的识别器包装
@@ -45,6 +53,8 @@ pub struct RegistryConfig {
     pub enable_tracing: bool,
     /// 最大并行识别器数（预留，未来使用）
     pub max_parallel: usize,
+    /// 是否在归一化文本上二次识别（全角、中文数字、数字分隔符、口语化邮箱）
+    pub enable_normalization: bool,
 }
 
 impl Default for RegistryConfig {
@@ -53,6 +63,7 @@ impl Default for RegistryConfig {
             global_confidence_threshold: 0.5,
             enable_tracing: true,
             max_parallel: 4,
+            enable_normalization: true,
         }
     }
 }
@@ -63,6 +74,7 @@ impl RecognizerRegistry {
         Self {
             recognizers: Vec::new(),
             config,
+            normalizer: TextNormalizer::new(),
         }
     }
 
@@ -159,16 +171,64 @@ impl RecognizerRegistry {
 
     /// 执行所有识别器，返回合并后的结果
     ///
+    /// 先在原文上完整执行一轮；若启用归一化且文本被改写，
+    /// 再在归一化文本中改写区间附近的窗口上执行一轮非 AI 识别器，
+    /// 与改写区间相交的命中映射回原文偏移后补充进结果
+    /// （与原文命中区间、类型完全相同的跨度视为重复，直接丢弃）。
+    pub fn analyze(&self, context: &AnalysisContext) -> Vec<EntitySpan> {
+        let mut all_spans = self.analyze_pass(context, |_| true);
+
+        if self.config.enable_normalization
+            && let Some(normalized) = self.normalizer.normalize(context.text)
+        {
+            let mut seen: HashSet<(usize, usize, EntityType)> = all_spans
+                .iter()
+                .map(|s| (s.start, s.end, s.entity_type.clone()))
+                .collect();
+            for (window, rewritten) in normalized.rewritten_windows(NORMALIZED_WINDOW_MARGIN) {
+                let mut window_context = AnalysisContext::from_text(&normalized.text[window.clone()]);
+                window_context.language = context.language;
+                window_context.file_type.clone_from(&context.file_type);
+
+                let spans = self.analyze_pass(&window_context, |r| r.recognizer_type() != RecognizerType::Ai);
+                for mut span in spans {
+                    let (start, end) = (window.start + span.start, window.start + span.end);
+                    if end <= rewritten.start || start >= rewritten.end {
+                        continue; // 未触及改写内容，原文一轮已覆盖
+                    }
+                    let Some((start, end)) = normalized.map_range(start, end) else {
+                        continue;
+                    };
+                    if !seen.insert((start, end, span.entity_type.clone())) {
+                        continue;
+                    }
+                    span.start = start;
+                    span.end = end;
+                    all_spans.push(span.with_metadata("normalized", "true"));
+                }
+            }
+        }
+
+        debug!(
+            "📊 识别完成: 共 {} 个实体跨度",
+            all_spans.len()
+        );
+
+        all_spans
+    }
+
+    /// 在给定上下文上执行一轮识别（只运行 `include` 接受的识别器）
+    ///
     /// 执行顺序：
     /// 1. 先执行所有非依赖型识别器（`requires_context() == false`）
     /// 2. 将非依赖型结果注入 `AnalysisContext`
     /// 3. 再执行所有依赖型识别器（`requires_context() == true`）
-    pub fn analyze(&self, context: &AnalysisContext) -> Vec<EntitySpan> {
+    fn analyze_pass(&self, context: &AnalysisContext, include: impl Fn(&dyn Recognizer) -> bool) -> Vec<EntitySpan> {
         let mut all_spans = Vec::new();
 
         // Phase 1: 非依赖型识别器
         for registered in &self.recognizers {
-            if !registered.enabled {
+            if !registered.enabled || !include(registered.recognizer.as_ref()) {
                 continue;
             }
             if registered.recognizer.requires_context() {
@@ -191,7 +251,7 @@ impl RecognizerRegistry {
         }
 
         for registered in &self.recognizers {
-            if !registered.enabled {
+            if !registered.enabled || !include(registered.recognizer.as_ref()) {
                 continue;
             }
             if !registered.recognizer.requires_context() {
@@ -204,12 +264,6 @@ impl RecognizerRegistry {
 
         // 过滤低置信度结果
         all_spans.retain(|s| s.confidence >= self.config.global_confidence_threshold);
-
-        debug!(
-            "📊 识别完成: 共 {} 个实体跨度",
-            all_spans.len()
-        );
-
         all_spans
     }
 
@@ -444,4 +498,58 @@ mod tests {
         // 低置信度结果应被过滤
         assert_eq!(spans.len(), 0);
     }
+
+    #[test]
+    fn test_registry_normalized_pass() {
+        let mut registry = RecognizerRegistry::default_config();
+        registry.register(Box::new(TestRecognizer::new(
+            "test_phone",
+            90,
+            EntityType::Phone,
+            "13800138000",
+        )));
+
+        let text = "phone: 一三八 零零一三 八零零零";
+        let spans = registry.analyze(&AnalysisContext::from_text(text.as_bytes()));
+        assert_eq!(spans.len(), 1);
+        assert_eq!(&text[spans[0].start..spans[0].end], "一三八 零零一三 八零零零");
+        assert_eq!(spans[0].meta("normalized"), Some("true"));
+
+        // 关闭归一化后不再命中
+        registry.config_mut().enable_normalization = false;
+        assert!(registry.analyze(&AnalysisContext::from_text(text.as_bytes())).is_empty());
+    }
+
+    #[test]
+    fn test_normalized_pass_skips_ai_and_unchanged_text() {
+        use std::sync::atomic::{AtomicUsize, Ordering};
+        use std::sync::Arc;
+
+        struct CountingAi(Arc<AtomicUsize>);
+        impl Recognizer for CountingAi {
+            fn name(&self) -> &str { "counting_ai" }
+            fn recognizer_type(&self) -> RecognizerType { RecognizerType::Ai }
+            fn supported_entities(&self) -> Vec<EntityType> { vec![EntityType::Person] }
+            fn analyze(&self, _context: &AnalysisContext) -> AnalysisResult {
+                self.0.fetch_add(1, Ordering::Relaxed);
+                AnalysisResult { spans: vec![], elapsed_us: 0, recognizer: "counting_ai".to_string() }
+            }
+        }
+
+        let calls = Arc::new(AtomicUsize::new(0));
+        let mut registry = RecognizerRegistry::default_config();
+        registry.register(Box::new(CountingAi(calls.clone())));
+        registry.register(Box::new(TestRecognizer::new("test_phone", 90, EntityType::Phone, "13800138000")));
+
+        // 全角标点不触发二次识别
+        registry.analyze(&AnalysisContext::from_text("你好，世界（测试）".as_bytes()));
+        assert_eq!(calls.load(Ordering::Relaxed), 1);
+
+        // 改写文本只在窗口内运行规则识别器，偏移仍映射回原文
+        let text = format!("{}电话：１３８ ００１３ ８０００", "注".repeat(60));
+        let spans = registry.analyze(&AnalysisContext::from_text(text.as_bytes()));
+        assert_eq!(calls.load(Ordering::Relaxed), 2);
+        assert_eq!(spans.len(), 1);
+        assert_eq!(&text[spans[0].start..spans[0].end], "１３８ ００１３ ８０００");
+    }
 }
@@ -25,6 +25,18 @@ pub enum EntityType {
     IdCard,
     /// 出生日期
     DateOfBirth,
+    /// 统一社会信用代码
+    CreditCode,
+    /// 护照号
+    Passport,
+    /// 港澳台通行证 / 回乡证 / 台胞证
+    TravelPermit,
+    /// 机动车号牌
+    LicensePlate,
+    /// 军官证 / 士兵证 / 警官证
+    MilitaryId,
+    /// 税号（ITIN / Steuer-ID / 增值税号等）
+    TaxId,
 
     // ── 联系方式 ──
     /// 电子邮箱
@@ -33,20 +45,34 @@ pub enum EntityType {
     Phone,
     /// 物理地址
     Address,
+    /// 邮政编码
+    PostalCode,
     /// URL 链接
     Url,
 
     // ── 金融类 ──
     /// 银行卡号
     BankCard,
+    /// 国际银行账号 (IBAN)
+    Iban,
+    /// SWIFT/BIC 银行识别码
+    SwiftCode,
 
     // ── 凭证类 ──
     /// API Key / Token / Secret
     ApiKey,
     /// 密码
     Password,
+    /// 用户名 / 账号
+    Username,
+    /// 主机名
+    Hostname,
     /// IP 地址
     IpAddress,
+    /// MAC 地址
+    MacAddress,
+    /// 加密货币钱包地址
+    CryptoAddress,
 
     // ── 扩展 ──
     /// 用户自定义实体类型
@@ -61,13 +87,26 @@ impl EntityType {
             "email" | "email_address" => Self::Email,
             "phone" | "phone_number" | "telephone" => Self::Phone,
             "address" | "location" | "street_address" => Self::Address,
+            "postal_code" | "postcode" | "zip" | "zip_code" => Self::PostalCode,
             "id_card" | "idcard" | "national_id" | "ssn" => Self::IdCard,
             "bank_card" | "credit_card" | "debit_card" => Self::BankCard,
+            "iban" => Self::Iban,
+            "swift" | "bic" | "swift_code" | "swift_bic" => Self::SwiftCode,
             "dob" | "date_of_birth" | "birthday" => Self::DateOfBirth,
+            "credit_code" | "uscc" | "social_credit_code" => Self::CreditCode,
+            "passport" | "passport_number" => Self::Passport,
+            "travel_permit" | "permit" => Self::TravelPermit,
+            "license_plate" | "plate" | "car_plate" => Self::LicensePlate,
+            "military_id" | "officer_id" => Self::MilitaryId,
+            "tax_id" | "taxid" | "vat" | "vat_number" | "itin" => Self::TaxId,
             "url" | "website" | "link" => Self::Url,
             "api_key" | "apikey" | "token" | "secret" | "api_token" => Self::ApiKey,
             "password" | "passwd" | "pwd" => Self::Password,
+            "username" | "user" | "user_name" | "login" => Self::Username,
+            "hostname" | "host" => Self::Hostname,
             "ip" | "ip_address" | "ipv4" | "ipv6" => Self::IpAddress,
+            "mac" | "mac_address" => Self::MacAddress,
+            "crypto_address" | "wallet" | "wallet_address" | "crypto_wallet" => Self::CryptoAddress,
             other => Self::Custom(other.to_string()),
         }
     }
@@ -79,13 +118,26 @@ impl EntityType {
             Self::Email => "EMAIL",
             Self::Phone => "PHONE",
             Self::Address => "ADDRESS",
+            Self::PostalCode => "POSTAL_CODE",
             Self::IdCard => "ID_CARD",
             Self::BankCard => "BANK_CARD",
+            Self::Iban => "IBAN",
+            Self::SwiftCode => "SWIFT",
             Self::DateOfBirth => "DATE",
+            Self::CreditCode => "CREDIT_CODE",
+            Self::Passport => "PASSPORT",
+            Self::TravelPermit => "TRAVEL_PERMIT",
+            Self::LicensePlate => "LICENSE_PLATE",
+            Self::MilitaryId => "MILITARY_ID",
+            Self::TaxId => "TAX_ID",
             Self::Url => "URL",
             Self::ApiKey => "API_KEY",
             Self::Password => "PASSWORD",
+            Self::Username => "USERNAME",
+            Self::Hostname => "HOST",
             Self::IpAddress => "IP",
+            Self::MacAddress => "MAC",
+            Self::CryptoAddress => "CRYPTO_ADDRESS",
             Self::Custom(s) => s.as_str(),
         }
     }
@@ -97,13 +149,26 @@ impl EntityType {
             Self::Email => "email",
             Self::Phone => "phone",
             Self::Address => "address",
+            Self::PostalCode => "postal_code",
             Self::IdCard => "id_card",
             Self::BankCard => "bank_card",
+            Self::Iban => "iban",
+            Self::SwiftCode => "swift_code",
             Self::DateOfBirth => "date_of_birth",
+            Self::CreditCode => "credit_code",
+            Self::Passport => "passport",
+            Self::TravelPermit => "travel_permit",
+            Self::LicensePlate => "license_plate",
+            Self::MilitaryId => "military_id",
+            Self::TaxId => "tax_id",
             Self::Url => "url",
             Self::ApiKey => "api_key",
             Self::Password => "password",
+            Self::Username => "username",
+            Self::Hostname => "hostname",
             Self::IpAddress => "ip_address",
+            Self::MacAddress => "mac_address",
+            Self::CryptoAddress => "crypto_address",
             Self::Custom(s) => s.as_str(),
         }
     }
@@ -166,6 +231,11 @@ pub struct EntitySpan {
     /// 冲突解决时，高 priority 的区间会在低 priority 区间中"雕刻"出位置，
     /// 而不是简单丢弃低 priority 的一方。默认 0。
     pub priority: i32,
+    /// 识别器附加的结构化元数据（如 `card_brand`、`iban_country`）
+    ///
+    /// 供脱敏策略与策略路由使用，例如按卡组织输出 `<CARD:UNIONPAY>`。
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub metadata: HashMap<String, String>,
 }
 
 impl EntitySpan {
@@ -186,6 +256,7 @@ impl EntitySpan {
             context: None,
             mask: None,
             priority: 0,
+            metadata: HashMap::new(),
         }
     }
 
@@ -207,6 +278,7 @@ impl EntitySpan {
             context: None,
             mask: Some(mask.into()),
             priority: 0,
+            metadata: HashMap::new(),
         }
     }
 
@@ -216,6 +288,17 @@ impl EntitySpan {
         self
     }
 
+    /// 附加一条元数据（builder 模式）
+    pub fn with_metadata(mut self, key: impl Into<String>, value: impl Into<String>) -> Self {
+        self.metadata.insert(key.into(), value.into());
+        self
+    }
+
+    /// 读取元数据
+    pub fn meta(&self, key: &str) -> Option<&str> {
+        self.metadata.get(key).map(String::as_str)
+    }
+
     /// 获取实体在原文中的文本内容
     pub fn text<'a>(&self, source: &'a [u8]) -> &'a [u8] {
         &source[self.start..self.end]
@@ -241,17 +324,132 @@ impl EntitySpan {
     }
 }
 
+// ─────────────────────────────────────────────────────────────────────────────
+// 词边界 (WordBoundary)
+// ─────────────────────────────────────────────────────────────────────────────
+
+/// 词边界模式
+///
+/// 用于字面量与正则规则的"整词匹配"，避免 `Li` 命中 `Linux` 中间。
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum WordBoundary {
+    /// 不检查边界
+    #[default]
+    None,
+    /// ASCII 词边界：仅 `[A-Za-z0-9_]` 视为词字符
+    Ascii,
+    /// Unicode 词边界：Unicode 字母数字均为词字符；
+    /// CJK 文本没有分词空格，相邻 CJK 字符及 CJK/非 CJK 交界都视为边界
+    Unicode,
+}
+
+impl WordBoundary {
+    /// 由规则开关得到边界模式（`unicode_boundary` 隐含整词匹配）
+    pub fn from_flags(whole_word: bool, unicode_boundary: bool) -> Self {
+        if unicode_boundary {
+            Self::Unicode
+        } else if whole_word {
+            Self::Ascii
+        } else {
+            Self::None
+        }
+    }
+
+    /// `text[start..end]` 的两端是否都处于词边界
+    pub fn is_bounded(self, text: &[u8], start: usize, end: usize) -> bool {
+        match self {
+            Self::None => true,
+            Self::Ascii => {
+                let is_word = |b: u8| b.is_ascii_alphanumeric() || b == b'_';
+                let left = start == 0 || start >= text.len() || !(is_word(text[start - 1]) && is_word(text[start]));
+                let right = end == 0 || end >= text.len() || !(is_word(text[end - 1]) && is_word(text[end]));
+                left && right
+            }
+            Self::Unicode => {
+                let joined = |a: Option<char>, b: Option<char>| match (a, b) {
+                    (Some(a), Some(b)) => is_unicode_word(a) && is_unicode_word(b) && !is_cjk(a) && !is_cjk(b),
+                    _ => false,
+                };
+                !joined(char_before(text, start), char_at(text, start)) && !joined(char_before(text, end), char_at(text, end))
+            }
+        }
+    }
+}
+
+fn is_unicode_word(c: char) -> bool {
+    c.is_alphanumeric() || c == '_'
+}
+
+/// CJK 统一表意文字（含扩展 A）
+pub fn is_han(c: char) -> bool {
+    matches!(c, '\u{4E00}'..='\u{9FFF}' | '\u{3400}'..='\u{4DBF}')
+}
+
+/// CJK 表意文字、假名、谚文
+pub fn is_cjk(c: char) -> bool {
+    is_han(c) || matches!(c, '\u{3040}'..='\u{30FF}' | '\u{AC00}'..='\u{D7AF}')
+}
+
+/// 解码 `pos` 处开始的字符（非法 UTF-8 返回 `None`）
+fn char_at(text: &[u8], pos: usize) -> Option<char> {
+    let tail = text.get(pos..)?;
+    let len = tail.len().min(4);
+    (1..=len).find_map(|n| std::str::from_utf8(&tail[..n]).ok()).and_then(|s| s.chars().next())
+}
+
+/// 解码 `pos` 之前的最后一个字符（非法 UTF-8 返回 `None`）
+fn char_before(text: &[u8], pos: usize) -> Option<char> {
+    let head = text.get(..pos)?;
+    (1..=head.len().min(4))
+        .find_map(|n| std::str::from_utf8(&head[head.len() - n..]).ok())
+        .and_then(|s| s.chars().next_back())
+}
+
+/// 转小写，返回折叠文本与"折叠字节 → 原文字节"映射（末尾附原文长度）
+pub fn fold_case(text: &str) -> (Vec<u8>, Vec<usize>) {
+    let mut folded = Vec::with_capacity(text.len());
+    let mut offsets = Vec::with_capacity(text.len() + 1);
+    let mut buf = [0u8; 4];
+    for (i, c) in text.char_indices() {
+        for lower in c.to_lowercase() {
+            let bytes = lower.encode_utf8(&mut buf).as_bytes();
+            folded.extend_from_slice(bytes);
+            offsets.extend(std::iter::repeat_n(i, bytes.len()));
+        }
+    }
+    offsets.push(text.len());
+    (folded, offsets)
+}
+
 // ─────────────────────────────────────────────────────────────────────────────
 // 文本编码 (TextEncoding)
 // ─────────────────────────────────────────────────────────────────────────────
 
 /// 文本编码类型
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+///
+/// 识别器始终分析 UTF-8 文本；其他编码的文件由文件处理层解码后再送入引擎。
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
 pub enum TextEncoding {
     Utf8,
     Gbk,
+    /// ISO-8859-1（按 Windows-1252 超集解码）
     Latin1,
-    // 未来扩展：更多编码
+    Utf16Le,
+    Utf16Be,
+}
+
+impl TextEncoding {
+    /// 编码名称（用于日志与报告）
+    pub fn label(&self) -> &'static str {
+        match self {
+            Self::Utf8 => "UTF-8",
+            Self::Gbk => "GBK",
+            Self::Latin1 => "ISO-8859-1",
+            Self::Utf16Le => "UTF-16LE",
+            Self::Utf16Be => "UTF-16BE",
+        }
+    }
 }
 
 impl Default for TextEncoding {
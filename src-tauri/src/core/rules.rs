@@ -1,3 +1,4 @@
+use crate::core::recognizer::WordBoundary;
 use serde::{Deserialize, Serialize};
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -22,6 +23,29 @@ pub struct Rule {
     /// 是否为用户自定义规则 (影响 UI 显示和存储位置)
     #[serde(default)]
     pub is_custom: bool,
+
+    /// 忽略大小写匹配
+    #[serde(default)]
+    pub case_insensitive: bool,
+
+    /// 整词匹配（ASCII 词边界，避免 `Li` 命中 `Linux`）
+    #[serde(default)]
+    pub whole_word: bool,
+
+    /// 按 Unicode 字母数字判断词边界，CJK 字符自身即为边界（隐含 `whole_word`）
+    #[serde(default)]
+    pub unicode_boundary: bool,
+
+    /// 所属规则组（加载时由 YAML 的 `group` 字段填充，写回文件前清空）
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub group: String,
+}
+
+impl Rule {
+    /// 规则的词边界模式
+    pub fn word_boundary(&self) -> WordBoundary {
+        WordBoundary::from_flags(self.whole_word, self.unicode_boundary)
+    }
 }
 
 fn default_true() -> bool {
@@ -33,4 +57,14 @@ fn default_true() -> bool {
 pub struct RuleGroup {
     pub group: String,
     pub rules: Vec<Rule>,
+}
+
+impl RuleGroup {
+    /// 构造写盘用的规则组：组名只记在外层，清空各规则上的 `group`
+    pub fn new(group: impl Into<String>, mut rules: Vec<Rule>) -> Self {
+        for r in &mut rules {
+            r.group.clear();
+        }
+        Self { group: group.into(), rules }
+    }
 }
\ No newline at end of file
@@ -69,10 +69,10 @@ impl GlobalClipboard {
         info!("🔔 [Handler] 开始处理新内容 ({} bytes)", text.len());
 
 
-        // 5. 执行脱敏计算（同时获取实体跨度摘要，用于前端高亮）
+        // 5. 执行脱敏计算（同时获取实体跨度摘要，用于前端高亮；.env 内容按键值规则处理）
         let (masked_result, entities) = {
             let engine = state.engine.read();
-            engine.mask_line_with_entities(text.as_bytes())
+            crate::infra::fs::key_value::mask_clipboard_text(&engine, &settings.key_value_options, text.as_bytes())
         };
         let masked_text = String::from_utf8_lossy(&masked_result).to_string();
         let has_privacy = text != masked_text;
@@ -1,5 +1,8 @@
 use crate::common::errors::{AppError, AppResult};
 use crate::core::rules::{Rule, RuleGroup};
+use crate::core::allowlist::{AllowlistEntry, AllowlistFile};
+use crate::core::recognizer::EdmIndex;
+use crate::core::recognizer::dictionary_recognizer::{self, DictionaryBuildReport, DictionaryFile, DictionarySpec, MappedDictionary};
 use crate::core::config::AppSettings;
 use std::fs;
 use std::path::{Path, PathBuf}; // 🚀 修复：导入 Path
@@ -100,10 +103,8 @@ impl ConfigLoader {
             rules.push(new_rule);
         }
 
-        let yaml = serde_yaml::to_string(&RuleGroup {
-            group: "CUSTOM".into(),
-            rules,
-        }).map_err(|e| AppError::Config(format!("YAML 序列化失败: {}", e)))?;
+        let yaml = serde_yaml::to_string(&RuleGroup::new("CUSTOM", rules))
+            .map_err(|e| AppError::Config(format!("YAML 序列化失败: {}", e)))?;
 
         fs::write(file_path, yaml)?;
         Ok(())
@@ -163,11 +164,8 @@ impl ConfigLoader {
             r.is_custom = true;
         }
 
-        let yaml = serde_yaml::to_string(&RuleGroup {
-            group: "CUSTOM".into(),
-            rules,
-        })
-        .map_err(|e| AppError::Config(format!("YAML 序列化失败: {}", e)))?;
+        let yaml = serde_yaml::to_string(&RuleGroup::new("CUSTOM", rules))
+            .map_err(|e| AppError::Config(format!("YAML 序列化失败: {}", e)))?;
 
         let file_path = custom_dir.join("user_rules.yaml");
         let tmp_path = custom_dir.join("user_rules.yaml.tmp");
@@ -179,6 +177,151 @@ impl ConfigLoader {
         Ok(())
     }
 
+    /// 读取白名单条目（custom/allowlist.yaml），文件不存在或解析失败时返回空列表。
+    pub fn load_allowlist(app_handle: &AppHandle) -> Vec<AllowlistEntry> {
+        Self::try_load_allowlist(app_handle).unwrap_or_else(|e| {
+            error!("{}", e);
+            vec![]
+        })
+    }
+
+    /// 读取白名单条目，文件不存在时返回空列表；读取或解析失败时报错（供写入前使用，避免覆盖无法解析的文件）
+    fn try_load_allowlist(app_handle: &AppHandle) -> AppResult<Vec<AllowlistEntry>> {
+        let file_path = Self::get_custom_storage_path(app_handle).join("allowlist.yaml");
+        if !file_path.exists() {
+            return Ok(vec![]);
+        }
+        let content = fs::read_to_string(&file_path)
+            .map_err(|e| AppError::Config(format!("读取 allowlist.yaml 失败: {}", e)))?;
+        serde_yaml::from_str::<AllowlistFile>(&content)
+            .map(|f| f.entries)
+            .map_err(|e| AppError::Config(format!("解析 allowlist.yaml 失败，请先修正或移除该文件: {}", e)))
+    }
+
+    /// 保存白名单条目（按 ID 更新，ID 为空时生成新 ID），返回条目 ID。
+    pub fn save_allowlist_entry(app_handle: &AppHandle, mut entry: AllowlistEntry) -> AppResult<String> {
+        entry.validate()?;
+        let mut entries = Self::try_load_allowlist(app_handle)?;
+        if entry.id.is_empty() {
+            entry.id = uuid::Uuid::new_v4().to_string();
+        }
+        let id = entry.id.clone();
+        if let Some(pos) = entries.iter().position(|e| e.id == entry.id) {
+            entries[pos] = entry;
+        } else {
+            entries.push(entry);
+        }
+        Self::write_allowlist(app_handle, entries)?;
+        Ok(id)
+    }
+
+    /// 从 custom/allowlist.yaml 中删除白名单条目
+    pub fn delete_allowlist_entry(app_handle: &AppHandle, id: &str) -> AppResult<()> {
+        let mut entries = Self::try_load_allowlist(app_handle)?;
+        entries.retain(|e| e.id != id);
+        Self::write_allowlist(app_handle, entries)
+    }
+
+    /// 原子替换写入完整白名单
+    fn write_allowlist(app_handle: &AppHandle, entries: Vec<AllowlistEntry>) -> AppResult<()> {
+        let custom_dir = Self::get_custom_storage_path(app_handle);
+        if !custom_dir.exists() {
+            fs::create_dir_all(&custom_dir)?;
+        }
+
+        let yaml = serde_yaml::to_string(&AllowlistFile { entries })
+            .map_err(|e| AppError::Config(format!("YAML 序列化失败: {}", e)))?;
+
+        let file_path = custom_dir.join("allowlist.yaml");
+        let tmp_path = custom_dir.join("allowlist.yaml.tmp");
+        fs::write(&tmp_path, yaml.as_bytes())?;
+        if file_path.exists() {
+            let _ = fs::remove_file(&file_path);
+        }
+        fs::rename(&tmp_path, &file_path)?;
+        Ok(())
+    }
+
+    /// EDM 精确匹配索引文件路径（custom/edm_index.bin）
+    pub fn edm_index_path(app_handle: &AppHandle) -> PathBuf {
+        Self::get_custom_storage_path(app_handle).join("edm_index.bin")
+    }
+
+    /// 读取 EDM 索引，文件不存在或损坏时返回 None
+    pub fn load_edm_index(app_handle: &AppHandle) -> Option<EdmIndex> {
+        let path = Self::edm_index_path(app_handle);
+        if !path.exists() {
+            return None;
+        }
+        EdmIndex::load(&path)
+            .map_err(|e| error!("读取 EDM 索引失败: {}", e))
+            .ok()
+    }
+
+    /// 读取外部字典登记（custom/dictionaries.yaml），文件不存在或解析失败时返回空列表
+    pub fn load_dictionary_specs(app_handle: &AppHandle) -> Vec<DictionarySpec> {
+        Self::try_load_dictionary_specs(app_handle).unwrap_or_else(|e| {
+            error!("{}", e);
+            vec![]
+        })
+    }
+
+    /// 读取外部字典登记，文件不存在时返回空列表；读取或解析失败时报错（供写入前使用，避免覆盖无法解析的文件）
+    fn try_load_dictionary_specs(app_handle: &AppHandle) -> AppResult<Vec<DictionarySpec>> {
+        let file_path = Self::get_custom_storage_path(app_handle).join("dictionaries.yaml");
+        if !file_path.exists() {
+            return Ok(vec![]);
+        }
+        let content = fs::read_to_string(&file_path)
+            .map_err(|e| AppError::Config(format!("读取 dictionaries.yaml 失败: {}", e)))?;
+        serde_yaml::from_str::<DictionaryFile>(&content)
+            .map(|f| f.dictionaries)
+            .map_err(|e| AppError::Config(format!("解析 dictionaries.yaml 失败，请先修正或移除该文件: {}", e)))
+    }
+
+    /// 保存外部字典登记（按名称更新或追加）
+    pub fn save_dictionary_spec(app_handle: &AppHandle, spec: DictionarySpec) -> AppResult<()> {
+        let mut dictionaries = Self::try_load_dictionary_specs(app_handle)?;
+        if let Some(pos) = dictionaries.iter().position(|d| d.name == spec.name) {
+            dictionaries[pos] = spec;
+        } else {
+            dictionaries.push(spec);
+        }
+        Self::write_dictionary_specs(app_handle, dictionaries)
+    }
+
+    /// 删除外部字典登记（词表源文件保留）
+    pub fn delete_dictionary_spec(app_handle: &AppHandle, name: &str) -> AppResult<()> {
+        let mut dictionaries = Self::try_load_dictionary_specs(app_handle)?;
+        dictionaries.retain(|d| d.name != name);
+        Self::write_dictionary_specs(app_handle, dictionaries)
+    }
+
+    fn write_dictionary_specs(app_handle: &AppHandle, dictionaries: Vec<DictionarySpec>) -> AppResult<()> {
+        let custom_dir = Self::get_custom_storage_path(app_handle);
+        if !custom_dir.exists() {
+            fs::create_dir_all(&custom_dir)?;
+        }
+        let yaml = serde_yaml::to_string(&DictionaryFile { dictionaries })
+            .map_err(|e| AppError::Config(format!("YAML 序列化失败: {}", e)))?;
+
+        let file_path = custom_dir.join("dictionaries.yaml");
+        let tmp_path = custom_dir.join("dictionaries.yaml.tmp");
+        fs::write(&tmp_path, yaml.as_bytes())?;
+        if file_path.exists() {
+            let _ = fs::remove_file(&file_path);
+        }
+        fs::rename(&tmp_path, &file_path)?;
+        Ok(())
+    }
+
+    /// 编译（仅源词表变化的字典）并内存映射全部外部字典
+    pub fn prepare_dictionaries(app_handle: &AppHandle, force: bool) -> (Vec<MappedDictionary>, Vec<DictionaryBuildReport>) {
+        let custom_dir = Self::get_custom_storage_path(app_handle);
+        let specs = Self::load_dictionary_specs(app_handle);
+        dictionary_recognizer::prepare_dictionaries(&specs, &custom_dir, &custom_dir.join("dictionaries"), force)
+    }
+
     /// 内置规则名称集合（用于导入冲突检测）。
     pub fn builtin_rule_names(app_handle: &AppHandle) -> std::collections::HashSet<String> {
         let mut names = std::collections::HashSet::new();
@@ -237,13 +380,23 @@ impl ConfigLoader {
 
         // 尝试解析为 RuleGroup { group: String, rules: Vec<Rule> }
         if let Ok(group) = serde_yaml::from_str::<RuleGroup>(&content) {
-            return Ok(group.rules);
+            let mut rules = group.rules;
+            for rule in &mut rules {
+                rule.group = group.group.clone();
+            }
+            return Ok(rules);
         }
 
-        // 尝试解析为纯数组 [Rule, Rule, ...]
-        let rules: Vec<Rule> = serde_yaml::from_str(&content)
+        // 尝试解析为纯数组 [Rule, Rule, ...]，组名取文件名
+        let mut rules: Vec<Rule> = serde_yaml::from_str(&content)
             .with_context(|| format!("YAML 格式解析失败: {:?}", path))?;
-            
+        let stem = path.file_stem().and_then(|s| s.to_str()).unwrap_or_default();
+        for rule in &mut rules {
+            if rule.group.is_empty() {
+                rule.group = stem.to_string();
+            }
+        }
+
         Ok(rules)
     }
 
@@ -341,6 +341,10 @@ rules:
             priority: 1,
             enabled: true,
             is_custom: false,
+            case_insensitive: false,
+            whole_word: false,
+            unicode_boundary: false,
+            group: String::new(),
         };
         assert!(validate_and_normalize_rule(r, "angle", "t").is_err());
     }
@@ -354,6 +358,10 @@ rules:
             priority: 1,
             enabled: true,
             is_custom: true,
+            case_insensitive: false,
+            whole_word: false,
+            unicode_boundary: false,
+            group: String::new(),
         }];
         let mut builtin = HashSet::new();
         builtin.insert("Builtin".into());
@@ -0,0 +1,657 @@
+//! 目录批量脱敏
+//!
+//! 递归遍历输入目录，按 include / exclude glob、文件大小、隐藏文件、二进制文件过滤，
+//! 将目录结构镜像到输出根目录，并行调用 `processor::process_file` 逐个处理。
+//!
+//! - glob 匹配相对于输入根目录的路径（统一使用 `/` 分隔），`*` 可跨目录，
+//!   因此 `*.log` 匹配任意层级的日志文件，`node_modules/**` 排除整棵子树
+//! - 被 exclude 命中的目录整体剪枝，不再深入遍历
+//! - 二进制判定：前 8KB 含 NUL 字节；Office / PDF 等处理器支持的文档格式不受此限制
+//! - 所有文件共享同一个引擎快照，批处理期间规则热更新不影响本次任务
+//! - [`scan_directory`] 使用同一套过滤规则只做检测，不写输出文件
+
+use crate::core::file_options::ProcessOptions;
+use crate::core::hybrid_engine::HybridEngine;
+use crate::core::recognizer::TextEncoding;
+use crate::infra::fs::encoding::detect_encoding;
+use crate::infra::fs::processor;
+use crate::infra::fs::scan_report::ScanReport;
+use anyhow::{Context, Result};
+use globset::{Glob, GlobSet, GlobSetBuilder};
+use rayon::prelude::*;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fs::File;
+use std::io::Read;
+use std::path::{Component, Path, PathBuf};
+use std::sync::Arc;
+use std::sync::atomic::{AtomicU64, AtomicUsize, Ordering};
+use std::time::Instant;
+use walkdir::WalkDir;
+
+/// 二进制嗅探读取的字节数
+const SNIFF_LEN: usize = 8 * 1024;
+
+/// 处理器按格式解析、不受二进制过滤影响的扩展名
+const DOCUMENT_EXTENSIONS: &[&str] = &["docx", "xlsx", "xls", "xlsm", "xlsb", "pdf", "doc"];
+
+/// 批处理选项
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BatchOptions {
+    /// 包含的 glob（为空表示全部文件）
+    #[serde(default)]
+    pub include: Vec<String>,
+
+    /// 排除的 glob（对文件和目录都生效）
+    #[serde(default)]
+    pub exclude: Vec<String>,
+
+    /// 单个文件大小上限（字节），为空表示不限
+    #[serde(default)]
+    pub max_file_size: Option<u64>,
+
+    /// 跳过隐藏文件和目录（以 `.` 开头）
+    #[serde(default = "default_true")]
+    pub skip_hidden: bool,
+
+    /// 跳过二进制文件
+    #[serde(default = "default_true")]
+    pub skip_binary: bool,
+}
+
+fn default_true() -> bool {
+    true
+}
+
+impl Default for BatchOptions {
+    fn default() -> Self {
+        Self {
+            include: Vec::new(),
+            exclude: Vec::new(),
+            max_file_size: None,
+            skip_hidden: true,
+            skip_binary: true,
+        }
+    }
+}
+
+/// 单个文件的处理状态
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum BatchFileStatus {
+    Processed,
+    Skipped,
+    Failed,
+}
+
+/// 单个文件的处理结果
+#[derive(Debug, Clone, Serialize)]
+pub struct BatchFileReport {
+    /// 相对输入根目录的路径
+    pub relative_path: String,
+    /// 输出文件路径（跳过时为空）
+    pub output_path: Option<String>,
+    pub status: BatchFileStatus,
+    /// 跳过或失败原因
+    pub reason: Option<String>,
+    /// 文件字节数
+    pub bytes: u64,
+    /// 检测到的实体数
+    pub entity_count: usize,
+    pub duration_secs: f64,
+}
+
+/// 批处理进度（每完成一个文件回调一次）
+#[derive(Debug, Clone, Serialize)]
+pub struct BatchProgress {
+    /// 刚完成的文件
+    pub file: BatchFileReport,
+    /// 已完成文件数（含跳过、失败）
+    pub files_done: usize,
+    pub files_total: usize,
+    /// 已处理字节数 / 待处理总字节数
+    pub bytes_done: u64,
+    pub bytes_total: u64,
+}
+
+/// 批处理汇总
+#[derive(Debug, Clone, Serialize)]
+pub struct BatchSummary {
+    pub output_root: String,
+    pub files_total: usize,
+    pub processed: usize,
+    pub skipped: usize,
+    pub failed: usize,
+    pub bytes_processed: u64,
+    /// 按实体类型统计的命中数
+    pub entity_counts: BTreeMap<String, usize>,
+    pub duration_secs: f64,
+    /// 逐文件结果
+    pub files: Vec<BatchFileReport>,
+}
+
+/// 待处理文件
+struct PlannedFile {
+    input: PathBuf,
+    relative: String,
+    bytes: u64,
+}
+
+/// 批处理计划：待处理文件与已判定跳过的文件
+struct BatchPlan {
+    files: Vec<PlannedFile>,
+    skipped: Vec<BatchFileReport>,
+}
+
+/// 编译 glob 列表（为空返回 `None`）
+fn build_globset(patterns: &[String]) -> Result<Option<GlobSet>> {
+    if patterns.is_empty() {
+        return Ok(None);
+    }
+    let mut builder = GlobSetBuilder::new();
+    for pattern in patterns {
+        builder.add(Glob::new(pattern).with_context(|| format!("无效的 glob: {}", pattern))?);
+    }
+    Ok(Some(builder.build()?))
+}
+
+fn is_hidden(path: &Path) -> bool {
+    path.file_name()
+        .and_then(|n| n.to_str())
+        .is_some_and(|n| n.starts_with('.'))
+}
+
+fn is_document(path: &Path) -> bool {
+    path.extension()
+        .and_then(|e| e.to_str())
+        .is_some_and(|e| DOCUMENT_EXTENSIONS.iter().any(|d| e.eq_ignore_ascii_case(d)))
+}
+
+/// 前 8KB 含 NUL 字节视为二进制（UTF-16 文本的 NUL 属于正常编码，不算）
+fn is_binary(path: &Path) -> bool {
+    let Ok(mut file) = File::open(path) else {
+        return false;
+    };
+    let mut buf = [0u8; SNIFF_LEN];
+    let n = file.read(&mut buf).unwrap_or(0);
+    let (encoding, _) = detect_encoding(&buf[..n]);
+    !matches!(encoding, TextEncoding::Utf16Le | TextEncoding::Utf16Be) && memchr::memchr(0, &buf[..n]).is_some()
+}
+
+/// 相对路径（统一 `/` 分隔，便于 glob 跨平台匹配）
+fn relative_path(root: &Path, path: &Path) -> String {
+    let rel = path.strip_prefix(root).unwrap_or(path);
+    rel.components()
+        .map(|c| c.as_os_str().to_string_lossy())
+        .collect::<Vec<_>>()
+        .join("/")
+}
+
+fn skipped(relative: String, bytes: u64, reason: &str) -> BatchFileReport {
+    BatchFileReport {
+        relative_path: relative,
+        output_path: None,
+        status: BatchFileStatus::Skipped,
+        reason: Some(reason.to_string()),
+        bytes,
+        entity_count: 0,
+        duration_secs: 0.0,
+    }
+}
+
+/// 规范化路径；路径尚不存在时规范化最近的已存在祖先，再拼接其余部分
+///
+/// 输入、输出目录的重合判断都基于规范化结果，经由符号链接或 `..` 的写法不会绕过检查。
+fn canonical_path(path: &Path) -> Result<PathBuf> {
+    let absolute = if path.is_absolute() { path.to_path_buf() } else { std::env::current_dir()?.join(path) };
+    for ancestor in absolute.ancestors() {
+        let Ok(base) = ancestor.canonicalize() else {
+            continue;
+        };
+        let mut resolved = base;
+        for component in absolute.strip_prefix(ancestor)?.components() {
+            match component {
+                Component::ParentDir => {
+                    resolved.pop();
+                }
+                Component::CurDir => {}
+                other => resolved.push(other),
+            }
+        }
+        return Ok(resolved);
+    }
+    Ok(absolute)
+}
+
+/// 遍历输入目录，生成处理计划（`output_root` 为规范化的输出目录，位于输入目录内时整棵跳过）
+fn plan(input_root: &Path, output_root: Option<&Path>, options: &BatchOptions) -> Result<BatchPlan> {
+    let include = build_globset(&options.include)?;
+    let exclude = build_globset(&options.exclude)?;
+
+    let mut files = Vec::new();
+    let mut skipped_files = Vec::new();
+
+    let walker = WalkDir::new(input_root).follow_links(false).into_iter().filter_entry(|e| {
+        if e.depth() == 0 {
+            return true;
+        }
+        // 输出目录位于输入目录内时不能再被遍历
+        if output_root.is_some_and(|output| e.path().starts_with(output)) {
+            return false;
+        }
+        if options.skip_hidden && is_hidden(e.path()) {
+            return false;
+        }
+        // 目录命中 exclude 时整棵剪枝
+        !(e.file_type().is_dir()
+            && exclude.as_ref().is_some_and(|set| set.is_match(relative_path(input_root, e.path()))))
+    });
+
+    for entry in walker {
+        let entry = match entry {
+            Ok(e) => e,
+            Err(e) => {
+                let relative = e.path().map(|p| relative_path(input_root, p)).unwrap_or_default();
+                skipped_files.push(skipped(relative, 0, &format!("无法访问: {}", e)));
+                continue;
+            }
+        };
+        if !entry.file_type().is_file() {
+            continue;
+        }
+
+        let path = entry.path();
+        let relative = relative_path(input_root, path);
+        if exclude.as_ref().is_some_and(|set| set.is_match(&relative)) {
+            continue;
+        }
+        if include.as_ref().is_some_and(|set| !set.is_match(&relative)) {
+            continue;
+        }
+
+        let bytes = entry.metadata().map(|m| m.len()).unwrap_or(0);
+        if options.max_file_size.is_some_and(|max| bytes > max) {
+            skipped_files.push(skipped(relative, bytes, "超过文件大小上限"));
+            continue;
+        }
+        if options.skip_binary && !is_document(path) && is_binary(path) {
+            skipped_files.push(skipped(relative, bytes, "二进制文件"));
+            continue;
+        }
+
+        files.push(PlannedFile {
+            input: path.to_path_buf(),
+            relative,
+            bytes,
+        });
+    }
+
+    Ok(BatchPlan {
+        files,
+        skipped: skipped_files,
+    })
+}
+
+/// 输出路径：镜像相对路径，PDF 输出为纯文本
+fn output_path_for(output_root: &Path, relative: &str) -> PathBuf {
+    let target = output_root.join(relative);
+    match target.extension().and_then(|e| e.to_str()) {
+        Some(ext) if ext.eq_ignore_ascii_case("pdf") => target.with_extension("txt"),
+        _ => target,
+    }
+}
+
+/// 处理单个文件
+fn process_one(
+    file: &PlannedFile,
+    output_root: &Path,
+    engine: &Arc<HybridEngine>,
+    process: &ProcessOptions,
+) -> (BatchFileReport, BTreeMap<String, usize>) {
+    let start = Instant::now();
+    let output = output_path_for(output_root, &file.relative);
+
+    let result = output
+        .parent()
+        .map_or(Ok(()), std::fs::create_dir_all)
+        .context("无法创建输出目录")
+        .and_then(|_| processor::process_file(file.input.as_path(), output.as_path(), engine, process, |_| {}));
+
+    let mut counts = BTreeMap::new();
+    let report = match result {
+        Ok(stats) => {
+            for entity in &stats.entities {
+                *counts.entry(entity.entity_type.clone()).or_insert(0) += 1;
+            }
+            BatchFileReport {
+                relative_path: file.relative.clone(),
+                output_path: Some(output.to_string_lossy().to_string()),
+                status: BatchFileStatus::Processed,
+                reason: None,
+                bytes: file.bytes,
+                entity_count: stats.entities.len(),
+                duration_secs: start.elapsed().as_secs_f64(),
+            }
+        }
+        Err(e) => BatchFileReport {
+            relative_path: file.relative.clone(),
+            output_path: None,
+            status: BatchFileStatus::Failed,
+            reason: Some(format!("{:#}", e)),
+            bytes: file.bytes,
+            entity_count: 0,
+            duration_secs: start.elapsed().as_secs_f64(),
+        },
+    };
+    (report, counts)
+}
+
+/// 批量处理目录
+///
+/// 单个文件失败不会中断任务，失败原因记录在汇总的逐文件结果中。
+/// 整个批次使用同一份 `process` 选项快照；`on_progress` 在每个文件完成（或被跳过）后调用，可能来自多个工作线程。
+pub fn process_directory(
+    input_root: &Path,
+    output_root: &Path,
+    options: &BatchOptions,
+    engine: &Arc<HybridEngine>,
+    process: &ProcessOptions,
+    on_progress: impl Fn(&BatchProgress) + Sync + Send,
+) -> Result<BatchSummary> {
+    let start = Instant::now();
+    if !input_root.is_dir() {
+        anyhow::bail!("输入路径不是目录: {}", input_root.display());
+    }
+    let input_root = &input_root.canonicalize().context("无法解析输入目录")?;
+    let output_root = &canonical_path(output_root).context("无法解析输出目录")?;
+    if output_root == input_root {
+        anyhow::bail!("输出目录不能与输入目录相同");
+    }
+    if input_root.starts_with(output_root) {
+        anyhow::bail!("输入目录不能位于输出目录内");
+    }
+    std::fs::create_dir_all(output_root).context("无法创建输出根目录")?;
+
+    let plan = plan(input_root, Some(output_root), options)?;
+    let files_total = plan.files.len() + plan.skipped.len();
+    let bytes_total: u64 = plan.files.iter().map(|f| f.bytes).sum();
+    let files_done = AtomicUsize::new(0);
+    let bytes_done = AtomicU64::new(0);
+
+    let report_progress = |file: &BatchFileReport, bytes: u64| {
+        on_progress(&BatchProgress {
+            file: file.clone(),
+            files_done: files_done.fetch_add(1, Ordering::Relaxed) + 1,
+            files_total,
+            bytes_done: bytes_done.fetch_add(bytes, Ordering::Relaxed) + bytes,
+            bytes_total,
+        });
+    };
+
+    for report in &plan.skipped {
+        report_progress(report, 0);
+    }
+
+    let results: Vec<(BatchFileReport, BTreeMap<String, usize>)> = plan
+        .files
+        .par_iter()
+        .map(|file| {
+            let (report, counts) = process_one(file, output_root, engine, process);
+            report_progress(&report, file.bytes);
+            (report, counts)
+        })
+        .collect();
+
+    let mut entity_counts = BTreeMap::new();
+    let mut files = plan.skipped;
+    for (report, counts) in results {
+        for (entity_type, n) in counts {
+            *entity_counts.entry(entity_type).or_insert(0) += n;
+        }
+        files.push(report);
+    }
+    files.sort_by(|a, b| a.relative_path.cmp(&b.relative_path));
+
+    let count = |status: BatchFileStatus| files.iter().filter(|f| f.status == status).count();
+    Ok(BatchSummary {
+        output_root: output_root.to_string_lossy().to_string(),
+        files_total,
+        processed: count(BatchFileStatus::Processed),
+        skipped: count(BatchFileStatus::Skipped),
+        failed: count(BatchFileStatus::Failed),
+        bytes_processed: files
+            .iter()
+            .filter(|f| f.status == BatchFileStatus::Processed)
+            .map(|f| f.bytes)
+            .sum(),
+        entity_counts,
+        duration_secs: start.elapsed().as_secs_f64(),
+        files,
+    })
+}
+
+/// 扫描目录（仅检测，不写输出文件）
+///
+/// 过滤规则与 [`process_directory`] 相同；各文件报告按相对路径顺序合并，
+/// 汇总报告的 `root` 记为输入目录。单个文件扫描失败只通过进度回调报告。
+pub fn scan_directory(
+    input_root: &Path,
+    options: &BatchOptions,
+    engine: &Arc<HybridEngine>,
+    process: &ProcessOptions,
+    max_samples: usize,
+    on_progress: impl Fn(&BatchProgress) + Sync + Send,
+) -> Result<ScanReport> {
+    let start = Instant::now();
+    if !input_root.is_dir() {
+        anyhow::bail!("输入路径不是目录: {}", input_root.display());
+    }
+
+    let plan = plan(input_root, None, options)?;
+    let files_total = plan.files.len() + plan.skipped.len();
+    let bytes_total: u64 = plan.files.iter().map(|f| f.bytes).sum();
+    let files_done = AtomicUsize::new(0);
+    let bytes_done = AtomicU64::new(0);
+
+    let report_progress = |file: &BatchFileReport, bytes: u64| {
+        on_progress(&BatchProgress {
+            file: file.clone(),
+            files_done: files_done.fetch_add(1, Ordering::Relaxed) + 1,
+            files_total,
+            bytes_done: bytes_done.fetch_add(bytes, Ordering::Relaxed) + bytes,
+            bytes_total,
+        });
+    };
+
+    for report in &plan.skipped {
+        report_progress(report, 0);
+    }
+
+    let mut results: Vec<(&str, ScanReport)> = plan
+        .files
+        .par_iter()
+        .filter_map(|file| {
+            let file_start = Instant::now();
+            let result = processor::scan_file(file.input.as_path(), engine, process, max_samples, |_| {});
+            let (status, reason, entity_count) = match &result {
+                Ok(report) => (BatchFileStatus::Processed, None, report.total_findings),
+                Err(e) => (BatchFileStatus::Failed, Some(format!("{:#}", e)), 0),
+            };
+            let file_report = BatchFileReport {
+                relative_path: file.relative.clone(),
+                output_path: None,
+                status,
+                reason,
+                bytes: file.bytes,
+                entity_count,
+                duration_secs: file_start.elapsed().as_secs_f64(),
+            };
+            report_progress(&file_report, file.bytes);
+            result.ok().map(|report| (file.relative.as_str(), report))
+        })
+        .collect();
+    results.sort_by(|a, b| a.0.cmp(b.0));
+
+    let mut report = ScanReport::new(max_samples).with_root(input_root.to_string_lossy());
+    for (_, partial) in results {
+        report.merge(partial);
+    }
+    report.duration_secs = start.elapsed().as_secs_f64();
+    Ok(report)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::rules::Rule;
+
+    fn temp_dir(tag: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("safemask_batch_{}_{}", tag, uuid::Uuid::new_v4()));
+        std::fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    fn write(root: &Path, relative: &str, content: &[u8]) {
+        let path = root.join(relative);
+        std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+        std::fs::write(path, content).unwrap();
+    }
+
+    fn engine() -> Arc<HybridEngine> {
+        Arc::new(HybridEngine::from_rules(vec![Rule {
+            name: "email".to_string(),
+            pattern: r"[a-zA-Z0-9._%+-]+@[a-zA-Z0-9.-]+\.[a-zA-Z]{2,}".to_string(),
+            mask: "<EMAIL>".to_string(),
+            priority: 0,
+            enabled: true,
+            is_custom: false,
+            case_insensitive: false,
+            whole_word: false,
+            unicode_boundary: false,
+            group: String::new(),
+        }]))
+    }
+
+    #[test]
+    fn test_mirrors_tree_and_filters() {
+        let input = temp_dir("in");
+        write(&input, "app.log", b"user a@example.com\n");
+        write(&input, "nested/deep/trace.log", b"b@example.com c@example.com\n");
+        write(&input, "notes.md", b"d@example.com\n");
+        write(&input, ".git/config", b"e@example.com\n");
+        write(&input, "target/build.log", b"f@example.com\n");
+        write(&input, "image.log", b"\x89PNG\x00\x00binary");
+        write(&input, "huge.log", &vec![b'x'; 4096]);
+        let output = input.join("masked");
+
+        let options = BatchOptions {
+            include: vec!["*.log".into()],
+            exclude: vec!["target".into()],
+            max_file_size: Some(1024),
+            ..Default::default()
+        };
+        let progress = AtomicUsize::new(0);
+        let summary = process_directory(&input, &output, &options, &engine(), &ProcessOptions::default(), |_| {
+            progress.fetch_add(1, Ordering::Relaxed);
+        })
+        .unwrap();
+
+        assert_eq!(summary.processed, 2);
+        assert_eq!(summary.skipped, 2); // 二进制 + 超大
+        assert_eq!(summary.failed, 0);
+        assert_eq!(summary.entity_counts.values().sum::<usize>(), 3);
+        assert_eq!(progress.load(Ordering::Relaxed), summary.files_total);
+
+        let masked = std::fs::read_to_string(output.join("nested/deep/trace.log")).unwrap();
+        assert_eq!(masked, "<EMAIL> <EMAIL>\n");
+        assert!(!output.join("notes.md").exists());
+        assert!(!output.join("target").exists());
+        assert!(!output.join(".git").exists());
+        // 输出目录位于输入目录内，不会被再次处理
+        assert!(!output.join("masked").exists());
+
+        std::fs::remove_dir_all(&input).unwrap();
+    }
+
+    #[test]
+    fn test_rejects_same_output_and_bad_glob() {
+        let input = temp_dir("same");
+        assert!(process_directory(&input, &input, &BatchOptions::default(), &engine(), &ProcessOptions::default(), |_| {}).is_err());
+
+        let options = BatchOptions {
+            include: vec!["[".into()],
+            ..Default::default()
+        };
+        assert!(process_directory(&input, &input.join("out"), &options, &engine(), &ProcessOptions::default(), |_| {}).is_err());
+        std::fs::remove_dir_all(&input).unwrap();
+    }
+
+    #[test]
+    fn test_output_checks_use_canonical_paths() {
+        let input = temp_dir("canonical");
+        write(&input, "sub/app.log", b"a@example.com
+");
+        let process = |input: &Path, output: &Path| {
+            process_directory(input, output, &BatchOptions::default(), &engine(), &ProcessOptions::default(), |_| {})
+        };
+
+        // 同一目录的不同写法
+        assert!(process(&input, &input.join("sub/..")).is_err());
+        // 输入目录位于输出目录内
+        assert!(process(&input.join("sub"), &input.join("sub/../.")).is_err());
+        assert!(process(&input.join("sub"), &input).is_err());
+
+        // 输出目录以非规范写法位于输入目录内：输出树整体跳过，不会被再次处理
+        let summary = process(&input, &input.join("sub/../masked")).unwrap();
+        assert_eq!(summary.processed, 1);
+        let summary = process(&input, &input.join("sub/../masked")).unwrap();
+        assert_eq!(summary.processed, 1);
+        assert!(!input.join("masked/masked").exists());
+        std::fs::remove_dir_all(&input).unwrap();
+    }
+
+    #[test]
+    fn test_utf16_files_are_not_binary() {
+        let input = temp_dir("utf16");
+        let utf16 = |bom: &[u8], text: &str| -> Vec<u8> {
+            bom.iter().copied().chain(text.encode_utf16().flat_map(u16::to_le_bytes)).collect()
+        };
+        write(&input, "bom.csv", &utf16(&[0xFF, 0xFE], "name,mail\r\nA,a@example.com\r\n"));
+        write(&input, "plain.log", &utf16(&[], "user b@example.com logged in\r\n"));
+        write(&input, "blob.bin", &[0x7F, b'E', b'L', b'F', 0, 0, 0, 1, 2, 0, 0, 0]);
+
+        let output = input.join("masked");
+        let summary = process_directory(&input, &output, &BatchOptions::default(), &engine(), &ProcessOptions::default(), |_| {}).unwrap();
+        assert_eq!((summary.processed, summary.skipped), (2, 1));
+        let masked = std::fs::read(output.join("plain.log")).unwrap();
+        assert_eq!(masked, utf16(&[], "user <EMAIL> logged in\r\n"));
+
+        let report = scan_directory(&input, &BatchOptions::default(), &engine(), &ProcessOptions::default(), 10, |_| {}).unwrap();
+        assert_eq!(report.total_findings, 2);
+        std::fs::remove_dir_all(&input).unwrap();
+    }
+
+    #[test]
+    fn test_scan_directory_writes_nothing() {
+        let input = temp_dir("scan");
+        write(&input, "b/trace.log", b"b@example.com\n");
+        write(&input, "a.log", b"x\na@example.com c@example.com\n");
+        write(&input, "skip.md", b"d@example.com\n");
+
+        let options = BatchOptions {
+            include: vec!["*.log".into()],
+            ..Default::default()
+        };
+        let report = scan_directory(&input, &options, &engine(), &ProcessOptions::default(), 10, |_| {}).unwrap();
+
+        assert_eq!(report.root.as_deref(), Some(input.to_string_lossy().as_ref()));
+        assert_eq!(report.total_findings, 3);
+        assert_eq!(report.files.len(), 2);
+        // 按相对路径顺序合并：a.log 在 b/trace.log 之前
+        assert!(report.samples[0].file.ends_with("a.log"));
+        assert_eq!((report.samples[0].line, report.samples[0].offset), (2, 2));
+        assert!(report.samples[2].file.ends_with("trace.log"));
+        assert_eq!(std::fs::read_dir(&input).unwrap().count(), 3);
+        std::fs::remove_dir_all(&input).unwrap();
+    }
+}
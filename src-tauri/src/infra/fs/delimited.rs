@@ -0,0 +1,663 @@
+//! CSV / TSV 按列脱敏
+//!
+//! 以记录为单位解析（引号内的分隔符与换行不会被拆开），按列策略决定每个单元格的处理方式：
+//! - `mask`：整格脱敏，使用指定的脱敏策略，不依赖识别结果（如 `phone` 列一律打码）
+//! - `detect`（默认）：按识别结果脱敏；开启列推断时，抽样行中多数单元格整格命中同一实体类型的列
+//!   视为该类型的列，整列脱敏（识别器漏掉的变体也不会泄露）
+//! - `skip`：原样保留（如 `order_id`）
+//!
+//! 方言探测：分隔符取 `,` / `\t` / `;` / `|` 中各行字段数最一致的一个（`.tsv` 优先制表符），
+//! 同时沿用源文件的换行风格（CRLF / LF）与引号风格（全部字段带引号则输出也全部带引号）。
+//! 表头判定：首行命中按名称配置的列策略即为表头；否则要求首行非空、非数字、不重复且不含识别到的实体。
+//! 输出单元格按 CSV 规则重新转义（含分隔符、引号、换行的值自动加引号，引号双写）。
+
+use crate::common::state::EntitySpanBrief;
+use crate::core::file_options::{ColumnAction, ColumnRef, CsvOptions};
+use crate::core::hybrid_engine::HybridEngine;
+use crate::core::masking::MaskStrategyType;
+use crate::core::recognizer::{EntitySpan, EntityType};
+use crate::infra::fs::processor::{check_cancel, count_lines, span_to_finding, LineTracker};
+use crate::infra::fs::scan_report::ScanReport;
+use anyhow::Result;
+use csv::{ByteRecord, QuoteStyle, ReaderBuilder, Terminator, WriterBuilder};
+use rayon::prelude::*;
+use std::collections::{HashMap, HashSet};
+use std::ops::Range;
+use std::sync::atomic::AtomicBool;
+
+/// 每批并行处理的记录数
+const BATCH_RECORDS: usize = 4096;
+/// 方言探测读取的字节数 / 记录数
+const SNIFF_BYTES: usize = 64 * 1024;
+const SNIFF_RECORDS: usize = 50;
+/// 候选分隔符
+const DELIMITERS: [u8; 4] = [b',', b'\t', b';', b'|'];
+/// 列推断：至少需要的非空样本数、整格命中的样本占比、单格被实体覆盖的比例
+const MIN_INFER_SAMPLES: usize = 3;
+const INFER_RATIO: f64 = 0.8;
+const COVER_RATIO: f64 = 0.9;
+
+// ─────────────────────────────────────────────────────────────────────────────
+// 方言探测
+// ─────────────────────────────────────────────────────────────────────────────
+
+/// CSV 方言（引号固定为双引号，引号内以双写转义）
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Dialect {
+    pub delimiter: u8,
+    pub has_headers: bool,
+    /// 源文件所有字段都带引号（输出保持全引号）
+    pub quote_all: bool,
+    /// 源文件使用 CRLF 换行
+    pub crlf: bool,
+}
+
+impl Dialect {
+    /// 按文件开头的样本探测方言（`default_delimiter` 在得分相同时优先）
+    pub fn sniff(data: &[u8], default_delimiter: u8, engine: &HybridEngine, options: &CsvOptions) -> Self {
+        let truncated = data.len() > SNIFF_BYTES;
+        let sample = &data[..data.len().min(SNIFF_BYTES)];
+
+        let candidates = std::iter::once(default_delimiter)
+            .chain(DELIMITERS.into_iter().filter(|&d| d != default_delimiter));
+        let mut best: Option<(u8, (f64, usize), Vec<ByteRecord>)> = None;
+        for delimiter in candidates {
+            let records = sample_records(sample, delimiter, truncated);
+            let Some(score) = delimiter_score(&records) else {
+                continue;
+            };
+            if best.as_ref().is_none_or(|(_, b, _)| score > *b) {
+                best = Some((delimiter, score, records));
+            }
+        }
+        let (delimiter, records) = match best {
+            Some((delimiter, _, records)) => (delimiter, records),
+            None => (default_delimiter, sample_records(sample, default_delimiter, truncated)),
+        };
+
+        Self {
+            delimiter,
+            has_headers: records.first().is_some_and(|first| looks_like_header(first, engine, options)),
+            quote_all: all_fields_quoted(sample, delimiter),
+            crlf: memchr::memchr(b'\n', sample).is_some_and(|i| i > 0 && sample[i - 1] == b'\r'),
+        }
+    }
+
+    fn reader<'a>(&self, data: &'a [u8]) -> csv::Reader<&'a [u8]> {
+        ReaderBuilder::new()
+            .delimiter(self.delimiter)
+            .has_headers(false)
+            .flexible(true)
+            .from_reader(data)
+    }
+
+    /// 按方言重新编码单条记录（不含行终止符）
+    fn encode(&self, record: &ByteRecord) -> Result<Vec<u8>> {
+        let mut writer = WriterBuilder::new()
+            .delimiter(self.delimiter)
+            .flexible(true)
+            .quote_style(if self.quote_all { QuoteStyle::Always } else { QuoteStyle::Necessary })
+            .terminator(if self.crlf { Terminator::CRLF } else { Terminator::Any(b'\n') })
+            .from_writer(Vec::new());
+        writer.write_byte_record(record)?;
+        let mut out = writer.into_inner().map_err(|e| e.into_error())?;
+        out.truncate(out.len() - if self.crlf { 2 } else { 1 });
+        Ok(out)
+    }
+}
+
+/// 扩展名对应的默认分隔符
+pub fn default_delimiter(ext: &str) -> u8 {
+    if ext.eq_ignore_ascii_case("tsv") { b'\t' } else { b',' }
+}
+
+/// 样本内的完整记录（样本被截断时丢弃可能不完整的末条）
+fn sample_records(sample: &[u8], delimiter: u8, truncated: bool) -> Vec<ByteRecord> {
+    let mut reader = ReaderBuilder::new()
+        .delimiter(delimiter)
+        .has_headers(false)
+        .flexible(true)
+        .from_reader(sample);
+    let mut records: Vec<ByteRecord> = reader
+        .byte_records()
+        .take(SNIFF_RECORDS)
+        .filter_map(Result::ok)
+        .collect();
+    if truncated && records.len() < SNIFF_RECORDS {
+        records.pop();
+    }
+    records
+}
+
+/// 分隔符得分：（字段数一致的记录占比, 字段数），单列视为不匹配
+fn delimiter_score(records: &[ByteRecord]) -> Option<(f64, usize)> {
+    let mut counts: HashMap<usize, usize> = HashMap::new();
+    for record in records {
+        *counts.entry(record.len()).or_default() += 1;
+    }
+    let (fields, freq) = counts.into_iter().max_by_key(|&(fields, freq)| (freq, fields))?;
+    (fields >= 2).then(|| (freq as f64 / records.len() as f64, fields))
+}
+
+/// 样本中的每个字段是否都以引号开头
+fn all_fields_quoted(sample: &[u8], delimiter: u8) -> bool {
+    let mut in_quotes = false;
+    let mut at_field_start = true;
+    let mut fields = 0usize;
+    for &b in sample {
+        if at_field_start {
+            if b == b'\r' || b == b'\n' {
+                continue;
+            }
+            if b != b'"' {
+                return false;
+            }
+            fields += 1;
+            at_field_start = false;
+            in_quotes = true;
+            continue;
+        }
+        match b {
+            b'"' => in_quotes = !in_quotes,
+            b'\r' | b'\n' if !in_quotes => at_field_start = true,
+            _ if b == delimiter && !in_quotes => at_field_start = true,
+            _ => {}
+        }
+    }
+    fields > 0
+}
+
+/// 首行是否为表头
+fn looks_like_header(first: &ByteRecord, engine: &HybridEngine, options: &CsvOptions) -> bool {
+    let cells: Vec<String> = first
+        .iter()
+        .map(|cell| String::from_utf8_lossy(cell).trim().to_string())
+        .collect();
+    let named = options.policies.iter().any(|p| match &p.column {
+        ColumnRef::Name(name) => cells.iter().any(|c| c.eq_ignore_ascii_case(name.trim())),
+        ColumnRef::Index(_) => false,
+    });
+    if named {
+        return true;
+    }
+    let mut seen = HashSet::new();
+    cells
+        .iter()
+        .all(|c| !c.is_empty() && c.parse::<f64>().is_err() && seen.insert(c.to_lowercase()))
+        && first.iter().all(|cell| engine.detect(cell).is_empty())
+}
+
+// ─────────────────────────────────────────────────────────────────────────────
+// 列计划
+// ─────────────────────────────────────────────────────────────────────────────
+
+/// 单列的最终处理方式（列策略 + 推断结果）
+#[derive(Debug, Clone)]
+enum ColumnPlan {
+    Skip,
+    Detect,
+    /// 整格（去掉首尾空白）替换为 `template` 类型的实体
+    Whole { template: EntitySpan, strategy: MaskStrategyType },
+}
+
+impl ColumnPlan {
+    /// 单元格内需要替换的实体（偏移相对单元格）
+    fn spans(&self, engine: &HybridEngine, cell: &[u8]) -> Vec<EntitySpan> {
+        match self {
+            Self::Skip => Vec::new(),
+            Self::Detect => engine.detect(cell).into_iter().filter(|s| s.start < s.end).collect(),
+            Self::Whole { template, .. } => {
+                let range = trimmed(cell);
+                if range.is_empty() {
+                    return Vec::new();
+                }
+                let mut span = template.clone();
+                span.start = range.start;
+                span.end = range.end;
+                vec![span]
+            }
+        }
+    }
+
+    fn mask(&self, engine: &HybridEngine, cell: &[u8], spans: &[EntitySpan]) -> Vec<u8> {
+        match self {
+            Self::Whole { strategy, .. } if *strategy != MaskStrategyType::Replace => {
+                let span = &spans[0];
+                let original = String::from_utf8_lossy(&cell[span.start..span.end]);
+                let masked = engine.masking_engine().mask_with(strategy, &original, span);
+                [&cell[..span.start], masked.as_bytes(), &cell[span.end..]].concat()
+            }
+            _ => engine.mask_spans(cell, spans),
+        }
+    }
+}
+
+/// 根据表头与抽样行生成各列的处理方式
+fn build_plans(
+    header: Option<&ByteRecord>,
+    sample: &[ByteRecord],
+    engine: &HybridEngine,
+    options: &CsvOptions,
+) -> Vec<ColumnPlan> {
+    let width = sample.iter().map(ByteRecord::len).chain(header.map(ByteRecord::len)).max().unwrap_or(0);
+    (0..width)
+        .map(|i| {
+            let name = column_name(header, i);
+            let policy = options.policies.iter().find(|p| match &p.column {
+                ColumnRef::Index(n) => *n == i + 1,
+                ColumnRef::Name(n) => name.as_deref().is_some_and(|h| h.eq_ignore_ascii_case(n.trim())),
+            });
+            let inferred = || {
+                options
+                    .infer_columns
+                    .then(|| infer_column(engine, sample.iter().filter_map(|r| r.get(i))))
+                    .flatten()
+            };
+
+            match policy {
+                Some(p) if p.action == ColumnAction::Skip => ColumnPlan::Skip,
+                Some(p) if p.action == ColumnAction::Mask => {
+                    let template = match &p.entity_type {
+                        Some(label) => column_span(EntityType::from_label(label)),
+                        None => inferred().unwrap_or_else(|| {
+                            let label = name.unwrap_or_else(|| format!("COLUMN_{}", i + 1));
+                            column_span(EntityType::Custom(label))
+                        }),
+                    };
+                    let strategy = MaskStrategyType::from_str(p.strategy.as_deref().unwrap_or("replace"));
+                    ColumnPlan::Whole { template, strategy }
+                }
+                _ => match inferred() {
+                    Some(template) => ColumnPlan::Whole { template, strategy: MaskStrategyType::Replace },
+                    None => ColumnPlan::Detect,
+                },
+            }
+        })
+        .collect()
+}
+
+fn column_name(header: Option<&ByteRecord>, index: usize) -> Option<String> {
+    header
+        .and_then(|h| h.get(index))
+        .map(|cell| String::from_utf8_lossy(cell).trim().to_string())
+        .filter(|name| !name.is_empty())
+}
+
+fn column_span(entity_type: EntityType) -> EntitySpan {
+    EntitySpan::new(0, 0, entity_type, 1.0, "csv_column")
+}
+
+/// 列推断：多数非空样本整格命中同一实体类型时返回该类型的实体模板（沿用命中规则的掩码）
+fn infer_column<'a>(engine: &HybridEngine, cells: impl Iterator<Item = &'a [u8]>) -> Option<EntitySpan> {
+    let mut votes: HashMap<EntityType, (usize, EntitySpan)> = HashMap::new();
+    let mut filled = 0usize;
+    for cell in cells {
+        let value = &cell[trimmed(cell)];
+        if value.is_empty() {
+            continue;
+        }
+        filled += 1;
+        let Some(best) = engine.detect(value).into_iter().max_by_key(|s| s.end - s.start) else {
+            continue;
+        };
+        if ((best.end - best.start) as f64) < value.len() as f64 * COVER_RATIO {
+            continue;
+        }
+        votes.entry(best.entity_type.clone()).or_insert((0, best)).0 += 1;
+    }
+
+    let (count, mut template) = votes.into_values().max_by_key(|(count, _)| *count)?;
+    if filled < MIN_INFER_SAMPLES || (count as f64) < filled as f64 * INFER_RATIO {
+        return None;
+    }
+    template.metadata.clear();
+    Some(template)
+}
+
+/// 去掉首尾 ASCII 空白后的区间
+fn trimmed(cell: &[u8]) -> Range<usize> {
+    let start = cell.iter().position(|b| !b.is_ascii_whitespace()).unwrap_or(cell.len());
+    let end = cell.iter().rposition(|b| !b.is_ascii_whitespace()).map_or(start, |p| p + 1);
+    start..end
+}
+
+// ─────────────────────────────────────────────────────────────────────────────
+// 脱敏与扫描
+// ─────────────────────────────────────────────────────────────────────────────
+
+/// CSV 脱敏结果
+pub struct CsvStats {
+    /// 文本行数（按换行符计，末行无换行符时也计为一行；引号内的换行同样计入）
+    pub lines: u64,
+    /// 检测到的实体（偏移相对 `data`；`line` / `column` 为实体起点的行列，`field` 为字段序号）
+    pub entities: Vec<EntitySpanBrief>,
+}
+
+/// 按列策略脱敏 UTF-8 CSV 文本，脱敏后的记录按批交给 `sink`（每批都以完整记录结尾）；
+/// 表头与未命中的记录按原文字节输出，只有被替换的记录按方言重新编码
+pub fn mask_csv(
+    data: &[u8],
+    dialect: &Dialect,
+    engine: &HybridEngine,
+    options: &CsvOptions,
+    cancel: &AtomicBool,
+    progress: impl Fn(f64),
+    mut sink: impl FnMut(&[u8]) -> Result<()>,
+) -> Result<CsvStats> {
+    let mut records = dialect.reader(data).into_byte_records().peekable();
+    let header = if dialect.has_headers { records.next().transpose()? } else { None };
+    let mut batch = next_batch(&mut records, BATCH_RECORDS.max(options.sample_rows))?;
+    let plans = build_plans(header.as_ref(), &batch[..batch.len().min(options.sample_rows)], engine, options);
+
+    let total = data.len().max(1) as f64;
+    let mut stats = CsvStats { lines: count_lines(data), entities: Vec::new() };
+    // `data` 中已输出到的位置（原文照抄的部分包括表头、记录之间的换行与未命中的记录）
+    let mut copied = 0;
+    loop {
+        check_cancel(cancel)?;
+        let masked: Vec<Option<(ByteRecord, Vec<EntitySpanBrief>)>> =
+            batch.par_iter().map(|record| mask_record(data, dialect, record, &plans, engine)).collect();
+
+        let is_last = records.peek().is_none();
+        let batch_end = records
+            .peek()
+            .and_then(|next| next.as_ref().ok())
+            .and_then(ByteRecord::position)
+            .map_or(data.len(), |p| p.byte() as usize);
+        let mut out = Vec::new();
+        for (i, masked) in masked.into_iter().enumerate() {
+            let Some((record, entities)) = masked else { continue };
+            let start = batch[i].position().map_or(0, |p| p.byte() as usize);
+            let end = batch.get(i + 1).and_then(ByteRecord::position).map_or(batch_end, |p| p.byte() as usize);
+            // 记录位置可能落在上一条的行终止符或空行上；去掉首尾的换行字节后才是记录原文，换行随后照抄
+            let raw = &data[start..end];
+            let content_start = start + raw.iter().take_while(|&&b| matches!(b, b'\r' | b'\n')).count();
+            let content_end = end - raw.iter().rev().take_while(|&&b| matches!(b, b'\r' | b'\n')).count();
+            out.extend_from_slice(&data[copied..content_start]);
+            out.extend_from_slice(&dialect.encode(&record)?);
+            copied = content_end;
+            stats.entities.extend(entities);
+        }
+        out.extend_from_slice(&data[copied..batch_end]);
+        copied = batch_end;
+        sink(&out)?;
+        if let Some(position) = batch.last().and_then(ByteRecord::position) {
+            progress(position.byte() as f64 / total);
+        }
+        if is_last {
+            break;
+        }
+        batch = next_batch(&mut records, BATCH_RECORDS)?;
+    }
+    Ok(stats)
+}
+
+/// 按列策略扫描 UTF-8 CSV 文本（`location` 为列名，偏移相对 `data`，`line` / `column` 为命中起点的行列）
+pub fn scan_csv(
+    data: &[u8],
+    dialect: &Dialect,
+    file: &str,
+    engine: &HybridEngine,
+    options: &CsvOptions,
+    max_samples: usize,
+) -> Result<ScanReport> {
+    let mut records = dialect.reader(data).into_byte_records().peekable();
+    let header = if dialect.has_headers { records.next().transpose()? } else { None };
+    let mut batch = next_batch(&mut records, BATCH_RECORDS.max(options.sample_rows))?;
+    let plans = build_plans(header.as_ref(), &batch[..batch.len().min(options.sample_rows)], engine, options);
+
+    let mut report = ScanReport::new(max_samples);
+    report.bytes_scanned = data.len() as u64;
+    report.lines_scanned = count_lines(data);
+    while !batch.is_empty() {
+        let partials: Vec<ScanReport> = batch
+            .par_iter()
+            .map(|record| {
+                let mut partial = ScanReport::new(max_samples);
+                let mut locator = CellLocator::new(data, dialect, record);
+                for (i, cell) in record.iter().enumerate() {
+                    let plan = plans.get(i).unwrap_or(&ColumnPlan::Detect);
+                    for span in plan.spans(engine, cell) {
+                        let mut finding = span_to_finding(&span, cell, file, engine);
+                        let (start, end) = (locator.offset(i, span.start), locator.offset(i, span.end));
+                        let (line, column) = locator.locate(start);
+                        finding.location = Some(column_name(header.as_ref(), i).unwrap_or_else(|| format!("#{}", i + 1)));
+                        (finding.line, finding.column) = (line, column);
+                        (finding.offset, finding.length) = (start as u64, (end - start) as u64);
+                        partial.add_finding(finding);
+                    }
+                }
+                partial
+            })
+            .collect();
+        for partial in partials {
+            report.merge(partial);
+        }
+        batch = next_batch(&mut records, BATCH_RECORDS)?;
+    }
+    Ok(report)
+}
+
+fn next_batch(records: &mut impl Iterator<Item = csv::Result<ByteRecord>>, size: usize) -> Result<Vec<ByteRecord>> {
+    Ok(records.take(size).collect::<csv::Result<Vec<_>>>()?)
+}
+
+fn mask_record(
+    data: &[u8],
+    dialect: &Dialect,
+    record: &ByteRecord,
+    plans: &[ColumnPlan],
+    engine: &HybridEngine,
+) -> Option<(ByteRecord, Vec<EntitySpanBrief>)> {
+    let mut locator = CellLocator::new(data, dialect, record);
+    let mut masked = ByteRecord::with_capacity(record.as_slice().len(), record.len());
+    let mut entities = Vec::new();
+    for (i, cell) in record.iter().enumerate() {
+        let plan = plans.get(i).unwrap_or(&ColumnPlan::Detect);
+        let spans = plan.spans(engine, cell);
+        if spans.is_empty() {
+            masked.push_field(cell);
+            continue;
+        }
+        masked.push_field(&plan.mask(engine, cell, &spans));
+        entities.extend(spans.iter().map(|span| {
+            let mut brief = engine.brief(span);
+            (brief.start, brief.end) = (locator.offset(i, span.start), locator.offset(i, span.end));
+            let (line, column) = locator.locate(brief.start);
+            (brief.line, brief.column, brief.field) = (Some(line), Some(column), Some(i as u64 + 1));
+            brief
+        }));
+    }
+    // 没有任何替换的记录返回 `None`，由调用方照抄原文
+    (!entities.is_empty()).then_some((masked, entities))
+}
+
+/// 单元格内偏移 → `data` 内偏移与行列（按记录原文重新切分字段，还原引号与转义占用的字节）
+struct CellLocator<'a> {
+    data: &'a [u8],
+    /// 各字段在原文中的起点（带引号的字段指向开引号）
+    fields: Vec<usize>,
+    tracker: LineTracker<'a>,
+}
+
+impl<'a> CellLocator<'a> {
+    fn new(data: &'a [u8], dialect: &Dialect, record: &ByteRecord) -> Self {
+        let (start, line) = record.position().map_or((0, 1), |p| (p.byte() as usize, p.line()));
+        let mut fields = Vec::with_capacity(record.len());
+        // 记录位置可能落在上一条 CRLF 的 `\n` 或空行上
+        let mut pos = start + data[start..].iter().take_while(|&&b| matches!(b, b'\r' | b'\n')).count();
+        for _ in 0..record.len() {
+            fields.push(pos);
+            if data.get(pos) == Some(&b'"') {
+                pos += 1;
+                while pos < data.len() {
+                    match (data[pos], data.get(pos + 1)) {
+                        (b'"', Some(b'"')) => pos += 2,
+                        (b'"', _) => {
+                            pos += 1;
+                            break;
+                        }
+                        _ => pos += 1,
+                    }
+                }
+            }
+            while pos < data.len() && !matches!(data[pos], b'\r' | b'\n') && data[pos] != dialect.delimiter {
+                pos += 1;
+            }
+            pos += 1;
+        }
+        Self { data, fields, tracker: LineTracker::at(data, start, line) }
+    }
+
+    /// 第 `field` 个字段内偏移 `pos` 在 `data` 中的位置
+    fn offset(&self, field: usize, pos: usize) -> usize {
+        let start = self.fields[field];
+        if self.data.get(start) != Some(&b'"') {
+            return start + pos;
+        }
+        // 引号字段：跳过开引号，`""` 在单元格中只占一个字节
+        let mut at = start + 1;
+        for _ in 0..pos {
+            at += if self.data[at..].starts_with(b"\"\"") { 2 } else { 1 };
+        }
+        at
+    }
+
+    /// 按递增顺序查询行列
+    fn locate(&mut self, pos: usize) -> (u64, u64) {
+        self.tracker.locate(pos)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::file_options::ColumnPolicy;
+    use crate::core::rules::Rule;
+
+    fn engine() -> HybridEngine {
+        let rule = |name: &str, pattern: &str, mask: &str| Rule {
+            name: name.into(),
+            pattern: pattern.into(),
+            mask: mask.into(),
+            priority: 10,
+            enabled: true,
+            is_custom: false,
+            case_insensitive: false,
+            whole_word: false,
+            unicode_boundary: false,
+            group: String::new(),
+        };
+        HybridEngine::from_rules(vec![
+            rule("email", r"[a-z0-9.]+@[a-z0-9.]+\.[a-z]+", "<EMAIL>"),
+            rule("staff", r"EMP-\d{4}", "<STAFF>"),
+        ])
+    }
+
+    fn mask(input: &str, default_delimiter: u8, options: &CsvOptions) -> (String, CsvStats) {
+        let engine = engine();
+        let dialect = Dialect::sniff(input.as_bytes(), default_delimiter, &engine, options);
+        let mut out = Vec::new();
+        let stats = mask_csv(input.as_bytes(), &dialect, &engine, options, &AtomicBool::new(false), |_| {}, |bytes| {
+            out.extend_from_slice(bytes);
+            Ok(())
+        })
+        .unwrap();
+        (String::from_utf8(out).unwrap(), stats)
+    }
+
+    #[test]
+    fn test_sniff_dialect() {
+        let engine = engine();
+        let options = CsvOptions::default();
+        let tsv = "name\tnote\r\nalice\t\"a, b\"\r\n";
+        let dialect = Dialect::sniff(tsv.as_bytes(), b',', &engine, &options);
+        assert_eq!(dialect.delimiter, b'\t');
+        assert!(dialect.has_headers && dialect.crlf && !dialect.quote_all);
+
+        let quoted = "\"1\";\"x@a.com\"\n\"2\";\"y@b.com\"\n";
+        let dialect = Dialect::sniff(quoted.as_bytes(), b',', &engine, &options);
+        assert_eq!(dialect.delimiter, b';');
+        assert!(dialect.quote_all && !dialect.has_headers);
+    }
+
+    #[test]
+    fn test_quoted_fields_survive_masking() {
+        let input = "id,contact,note\n1,\"Bob, a@x.com\",\"line1\nline2 \"\"quoted\"\"\"\n2,b@y.org,plain";
+        let (out, stats) = mask(input, b',', &CsvOptions::default());
+        assert_eq!(out, "id,contact,note\n1,\"Bob, <EMAIL>\",\"line1\nline2 \"\"quoted\"\"\"\n2,<EMAIL>,plain");
+        assert_eq!(stats.lines, 4);
+        let positions: Vec<_> = stats.entities.iter().map(|e| (e.line, e.column, e.field)).collect();
+        assert_eq!(positions, vec![(Some(2), Some(9), Some(2)), (Some(4), Some(3), Some(2))]);
+        let values: Vec<_> = stats.entities.iter().map(|e| &input[e.start..e.end]).collect();
+        assert_eq!(values, vec!["a@x.com", "b@y.org"]);
+    }
+
+    #[test]
+    fn test_unmatched_records_copied_verbatim() {
+        let input = "\"id\",\"note\"\r\n\"1\",\"plain\"\r\n\n\"2\",\"a@x.com\"\r\n3,\"x\"";
+        let (out, stats) = mask(input, b',', &CsvOptions::default());
+        assert_eq!(out, "\"id\",\"note\"\r\n\"1\",\"plain\"\r\n\n2,<EMAIL>\r\n3,\"x\"");
+        assert_eq!(stats.lines, 5);
+        let entity = &stats.entities[0];
+        assert_eq!((&input[entity.start..entity.end], entity.line, entity.column), ("a@x.com", Some(4), Some(6)));
+    }
+
+    #[test]
+    fn test_entity_offsets_skip_escaped_quotes() {
+        let input = "note\n\"说 \"\"hi\"\" c@z.com\"\n";
+        let (_, stats) = mask(input, b',', &CsvOptions::default());
+        let entity = &stats.entities[0];
+        assert_eq!(&input[entity.start..entity.end], "c@z.com");
+        assert_eq!((entity.line, entity.column, entity.field), (Some(2), Some(11), Some(1)));
+    }
+
+    #[test]
+    fn test_column_policies() {
+        let options = CsvOptions {
+            policies: vec![
+                ColumnPolicy {
+                    column: ColumnRef::Name("Phone".into()),
+                    action: ColumnAction::Mask,
+                    strategy: Some("redact".into()),
+                    entity_type: Some("phone".into()),
+                },
+                ColumnPolicy { column: ColumnRef::Name("order_id".into()), action: ColumnAction::Skip, strategy: None, entity_type: None },
+                ColumnPolicy { column: ColumnRef::Index(4), action: ColumnAction::Mask, strategy: None, entity_type: None },
+            ],
+            ..CsvOptions::default()
+        };
+        let input = "order_id,phone,email,城市\nEMP-0001,13800001111,a@x.com,杭州\n";
+        let (out, _) = mask(input, b',', &options);
+        let masked_phone = engine().masking_engine().mask_with(
+            &MaskStrategyType::Redact,
+            "13800001111",
+            &column_span(EntityType::Phone),
+        );
+        assert_eq!(out, format!("order_id,phone,email,城市\nEMP-0001,{},<EMAIL>,<城市>\n", masked_phone));
+    }
+
+    #[test]
+    fn test_column_inference_masks_whole_column() {
+        // staff 列 5 个样本中 4 个可识别；推断为整列后识别器漏掉的 `emp 9` 也会被替换
+        let input = "staff,city\nEMP-0001,Paris\nEMP-0002,Rome\nEMP-0003,Oslo\nEMP-0004,Lima\nemp 9,Bern\n";
+        let (out, _) = mask(input, b',', &CsvOptions::default());
+        assert_eq!(out, "staff,city\n<STAFF>,Paris\n<STAFF>,Rome\n<STAFF>,Oslo\n<STAFF>,Lima\n<STAFF>,Bern\n");
+
+        let (out, _) = mask(input, b',', &CsvOptions { infer_columns: false, ..CsvOptions::default() });
+        assert!(out.contains("emp 9,Bern"));
+    }
+
+    #[test]
+    fn test_scan_reports_columns() {
+        let engine = engine();
+        let options = CsvOptions::default();
+        let input = "name\temail\nalice\ta@x.com\n";
+        let dialect = Dialect::sniff(input.as_bytes(), b'\t', &engine, &options);
+        let report = scan_csv(input.as_bytes(), &dialect, "a.tsv", &engine, &options, 10).unwrap();
+        assert_eq!((report.total_findings, report.lines_scanned), (1, 2));
+        let finding = &report.samples[0];
+        assert_eq!((finding.location.as_deref(), finding.line, finding.column), (Some("email"), 2, 7));
+        assert_eq!(finding.offset as usize, input.find("a@x.com").unwrap());
+    }
+}
@@ -0,0 +1,291 @@
+//! 文本编码检测与转码
+//!
+//! 识别引擎只分析 UTF-8 文本。纯文本文件在进入脱敏流水线前先检测编码：
+//!
+//! 1. BOM：`EF BB BF`（UTF-8）、`FF FE`（UTF-16LE）、`FE FF`（UTF-16BE）
+//! 2. 无 BOM 时按前 64KB 采样推断：奇 / 偶字节大量为 0 → UTF-16；合法 UTF-8 → UTF-8；
+//!    合法 GBK → GBK；其余按 Latin-1（Windows-1252 超集）处理，任意字节都可无损往返
+//!
+//! 非 UTF-8 文本整体解码为 UTF-8 供分析，并记录稀疏的偏移映射（每约 4KB 在行首打一个
+//! 检查点，检查点之间按原编码的字符宽度推算），以便把实体位置换算回原文件字节偏移。
+//! UTF-8 输入直接借用原始字节，不做任何拷贝。
+
+use crate::core::recognizer::TextEncoding;
+use encoding_rs::{DecoderResult, GB18030, GBK, UTF_16BE, UTF_16LE, WINDOWS_1252};
+use std::borrow::Cow;
+
+/// 无 BOM 时用于推断编码的采样长度
+const SNIFF_LEN: usize = 64 * 1024;
+/// 偏移映射检查点间隔（UTF-8 字节）
+const CHECKPOINT_INTERVAL: usize = 4096;
+
+/// 检测编码，返回 `(编码, BOM 长度)`
+pub fn detect_encoding(raw: &[u8]) -> (TextEncoding, usize) {
+    if raw.starts_with(&[0xEF, 0xBB, 0xBF]) {
+        return (TextEncoding::Utf8, 3);
+    }
+    if raw.starts_with(&[0xFF, 0xFE]) {
+        return (TextEncoding::Utf16Le, 2);
+    }
+    if raw.starts_with(&[0xFE, 0xFF]) {
+        return (TextEncoding::Utf16Be, 2);
+    }
+
+    let sample = &raw[..raw.len().min(SNIFF_LEN)];
+    if let Some(encoding) = sniff_utf16(sample) {
+        return (encoding, 0);
+    }
+    match std::str::from_utf8(sample) {
+        Ok(_) => return (TextEncoding::Utf8, 0),
+        // 采样恰好截断在多字节字符中间
+        Err(e) if e.error_len().is_none() => return (TextEncoding::Utf8, 0),
+        Err(_) => {}
+    }
+
+    let mut decoder = GBK.new_decoder_without_bom_handling();
+    let capacity = decoder
+        .max_utf8_buffer_length_without_replacement(sample.len())
+        .unwrap_or(sample.len() * 3);
+    let mut out = String::with_capacity(capacity);
+    let (result, _) = decoder.decode_to_string_without_replacement(sample, &mut out, sample.len() == raw.len());
+    if matches!(result, DecoderResult::InputEmpty) {
+        (TextEncoding::Gbk, 0)
+    } else {
+        (TextEncoding::Latin1, 0)
+    }
+}
+
+/// 无 BOM 的 UTF-16：以 ASCII 为主的文本每个码元都有一个 0 字节
+fn sniff_utf16(sample: &[u8]) -> Option<TextEncoding> {
+    let pairs = sample.len() / 2;
+    if pairs < 2 {
+        return None;
+    }
+    let (mut even, mut odd) = (0usize, 0usize);
+    for pair in sample.chunks_exact(2) {
+        even += usize::from(pair[0] == 0);
+        odd += usize::from(pair[1] == 0);
+    }
+    if odd * 10 >= pairs * 3 && even * 20 < pairs {
+        Some(TextEncoding::Utf16Le)
+    } else if even * 10 >= pairs * 3 && odd * 20 < pairs {
+        Some(TextEncoding::Utf16Be)
+    } else {
+        None
+    }
+}
+
+/// 解码后的文本
+pub struct DecodedText<'a> {
+    raw: &'a [u8],
+    /// 原始编码
+    pub encoding: TextEncoding,
+    /// 原始 BOM 长度（UTF-8 输入的 BOM 保留在 `text` 中）
+    pub bom_len: usize,
+    /// 供分析的 UTF-8 文本
+    pub text: Cow<'a, [u8]>,
+    /// 偏移映射检查点：(UTF-8 偏移, 原始字节偏移)，均位于行首
+    checkpoints: Vec<(usize, usize)>,
+}
+
+impl<'a> DecodedText<'a> {
+    /// 检测编码并解码为 UTF-8
+    ///
+    /// 严格解码失败时回退：GBK → Latin-1；UTF-16 → 按 UTF-8 原样处理（与未检测时行为一致）。
+    pub fn decode(raw: &'a [u8]) -> Self {
+        let (encoding, bom_len) = detect_encoding(raw);
+        let body = &raw[bom_len..];
+        let latin1 = || Some((TextEncoding::Latin1, WINDOWS_1252.decode_without_bom_handling(body).0));
+        let decoded = match encoding {
+            TextEncoding::Utf8 => None,
+            TextEncoding::Gbk => GBK
+                .decode_without_bom_handling_and_without_replacement(body)
+                .map(|text| (TextEncoding::Gbk, text))
+                .or_else(latin1),
+            TextEncoding::Latin1 => latin1(),
+            TextEncoding::Utf16Le => UTF_16LE
+                .decode_without_bom_handling_and_without_replacement(body)
+                .map(|text| (TextEncoding::Utf16Le, text)),
+            TextEncoding::Utf16Be => UTF_16BE
+                .decode_without_bom_handling_and_without_replacement(body)
+                .map(|text| (TextEncoding::Utf16Be, text)),
+        };
+
+        match decoded {
+            Some((encoding, text)) => {
+                let text = text.into_owned().into_bytes();
+                let checkpoints = build_checkpoints(encoding, body, &text, bom_len);
+                Self {
+                    raw,
+                    encoding,
+                    bom_len,
+                    text: Cow::Owned(text),
+                    checkpoints,
+                }
+            }
+            None => Self {
+                raw,
+                encoding: TextEncoding::Utf8,
+                bom_len: if encoding == TextEncoding::Utf8 { bom_len } else { 0 },
+                text: Cow::Borrowed(raw),
+                checkpoints: Vec::new(),
+            },
+        }
+    }
+
+    /// 是否经过转码（非 UTF-8 输入）
+    pub fn is_transcoded(&self) -> bool {
+        self.encoding != TextEncoding::Utf8
+    }
+
+    /// 原始 BOM 字节
+    pub fn bom(&self) -> &'a [u8] {
+        &self.raw[..self.bom_len]
+    }
+
+    /// UTF-8 偏移 → 原文件字节偏移（`pos` 需位于字符边界）
+    pub fn to_original(&self, pos: usize) -> usize {
+        if !self.is_transcoded() {
+            return pos;
+        }
+        let pos = pos.min(self.text.len());
+        let idx = self.checkpoints.partition_point(|&(u, _)| u <= pos).saturating_sub(1);
+        let (mut u, mut o) = self.checkpoints[idx];
+        while u < pos {
+            let char_len = utf8_char_len(self.text[u]);
+            o += match self.encoding {
+                TextEncoding::Latin1 => 1,
+                // 4 字节 UTF-8 字符为增补平面字符，UTF-16 中为代理对
+                TextEncoding::Utf16Le | TextEncoding::Utf16Be => {
+                    if char_len == 4 { 4 } else { 2 }
+                }
+                TextEncoding::Gbk => gbk_char_len(&self.raw[o..]),
+                TextEncoding::Utf8 => char_len,
+            };
+            u += char_len;
+        }
+        o
+    }
+}
+
+/// 在 UTF-8 文本与原始字节的同一行首处打检查点（两侧换行一一对应）
+fn build_checkpoints(encoding: TextEncoding, body: &[u8], text: &[u8], bom_len: usize) -> Vec<(usize, usize)> {
+    let (mut newlines, unit): (Box<dyn Iterator<Item = usize>>, usize) = match encoding {
+        TextEncoding::Utf16Le => (
+            Box::new(memchr::memchr_iter(b'\n', body).filter(|&i| i % 2 == 0 && body.get(i + 1) == Some(&0))),
+            2,
+        ),
+        TextEncoding::Utf16Be => (
+            Box::new(memchr::memchr_iter(b'\n', body).filter(|&i| i % 2 == 1 && body[i - 1] == 0).map(|i| i - 1)),
+            2,
+        ),
+        _ => (Box::new(memchr::memchr_iter(b'\n', body)), 1),
+    };
+
+    let mut checkpoints = vec![(0, bom_len)];
+    let mut last = 0;
+    for pos in memchr::memchr_iter(b'\n', text) {
+        let Some(orig) = newlines.next() else {
+            break;
+        };
+        if pos + 1 - last >= CHECKPOINT_INTERVAL {
+            checkpoints.push((pos + 1, bom_len + orig + unit));
+            last = pos + 1;
+        }
+    }
+    checkpoints
+}
+
+fn utf8_char_len(lead: u8) -> usize {
+    match lead {
+        0xF0.. => 4,
+        0xE0.. => 3,
+        0xC0.. => 2,
+        _ => 1,
+    }
+}
+
+/// GBK（GB18030）单个字符的字节数（输入已通过严格解码）
+fn gbk_char_len(bytes: &[u8]) -> usize {
+    match bytes {
+        [0x81..=0xFE, 0x30..=0x39, ..] => 4,
+        [0x81..=0xFE, ..] => 2,
+        _ => 1,
+    }
+}
+
+/// UTF-8 文本编码为目标编码（GBK 文本按 GB18030 写回，可表示全部 Unicode 字符；Latin-1 无法表示的字符按 HTML 数字引用输出）
+pub fn encode_text(text: &[u8], encoding: TextEncoding) -> Cow<'_, [u8]> {
+    if encoding == TextEncoding::Utf8 {
+        return Cow::Borrowed(text);
+    }
+    let text = String::from_utf8_lossy(text);
+    match encoding {
+        TextEncoding::Gbk => Cow::Owned(GB18030.encode(&text).0.into_owned()),
+        TextEncoding::Latin1 => Cow::Owned(WINDOWS_1252.encode(&text).0.into_owned()),
+        TextEncoding::Utf16Le => Cow::Owned(text.encode_utf16().flat_map(u16::to_le_bytes).collect()),
+        TextEncoding::Utf16Be => Cow::Owned(text.encode_utf16().flat_map(u16::to_be_bytes).collect()),
+        TextEncoding::Utf8 => unreachable!(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn utf16le(text: &str, bom: bool) -> Vec<u8> {
+        let mut out = if bom { vec![0xFF, 0xFE] } else { Vec::new() };
+        out.extend(text.encode_utf16().flat_map(u16::to_le_bytes));
+        out
+    }
+
+    #[test]
+    fn test_detects_bom_and_heuristics() {
+        assert_eq!(detect_encoding(b"\xEF\xBB\xBFabc"), (TextEncoding::Utf8, 3));
+        assert_eq!(detect_encoding(&utf16le("abc", true)), (TextEncoding::Utf16Le, 2));
+        assert_eq!(detect_encoding(b"\xFE\xFF\x00a"), (TextEncoding::Utf16Be, 2));
+        assert_eq!(detect_encoding(&utf16le("user=admin\r\n", false)).0, TextEncoding::Utf16Le);
+        assert_eq!(detect_encoding("用户 张三".as_bytes()).0, TextEncoding::Utf8);
+        assert_eq!(detect_encoding(&GBK.encode("用户 张三 电话").0).0, TextEncoding::Gbk);
+        assert_eq!(detect_encoding(b"caf\xE9 cr\xE8me").0, TextEncoding::Latin1);
+    }
+
+    #[test]
+    fn test_offsets_map_back_to_original_bytes() {
+        let mut line = String::new();
+        for i in 0..400 {
+            line.push_str(&format!("第{}行 😀 a@b.cn\n", i));
+        }
+        for (encoding, raw) in [
+            (TextEncoding::Gbk, GB18030.encode(&line).0.into_owned()),
+            (TextEncoding::Utf16Le, utf16le(&line, true)),
+        ] {
+            let decoded = DecodedText::decode(&raw);
+            assert_eq!(decoded.encoding, encoding);
+            let text = std::str::from_utf8(&decoded.text).unwrap();
+            // 检查点之间逐字符推算，最后一处邮箱的原始偏移应与重新编码前缀的长度一致
+            let pos = text.rfind("a@b.cn").unwrap();
+            let prefix = encode_text(&text.as_bytes()[..pos], encoding).len() + decoded.bom_len;
+            assert_eq!(decoded.to_original(pos), prefix);
+            assert!(decoded.checkpoints.len() > 1);
+            // 四字节字符（😀）完整往返
+            assert_eq!(text, line);
+            assert_eq!(&encode_text(&decoded.text, encoding)[..], &raw[decoded.bom_len..]);
+        }
+    }
+
+    #[test]
+    fn test_round_trip_and_fallbacks() {
+        let latin1 = b"caf\xE9 \x81\x8D\xFF\n";
+        let decoded = DecodedText::decode(latin1);
+        assert_eq!(encode_text(&decoded.text, decoded.encoding).as_ref(), latin1);
+        assert_eq!(decoded.to_original(decoded.text.len()), latin1.len());
+
+        let utf8 = "plain ascii".as_bytes();
+        let decoded = DecodedText::decode(utf8);
+        assert!(!decoded.is_transcoded());
+        assert!(matches!(decoded.text, Cow::Borrowed(_)));
+
+        assert_eq!(encode_text("<EMAIL> 张".as_bytes(), TextEncoding::Utf16Be).as_ref(), b"\x00<\x00E\x00M\x00A\x00I\x00L\x00>\x00 \x5F\x20");
+    }
+}
@@ -0,0 +1,721 @@
+//! 键值配置文件脱敏：`.env` / `.properties` / `.ini` / TOML
+//!
+//! 按格式切出每个值所在的字节区间，只替换值本身，键名、注释、引号、空白与顺序原样保留：
+//! - 键名命中敏感键模式（如 `*_PASSWORD`、`*_SECRET`、`*_TOKEN`、`*_KEY`、`DSN`）的值整体脱敏，
+//!   键名匹配前转大写并把 `.` `-` 视为 `_`（`spring.datasource.password` 命中 `*_PASSWORD`）
+//! - 其余值、注释行与无法解析的行照常识别
+//! - TOML 只替换字符串内容；需要替换的数字 / 布尔值改写为带引号的掩码，保证输出仍是合法 TOML
+//!
+//! 剪贴板文本被识别为 `.env` 内容时走同一套逻辑（见 [`mask_clipboard_text`]）。
+
+use crate::common::state::EntitySpanBrief;
+use crate::core::file_options::KeyValueOptions;
+use crate::core::hybrid_engine::HybridEngine;
+use crate::core::recognizer::{EntitySpan, EntityType};
+use crate::infra::fs::processor::{count_lines, span_to_finding, LineTracker};
+use crate::infra::fs::scan_report::ScanReport;
+use globset::{Glob, GlobSet, GlobSetBuilder};
+use rayon::prelude::*;
+use std::borrow::Cow;
+use std::ops::Range;
+use std::path::Path;
+
+/// 键值配置格式
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum KeyValueFormat {
+    Env,
+    Properties,
+    Ini,
+    Toml,
+}
+
+impl KeyValueFormat {
+    /// 按文件名识别：`.env` / `.env.*` / `*.env`、`*.properties`、`*.ini` / `*.cfg`、`*.toml`
+    pub fn from_path(path: &Path) -> Option<Self> {
+        let name = path.file_name()?.to_str()?.to_lowercase();
+        if name == ".env" || name.starts_with(".env.") {
+            return Some(Self::Env);
+        }
+        match name.rsplit_once('.')?.1 {
+            "env" => Some(Self::Env),
+            "properties" => Some(Self::Properties),
+            "ini" | "cfg" => Some(Self::Ini),
+            "toml" => Some(Self::Toml),
+            _ => None,
+        }
+    }
+}
+
+// ─────────────────────────────────────────────────────────────────────────────
+// 选项
+// ─────────────────────────────────────────────────────────────────────────────
+
+/// 编译后的敏感键模式
+struct SensitiveKeys(GlobSet);
+
+impl SensitiveKeys {
+    fn new(options: &KeyValueOptions) -> Self {
+        let mut builder = GlobSetBuilder::new();
+        for pattern in &options.sensitive_keys {
+            match Glob::new(&pattern.trim().to_uppercase()) {
+                Ok(glob) => {
+                    builder.add(glob);
+                }
+                Err(e) => log::warn!("⚠️ 忽略无效的敏感键模式 `{}`: {}", pattern, e),
+            }
+        }
+        Self(builder.build().unwrap_or_else(|_| GlobSet::empty()))
+    }
+
+    /// 键名命中时返回掩码使用的实体类型
+    fn entity_for(&self, key: &str) -> Option<EntityType> {
+        let normalized: String = key
+            .chars()
+            .map(|c| if c == '.' || c == '-' { '_' } else { c.to_ascii_uppercase() })
+            .collect();
+        if !self.0.is_match(&normalized) {
+            return None;
+        }
+        Some(if normalized.contains("PASSW") || normalized.ends_with("PWD") {
+            EntityType::Password
+        } else if normalized.ends_with("DSN") {
+            EntityType::Custom("DSN".to_string())
+        } else {
+            EntityType::ApiKey
+        })
+    }
+}
+
+// ─────────────────────────────────────────────────────────────────────────────
+// 解析
+// ─────────────────────────────────────────────────────────────────────────────
+
+/// 需要处理的区间
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Region {
+    /// 值（不含引号）；`bare` 表示 TOML 中不带引号的标量，替换后需补引号
+    Value { key: String, range: Range<usize>, bare: bool },
+    /// 注释或无法解析的行，照常识别
+    Text(Range<usize>),
+}
+
+impl Region {
+    fn range(&self) -> &Range<usize> {
+        match self {
+            Self::Value { range, .. } | Self::Text(range) => range,
+        }
+    }
+}
+
+fn parse(data: &[u8], format: KeyValueFormat) -> Vec<Region> {
+    match format {
+        KeyValueFormat::Env => parse_env(data),
+        KeyValueFormat::Properties => parse_properties(data),
+        KeyValueFormat::Ini => parse_ini(data),
+        KeyValueFormat::Toml => TomlScanner { data, pos: 0, regions: Vec::new() }.run(),
+    }
+}
+
+/// 行区间：（去掉 `\r` 的行尾, 下一行起点）
+fn line_bounds(data: &[u8], pos: usize) -> (usize, usize) {
+    let end = memchr::memchr(b'\n', &data[pos..]).map_or(data.len(), |i| pos + i);
+    let content_end = if end > pos && data[end - 1] == b'\r' { end - 1 } else { end };
+    (content_end, end + 1)
+}
+
+fn is_blank(b: u8) -> bool {
+    b == b' ' || b == b'\t' || b == b'\x0c'
+}
+
+fn skip_blank(data: &[u8], mut pos: usize, end: usize) -> usize {
+    while pos < end && is_blank(data[pos]) {
+        pos += 1;
+    }
+    pos
+}
+
+fn trim_end(data: &[u8], start: usize, mut end: usize) -> usize {
+    while end > start && is_blank(data[end - 1]) {
+        end -= 1;
+    }
+    end
+}
+
+fn text(data: &[u8], range: Range<usize>) -> String {
+    String::from_utf8_lossy(&data[range]).trim().to_string()
+}
+
+/// 行内注释起点：前面是空白的注释符
+fn inline_comment(data: &[u8], start: usize, end: usize, markers: &[u8]) -> usize {
+    (start..end)
+        .find(|&p| markers.contains(&data[p]) && p > 0 && is_blank(data[p - 1]))
+        .unwrap_or(end)
+}
+
+/// 闭合引号位置（`"` 内允许反斜杠转义；可跨行）
+fn closing_quote(data: &[u8], from: usize, quote: u8) -> Option<usize> {
+    let mut pos = from;
+    while pos < data.len() {
+        match data[pos] {
+            b'\\' if quote == b'"' => pos += 2,
+            b if b == quote => return Some(pos),
+            _ => pos += 1,
+        }
+    }
+    None
+}
+
+/// 行尾值与行内注释（未加引号的值在注释符前截断）
+fn push_unquoted(regions: &mut Vec<Region>, data: &[u8], key: String, start: usize, line_end: usize, markers: &[u8]) {
+    let comment = inline_comment(data, start, line_end, markers);
+    regions.push(Region::Value { key, range: start..trim_end(data, start, comment), bare: false });
+    if comment < line_end {
+        regions.push(Region::Text(comment..line_end));
+    }
+}
+
+/// `.env`：`[export ]KEY=VALUE`，引号值可跨行，`#` 注释
+fn parse_env(data: &[u8]) -> Vec<Region> {
+    let mut regions = Vec::new();
+    let mut pos = 0;
+    while pos < data.len() {
+        let (line_end, mut next) = line_bounds(data, pos);
+        let mut start = skip_blank(data, pos, line_end);
+        if data[start..line_end].starts_with(b"export ") {
+            start = skip_blank(data, start + 7, line_end);
+        }
+        let assignment = memchr::memchr(b'=', &data[start..line_end])
+            .map(|i| start + i)
+            .filter(|&eq| is_env_key(&data[start..trim_end(data, start, eq)]));
+
+        match assignment {
+            _ if start == line_end => {}
+            None => regions.push(Region::Text(start..line_end)),
+            Some(eq) => {
+                let key = text(data, start..eq);
+                let value = skip_blank(data, eq + 1, line_end);
+                let quote = data.get(value).copied().filter(|&q| value < line_end && matches!(q, b'"' | b'\'' | b'`'));
+                match quote.and_then(|q| closing_quote(data, value + 1, q)) {
+                    Some(close) => {
+                        regions.push(Region::Value { key, range: value + 1..close, bare: false });
+                        let (close_line_end, close_next) = line_bounds(data, close);
+                        let comment = inline_comment(data, close + 1, close_line_end, b"#");
+                        if comment < close_line_end {
+                            regions.push(Region::Text(comment..close_line_end));
+                        }
+                        next = close_next;
+                    }
+                    None => push_unquoted(&mut regions, data, key, value, line_end, b"#"),
+                }
+            }
+        }
+        pos = next;
+    }
+    regions
+}
+
+fn is_env_key(key: &[u8]) -> bool {
+    !key.is_empty() && key.iter().all(|&b| b.is_ascii_alphanumeric() || matches!(b, b'_' | b'.' | b'-'))
+}
+
+/// `.properties`：`key=value` / `key: value` / `key value`，行尾 `\` 续行，`#` `!` 注释行
+fn parse_properties(data: &[u8]) -> Vec<Region> {
+    let mut regions = Vec::new();
+    let mut pos = 0;
+    while pos < data.len() {
+        let (line_end, mut next) = line_bounds(data, pos);
+        let start = skip_blank(data, pos, line_end);
+        if start == line_end {
+            pos = next;
+            continue;
+        }
+        if matches!(data[start], b'#' | b'!') {
+            regions.push(Region::Text(start..line_end));
+            pos = next;
+            continue;
+        }
+
+        let mut key_end = start;
+        while key_end < line_end && !matches!(data[key_end], b'=' | b':') && !is_blank(data[key_end]) {
+            key_end += if data[key_end] == b'\\' { 2 } else { 1 };
+        }
+        let key_end = key_end.min(line_end);
+        let key = text(data, start..key_end).replace('\\', "");
+        let mut value = skip_blank(data, key_end, line_end);
+        if value < line_end && matches!(data[value], b'=' | b':') {
+            value = skip_blank(data, value + 1, line_end);
+        }
+
+        // 奇数个反斜杠结尾表示续行
+        let mut end = line_end;
+        while next < data.len() && data[value.min(end)..end].iter().rev().take_while(|&&b| b == b'\\').count() % 2 == 1 {
+            let (continued_end, continued_next) = line_bounds(data, next);
+            end = continued_end;
+            next = continued_next;
+        }
+        regions.push(Region::Value { key, range: value..end, bare: false });
+        pos = next;
+    }
+    regions
+}
+
+/// `.ini`：`[section]`、`key = value` / `key: value`，`;` `#` 注释
+fn parse_ini(data: &[u8]) -> Vec<Region> {
+    let mut regions = Vec::new();
+    let mut pos = 0;
+    while pos < data.len() {
+        let (line_end, next) = line_bounds(data, pos);
+        let start = skip_blank(data, pos, line_end);
+        let separator = data[start..line_end].iter().position(|&b| b == b'=' || b == b':').map(|i| start + i);
+        match data.get(start).copied().filter(|_| start < line_end) {
+            None | Some(b'[') => {}
+            Some(b';' | b'#') => regions.push(Region::Text(start..line_end)),
+            _ => match separator {
+                None => regions.push(Region::Text(start..line_end)),
+                Some(sep) => {
+                    let key = text(data, start..sep);
+                    let value = skip_blank(data, sep + 1, line_end);
+                    let quote = data.get(value).copied().filter(|&q| value < line_end && matches!(q, b'"' | b'\''));
+                    let close = quote.and_then(|q| memchr::memchr(q, &data[value + 1..line_end]).map(|i| value + 1 + i));
+                    match close {
+                        Some(close) => {
+                            regions.push(Region::Value { key, range: value + 1..close, bare: false });
+                            let comment = inline_comment(data, close + 1, line_end, b";#");
+                            if comment < line_end {
+                                regions.push(Region::Text(comment..line_end));
+                            }
+                        }
+                        None => push_unquoted(&mut regions, data, key, value, line_end, b";#"),
+                    }
+                }
+            },
+        }
+        pos = next;
+    }
+    regions
+}
+
+/// TOML：键值（含点号键、数组、内联表、多行字符串），表头原样保留，`#` 注释照常识别
+struct TomlScanner<'a> {
+    data: &'a [u8],
+    pos: usize,
+    regions: Vec<Region>,
+}
+
+impl TomlScanner<'_> {
+    fn run(mut self) -> Vec<Region> {
+        while self.pos < self.data.len() {
+            self.skip_space(true);
+            match self.peek() {
+                None => break,
+                Some(b'#') => self.comment(),
+                Some(b'[') => self.table_header(),
+                Some(_) => {
+                    let before = self.pos;
+                    self.key_value("");
+                    if self.pos == before {
+                        self.pos = line_bounds(self.data, self.pos).1;
+                    }
+                }
+            }
+        }
+        self.regions
+    }
+
+    fn peek(&self) -> Option<u8> {
+        self.data.get(self.pos).copied()
+    }
+
+    /// 跳过空白（`newlines` 为真时连同换行）
+    fn skip_space(&mut self, newlines: bool) {
+        while let Some(b) = self.peek() {
+            if is_blank(b) || (newlines && (b == b'\n' || b == b'\r')) {
+                self.pos += 1;
+            } else {
+                break;
+            }
+        }
+    }
+
+    fn comment(&mut self) {
+        let (line_end, _) = line_bounds(self.data, self.pos);
+        self.regions.push(Region::Text(self.pos..line_end));
+        self.pos = line_end;
+    }
+
+    /// 表头 `[a.b]` / `[[a]]`：表名原样保留，其后的行内注释按文本处理
+    fn table_header(&mut self) {
+        let (line_end, next) = line_bounds(self.data, self.pos);
+        let mut pos = self.pos;
+        while pos < line_end && self.data[pos] == b'[' {
+            pos += 1;
+        }
+        while pos < line_end {
+            match self.data[pos] {
+                q @ (b'"' | b'\'') => match closing_quote(self.data, pos + 1, q) {
+                    Some(close) if close < line_end => pos = close + 1,
+                    _ => pos = line_end,
+                },
+                b']' => break,
+                _ => pos += 1,
+            }
+        }
+        while pos < line_end && self.data[pos] == b']' {
+            pos += 1;
+        }
+        if pos < line_end {
+            self.regions.push(Region::Text(pos..line_end));
+        }
+        self.pos = next;
+    }
+
+    /// 跳过数组内的空白、换行与注释
+    fn skip_array_space(&mut self) {
+        loop {
+            self.skip_space(true);
+            if self.peek() == Some(b'#') {
+                self.comment();
+            } else {
+                break;
+            }
+        }
+    }
+
+    fn key_value(&mut self, prefix: &str) {
+        let key = self.key();
+        self.skip_space(false);
+        if key.is_empty() || self.peek() != Some(b'=') {
+            return;
+        }
+        self.pos += 1;
+        self.skip_space(false);
+        let key = if prefix.is_empty() { key } else { format!("{}.{}", prefix, key) };
+        self.value(&key);
+    }
+
+    /// 键名（点号键以 `.` 连接）
+    fn key(&mut self) -> String {
+        let mut parts = Vec::new();
+        loop {
+            self.skip_space(false);
+            match self.peek() {
+                Some(q @ (b'"' | b'\'')) => {
+                    let Some(close) = closing_quote(self.data, self.pos + 1, q) else {
+                        return String::new();
+                    };
+                    parts.push(String::from_utf8_lossy(&self.data[self.pos + 1..close]).into_owned());
+                    self.pos = close + 1;
+                }
+                _ => {
+                    let start = self.pos;
+                    while self.peek().is_some_and(|b| b.is_ascii_alphanumeric() || b == b'_' || b == b'-') {
+                        self.pos += 1;
+                    }
+                    if self.pos == start {
+                        return String::new();
+                    }
+                    parts.push(String::from_utf8_lossy(&self.data[start..self.pos]).into_owned());
+                }
+            }
+            self.skip_space(false);
+            if self.peek() != Some(b'.') {
+                return parts.join(".");
+            }
+            self.pos += 1;
+        }
+    }
+
+    fn value(&mut self, key: &str) {
+        match self.peek() {
+            Some(q @ (b'"' | b'\'')) => {
+                let triple = self.data[self.pos..].starts_with(&[q, q, q]);
+                let open = if triple { 3 } else { 1 };
+                let content = self.pos + open;
+                let close = if triple {
+                    find_triple(self.data, content, q)
+                } else {
+                    closing_quote(self.data, content, q)
+                };
+                let Some(close) = close else {
+                    self.pos = self.data.len();
+                    return;
+                };
+                self.regions.push(Region::Value { key: key.to_string(), range: content..close, bare: false });
+                self.pos = close + open;
+            }
+            Some(b'[') => {
+                self.pos += 1;
+                loop {
+                    self.skip_array_space();
+                    match self.peek() {
+                        None => break,
+                        Some(b']') => {
+                            self.pos += 1;
+                            break;
+                        }
+                        Some(b',') => self.pos += 1,
+                        Some(_) => {
+                            let before = self.pos;
+                            self.value(key);
+                            if self.pos == before {
+                                self.pos += 1;
+                            }
+                        }
+                    }
+                }
+            }
+            Some(b'{') => {
+                self.pos += 1;
+                loop {
+                    self.skip_space(false);
+                    match self.peek() {
+                        None | Some(b'\n') => break,
+                        Some(b'}') => {
+                            self.pos += 1;
+                            break;
+                        }
+                        Some(b',') => self.pos += 1,
+                        Some(_) => {
+                            let before = self.pos;
+                            self.key_value(key);
+                            if self.pos == before {
+                                self.pos += 1;
+                            }
+                        }
+                    }
+                }
+            }
+            Some(_) => {
+                let start = self.pos;
+                while self.peek().is_some_and(|b| !matches!(b, b',' | b']' | b'}' | b'#' | b'\n' | b'\r')) {
+                    self.pos += 1;
+                }
+                let end = trim_end(self.data, start, self.pos);
+                if end > start {
+                    self.regions.push(Region::Value { key: key.to_string(), range: start..end, bare: true });
+                }
+            }
+            None => {}
+        }
+    }
+}
+
+/// 多行字符串的结束引号（`"""` 内允许转义；连续多于三个引号时最后三个为结束符）
+fn find_triple(data: &[u8], from: usize, quote: u8) -> Option<usize> {
+    let mut pos = from;
+    while pos + 3 <= data.len() {
+        if quote == b'"' && data[pos] == b'\\' {
+            pos += 2;
+            continue;
+        }
+        if data[pos..pos + 3] == [quote; 3] {
+            let mut end = pos + 3;
+            while data.get(end) == Some(&quote) && end - pos < 5 {
+                end += 1;
+            }
+            return Some(end - 3);
+        }
+        pos += 1;
+    }
+    None
+}
+
+// ─────────────────────────────────────────────────────────────────────────────
+// 脱敏
+// ─────────────────────────────────────────────────────────────────────────────
+
+/// 区间内需要替换的实体（偏移相对区间）
+fn region_spans(engine: &HybridEngine, data: &[u8], region: &Region, sensitive: &SensitiveKeys) -> Vec<EntitySpan> {
+    let range = region.range().clone();
+    if range.is_empty() {
+        return Vec::new();
+    }
+    if let Region::Value { key, .. } = region
+        && let Some(entity_type) = sensitive.entity_for(key)
+    {
+        return vec![EntitySpan::new(0, range.len(), entity_type, 1.0, "key_value")];
+    }
+    engine.detect(&data[range]).into_iter().filter(|s| s.start < s.end).collect()
+}
+
+/// 按格式扫描键值配置文本：命中与脱敏时替换的实体一致（敏感键名的值整体计为一处，`location` 为键名），偏移相对 `data`
+pub fn scan_key_values(
+    data: &[u8],
+    format: KeyValueFormat,
+    file: &str,
+    engine: &HybridEngine,
+    options: &KeyValueOptions,
+    max_samples: usize,
+) -> ScanReport {
+    let sensitive = SensitiveKeys::new(options);
+    let regions = parse(data, format);
+    let hits: Vec<(&Region, Vec<EntitySpan>)> = regions
+        .par_iter()
+        .map(|region| (region, region_spans(engine, data, region, &sensitive)))
+        .filter(|(_, spans)| !spans.is_empty())
+        .collect();
+
+    let mut report = ScanReport::new(max_samples);
+    report.bytes_scanned = data.len() as u64;
+    report.lines_scanned = count_lines(data);
+    let mut tracker = LineTracker::new(data);
+    for (region, spans) in hits {
+        let offset = region.range().start;
+        for span in spans {
+            let (start, end) = (offset + span.start, offset + span.end);
+            let mut finding = span_to_finding(&EntitySpan { start, end, ..span }, data, file, engine);
+            (finding.line, finding.column) = tracker.locate(start);
+            if let Region::Value { key, .. } = region {
+                finding.location = Some(key.clone());
+            }
+            report.add_finding(finding);
+        }
+    }
+    report
+}
+
+/// 按格式脱敏键值配置文本，返回（脱敏结果, 实体）；实体偏移相对 `data`，行列号为实体起点
+pub fn mask_key_values(
+    data: &[u8],
+    format: KeyValueFormat,
+    engine: &HybridEngine,
+    options: &KeyValueOptions,
+) -> (Vec<u8>, Vec<EntitySpanBrief>) {
+    let sensitive = SensitiveKeys::new(options);
+    let regions = parse(data, format);
+    let edits: Vec<(&Region, Vec<EntitySpan>)> = regions
+        .par_iter()
+        .map(|region| (region, region_spans(engine, data, region, &sensitive)))
+        .filter(|(_, spans)| !spans.is_empty())
+        .collect();
+
+    let mut output = Vec::with_capacity(data.len());
+    let mut entities = Vec::new();
+    let mut tracker = LineTracker::new(data);
+    let mut last = 0;
+    for (region, spans) in edits {
+        let range = region.range().clone();
+        output.extend_from_slice(&data[last..range.start]);
+        let masked = engine.mask_spans(&data[range.clone()], &spans);
+        if matches!(region, Region::Value { bare: true, .. }) {
+            output.push(b'"');
+            output.extend_from_slice(&masked);
+            output.push(b'"');
+        } else {
+            output.extend_from_slice(&masked);
+        }
+        entities.extend(spans.iter().map(|span| {
+            let mut brief = engine.brief(span);
+            (brief.start, brief.end) = (range.start + span.start, range.start + span.end);
+            let (line, column) = tracker.locate(brief.start);
+            (brief.line, brief.column) = (Some(line), Some(column));
+            brief
+        }));
+        last = range.end;
+    }
+    output.extend_from_slice(&data[last..]);
+    (output, entities)
+}
+
+/// 文本是否像 `.env` 内容：至少两行 `KEY=VALUE`，且赋值行多于其他非注释行
+pub fn looks_like_env(text: &[u8]) -> bool {
+    let regions = parse_env(text);
+    let assignments = regions.iter().filter(|r| matches!(r, Region::Value { .. })).count();
+    let others = regions
+        .iter()
+        .filter(|r| matches!(r, Region::Text(range) if text[range.start] != b'#'))
+        .count();
+    assignments >= 2 && assignments > others
+}
+
+/// 剪贴板文本脱敏：识别为 `.env` 内容时按键值规则处理，否则按普通文本处理
+pub fn mask_clipboard_text<'a>(
+    engine: &HybridEngine,
+    options: &KeyValueOptions,
+    text: &'a [u8],
+) -> (Cow<'a, [u8]>, Vec<EntitySpanBrief>) {
+    if looks_like_env(text) {
+        let (masked, entities) = mask_key_values(text, KeyValueFormat::Env, engine, options);
+        return (Cow::Owned(masked), entities);
+    }
+    engine.mask_line_with_entities(text)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::rules::Rule;
+
+    fn engine() -> HybridEngine {
+        HybridEngine::from_rules(vec![Rule {
+            name: "email".into(),
+            pattern: r"[a-z0-9.]+@[a-z0-9.]+\.[a-z]+".into(),
+            mask: "<EMAIL>".into(),
+            priority: 10,
+            enabled: true,
+            is_custom: false,
+            case_insensitive: false,
+            whole_word: false,
+            unicode_boundary: false,
+            group: String::new(),
+        }])
+    }
+
+    fn mask(input: &str, format: KeyValueFormat) -> String {
+        let (out, _) = mask_key_values(input.as_bytes(), format, &engine(), &KeyValueOptions::default());
+        String::from_utf8(out).unwrap()
+    }
+
+    #[test]
+    fn test_env_preserves_quotes_and_comments() {
+        let input = "# owner: a@x.com\nexport DB_PASSWORD=\"p@ss \\\"word\\\"\"  # rotate\nAPI_KEY='abc'\nDSN=postgres://u:p@h/db\nNOTIFY=b@y.org # ops\nMULTI_SECRET=\"line1\nline2\"\nPLAIN=hello\r\n";
+        assert_eq!(
+            mask(input, KeyValueFormat::Env),
+            "# owner: <EMAIL>\nexport DB_PASSWORD=\"<PASSWORD>\"  # rotate\nAPI_KEY='<API_KEY>'\nDSN=<DSN>\nNOTIFY=<EMAIL> # ops\nMULTI_SECRET=\"<API_KEY>\"\nPLAIN=hello\r\n"
+        );
+    }
+
+    #[test]
+    fn test_properties_and_ini() {
+        let input = "! comment\nspring.datasource.password = s3cr\\\n    et\nmail.from: a@x.com\nserver.port 8080\n";
+        assert_eq!(
+            mask(input, KeyValueFormat::Properties),
+            "! comment\nspring.datasource.password = <PASSWORD>\nmail.from: <EMAIL>\nserver.port 8080\n"
+        );
+
+        let input = "[db]\nuser = a@x.com ; owner\npassword = \"hunter2\"\n; b@y.org\n";
+        assert_eq!(mask(input, KeyValueFormat::Ini), "[db]\nuser = <EMAIL> ; owner\npassword = \"<PASSWORD>\"\n; <EMAIL>\n");
+    }
+
+    #[test]
+    fn test_toml_keeps_valid_syntax() {
+        let input = "title = \"a@x.com\"\n[server] # owner a@x.com\n[[\"a]b\".c]] # b@y.org\nauth_token = '''\nmulti\n'''\nport = 8080 # b@y.org\nredis = { host = \"h\", password = 1234 }\nadmins = [\n  \"c@z.io\", # lead\n  \"d\",\n]\n";
+        assert_eq!(
+            mask(input, KeyValueFormat::Toml),
+            "title = \"<EMAIL>\"\n[server] # owner <EMAIL>\n[[\"a]b\".c]] # <EMAIL>\nauth_token = '''<API_KEY>'''\nport = 8080 # <EMAIL>\nredis = { host = \"h\", password = \"<PASSWORD>\" }\nadmins = [\n  \"<EMAIL>\", # lead\n  \"d\",\n]\n"
+        );
+    }
+
+    #[test]
+    fn test_clipboard_env_detection() {
+        let engine = engine();
+        let env = "APP_NAME=demo\nGITHUB_TOKEN=ghp_x\n";
+        assert!(looks_like_env(env.as_bytes()));
+        let (masked, entities) = mask_clipboard_text(&engine, &KeyValueOptions::default(), env.as_bytes());
+        assert_eq!(&*masked, b"APP_NAME=demo\nGITHUB_TOKEN=<API_KEY>\n");
+        assert_eq!((entities[0].line, entities[0].column), (Some(2), Some(14)));
+        assert_eq!(&env[entities[0].start..entities[0].end], "ghp_x");
+
+        assert!(!looks_like_env(b"see a=b in the docs\nand also this line\nplus another"));
+        assert!(!looks_like_env(b"ONLY=one"));
+    }
+
+    #[test]
+    fn test_format_from_path() {
+        assert_eq!(KeyValueFormat::from_path(Path::new("/app/.env")), Some(KeyValueFormat::Env));
+        assert_eq!(KeyValueFormat::from_path(Path::new(".env.production")), Some(KeyValueFormat::Env));
+        assert_eq!(KeyValueFormat::from_path(Path::new("application.properties")), Some(KeyValueFormat::Properties));
+        assert_eq!(KeyValueFormat::from_path(Path::new("Cargo.toml")), Some(KeyValueFormat::Toml));
+        assert_eq!(KeyValueFormat::from_path(Path::new("notes.txt")), None);
+    }
+}
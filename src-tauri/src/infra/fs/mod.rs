@@ -1 +1,8 @@
-pub mod processor;
\ No newline at end of file
+pub mod processor;
+pub mod batch;
+pub mod delimited;
+pub mod encoding;
+pub mod key_value;
+pub mod scan_report;
+pub mod sarif;
+pub mod structured;
@@ -0,0 +1,402 @@
+//! SARIF 2.1.0 导出
+//!
+//! 将扫描报告转换为 SARIF 日志，供安全平台（代码扫描、SIEM 等）直接导入：
+//!
+//! - `tool.driver.rules` 来自规则配置（名称、所属组、优先级、掩码）；内置识别器没有
+//!   对应 [`Rule`] 时只输出 ID
+//! - 位置：`region` 给出行 / 列（按 Unicode 码点计，见 `columnKind`）及字节偏移与长度；
+//!   Excel 命中没有字节偏移，工作表名写入 `logicalLocations`
+//! - 目录扫描使用相对路径 + `SRCROOT` 基准，单文件扫描使用绝对 `file://` URI
+//! - `partialFingerprints` 为本机指纹密钥对规则、文件 URI、命中原文摘要及同值出现序号的 HMAC-SHA256，
+//!   同一内容在本机重复扫描得到相同指纹，便于平台去重；密钥随安装随机生成、不随报告导出，
+//!   拿到报告也无法穷举手机号等低熵原文来比对指纹
+//!
+//! 结果包含报告中的全部命中（样本与超出样本上限的 `overflow`），不受界面样本上限约束。
+
+use super::scan_report::{Finding, ScanReport};
+use crate::core::rules::Rule;
+use hmac::{Hmac, Mac};
+use serde_json::{json, Value};
+use sha2::Sha256;
+use std::collections::HashMap;
+use std::fs;
+use std::path::Path;
+
+type HmacSha256 = Hmac<Sha256>;
+
+const SARIF_SCHEMA: &str = "https://json.schemastore.org/sarif-2.1.0.json";
+const SARIF_VERSION: &str = "2.1.0";
+/// 目录扫描根目录的 URI 基准名
+const SRCROOT: &str = "SRCROOT";
+/// 指纹名称（算法变化时递增版本号）
+const FINGERPRINT_KEY: &str = "safemask/v2";
+/// 指纹密钥长度（字节）
+const FINGERPRINT_KEY_LEN: usize = 32;
+
+/// 读取或创建本机指纹密钥（随机 32 字节，十六进制持久化到 `storage_dir/fingerprint_key`）
+pub fn load_or_create_fingerprint_key(storage_dir: &Path) -> Vec<u8> {
+    let path = storage_dir.join("fingerprint_key");
+    if let Some(key) = fs::read_to_string(&path).ok().and_then(|hex| decode_hex(hex.trim()))
+        && key.len() == FINGERPRINT_KEY_LEN
+    {
+        return key;
+    }
+    let key: [u8; FINGERPRINT_KEY_LEN] = rand::random();
+    let hex: String = key.iter().map(|b| format!("{:02x}", b)).collect();
+    if let Err(e) = fs::write(&path, hex) {
+        log::warn!("⚠️ 无法保存指纹密钥，本次运行使用临时密钥: {}", e);
+    }
+    key.to_vec()
+}
+
+fn decode_hex(hex: &str) -> Option<Vec<u8>> {
+    if !hex.len().is_multiple_of(2) {
+        return None;
+    }
+    (0..hex.len())
+        .step_by(2)
+        .map(|i| u8::from_str_radix(hex.get(i..i + 2)?, 16).ok())
+        .collect()
+}
+
+/// 生成 SARIF 2.1.0 日志（`fingerprint_key` 为本机指纹密钥，见 [`load_or_create_fingerprint_key`]）
+pub fn to_sarif(report: &ScanReport, rules: &[Rule], fingerprint_key: &[u8]) -> Value {
+    let root = report.root.as_deref().map(Path::new);
+    let by_name: HashMap<&str, &Rule> = rules.iter().map(|r| (r.name.as_str(), r)).collect();
+
+    // 规则按首次出现顺序编号
+    let mut rule_index: HashMap<&str, usize> = HashMap::new();
+    let mut driver_rules = Vec::new();
+    for finding in report.findings() {
+        if rule_index.contains_key(finding.rule.as_str()) {
+            continue;
+        }
+        rule_index.insert(&finding.rule, driver_rules.len());
+        driver_rules.push(rule_descriptor(finding, by_name.get(finding.rule.as_str()).copied()));
+    }
+
+    let mut artifact_index: HashMap<&str, usize> = HashMap::new();
+    let mut artifacts = Vec::new();
+    for file in report.files.iter().chain(report.findings().map(|f| &f.file)) {
+        if artifact_index.contains_key(file.as_str()) {
+            continue;
+        }
+        artifact_index.insert(file, artifacts.len());
+        artifacts.push(json!({ "location": artifact_location(file, root) }));
+    }
+
+    // 同一文件、同一规则、同一原文的第 n 次出现
+    let mut occurrences: HashMap<(&str, &str, &str), usize> = HashMap::new();
+    let results: Vec<Value> = report
+        .findings()
+        .map(|finding| {
+            let mut location = artifact_location(&finding.file, root);
+            location["index"] = json!(artifact_index[finding.file.as_str()]);
+
+            let occurrence = occurrences
+                .entry((&finding.file, &finding.rule, &finding.value_hash))
+                .or_insert(0);
+            let uri = location["uri"].as_str().unwrap_or_default();
+            let fingerprint = fingerprint(fingerprint_key, finding, uri, *occurrence);
+            *occurrence += 1;
+
+            let mut sarif_location = json!({
+                "physicalLocation": {
+                    "artifactLocation": location,
+                    "region": region(finding),
+                }
+            });
+            if let Some(sheet) = &finding.location {
+                sarif_location["logicalLocations"] = json!([{ "name": sheet }]);
+            }
+
+            json!({
+                "ruleId": finding.rule,
+                "ruleIndex": rule_index[finding.rule.as_str()],
+                "level": "warning",
+                "message": {
+                    "text": format!("检测到 {}（{}）：{}", finding.entity_type, finding.rule, finding.preview),
+                },
+                "locations": [sarif_location],
+                "partialFingerprints": { FINGERPRINT_KEY: fingerprint },
+                "properties": {
+                    "entityType": finding.entity_type,
+                    "mask": finding.mask,
+                },
+            })
+        })
+        .collect();
+
+    let mut run = json!({
+        "tool": {
+            "driver": {
+                "name": "SafeMask",
+                "version": env!("CARGO_PKG_VERSION"),
+                "rules": driver_rules,
+            }
+        },
+        "columnKind": "unicodeCodePoints",
+        "artifacts": artifacts,
+        "results": results,
+        "invocations": [{ "executionSuccessful": true }],
+        "properties": {
+            "totalFindings": report.total_findings,
+            "bytesScanned": report.bytes_scanned,
+            "linesScanned": report.lines_scanned,
+        },
+    });
+    if let Some(root) = root {
+        run["originalUriBaseIds"] = json!({ SRCROOT: { "uri": format!("{}/", file_uri(root).trim_end_matches('/')) } });
+    }
+
+    json!({
+        "$schema": SARIF_SCHEMA,
+        "version": SARIF_VERSION,
+        "runs": [run],
+    })
+}
+
+/// 规则元数据
+fn rule_descriptor(finding: &Finding, rule: Option<&Rule>) -> Value {
+    match rule {
+        Some(rule) => {
+            let mut properties = json!({
+                "priority": rule.priority,
+                "mask": rule.mask,
+                "custom": rule.is_custom,
+            });
+            if !rule.group.is_empty() {
+                properties["group"] = json!(rule.group);
+                properties["tags"] = json!([rule.group]);
+            }
+            json!({
+                "id": rule.name,
+                "name": rule.name,
+                "shortDescription": { "text": format!("{} → {}", rule.name, rule.mask) },
+                "defaultConfiguration": { "level": "warning" },
+                "properties": properties,
+            })
+        }
+        // 内置识别器（NER、校验类识别器等）
+        None => json!({
+            "id": finding.rule,
+            "name": finding.rule,
+            "shortDescription": { "text": format!("{} → {}", finding.entity_type, finding.mask) },
+            "defaultConfiguration": { "level": "warning" },
+        }),
+    }
+}
+
+/// 命中区域：Excel 只有单元格行列，文本类额外给出字节偏移
+fn region(finding: &Finding) -> Value {
+    let mut region = json!({
+        "startLine": finding.line.max(1),
+        "startColumn": finding.column.max(1),
+    });
+    if finding.location.is_none() {
+        region["byteOffset"] = json!(finding.offset);
+        region["byteLength"] = json!(finding.length);
+    }
+    region
+}
+
+/// 稳定指纹：HMAC-SHA256(密钥, 规则 \0 文件 URI \0 原文摘要 \0 序号)
+fn fingerprint(key: &[u8], finding: &Finding, uri: &str, occurrence: usize) -> String {
+    let mut mac = HmacSha256::new_from_slice(key).expect("HMAC key");
+    for part in [finding.rule.as_str(), uri, finding.value_hash.as_str(), &occurrence.to_string()] {
+        mac.update(part.as_bytes());
+        mac.update(&[0]);
+    }
+    mac.finalize().into_bytes().iter().map(|b| format!("{:02x}", b)).collect()
+}
+
+/// 文件位置：位于扫描根目录内时使用相对路径 + `SRCROOT`
+fn artifact_location(file: &str, root: Option<&Path>) -> Value {
+    let path = Path::new(file);
+    if let Some(relative) = root.and_then(|r| path.strip_prefix(r).ok()) {
+        let relative = relative
+            .components()
+            .map(|c| c.as_os_str().to_string_lossy())
+            .collect::<Vec<_>>()
+            .join("/");
+        return json!({ "uri": encode_uri_path(&relative, false), "uriBaseId": SRCROOT });
+    }
+    if path.is_absolute() {
+        json!({ "uri": file_uri(path) })
+    } else {
+        json!({ "uri": encode_uri_path(&file.replace('\\', "/"), false) })
+    }
+}
+
+/// 绝对路径 → `file://` URI（Windows 盘符路径为 `file:///C:/...`）
+fn file_uri(path: &Path) -> String {
+    let path = path.to_string_lossy().replace('\\', "/");
+    let path = path.strip_prefix("//?/").unwrap_or(&path);
+    if path.starts_with('/') {
+        format!("file://{}", encode_uri_path(path, true))
+    } else {
+        format!("file:///{}", encode_uri_path(path, true))
+    }
+}
+
+/// 百分号编码路径：保留非保留字符与 `/`，`keep_colon` 时保留盘符冒号
+fn encode_uri_path(path: &str, keep_colon: bool) -> String {
+    let mut out = String::with_capacity(path.len());
+    for &b in path.as_bytes() {
+        match b {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'.' | b'_' | b'~' | b'/' => out.push(b as char),
+            b':' if keep_colon => out.push(':'),
+            _ => out.push_str(&format!("%{:02X}", b)),
+        }
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::infra::fs::scan_report::value_hash;
+
+    const KEY: &[u8] = b"test-fingerprint-key";
+
+    fn finding(file: &str, rule: &str, value: &str, line: u64) -> Finding {
+        Finding {
+            file: file.into(),
+            location: None,
+            line,
+            column: 3,
+            entity_type: "EMAIL".into(),
+            rule: rule.into(),
+            preview: "a@*********om".into(),
+            mask: "<EMAIL>".into(),
+            offset: 40,
+            length: value.len() as u64,
+            value_hash: value_hash(value.as_bytes()),
+        }
+    }
+
+    fn rule(name: &str, group: &str) -> Rule {
+        Rule {
+            name: name.into(),
+            pattern: "x".into(),
+            mask: "<EMAIL>".into(),
+            priority: 7,
+            enabled: true,
+            is_custom: false,
+            case_insensitive: false,
+            whole_word: false,
+            unicode_boundary: false,
+            group: group.into(),
+        }
+    }
+
+    #[test]
+    fn test_directory_scan_uses_relative_uris_and_rule_metadata() {
+        let mut report = ScanReport::new(10).with_root("/data/export");
+        report.files.push("/data/export/logs/app 1.log".into());
+        report.add_finding(finding("/data/export/logs/app 1.log", "email", "a@example.com", 2));
+        report.add_finding(finding("/data/export/logs/app 1.log", "ner_person", "张三", 5));
+
+        let sarif = to_sarif(&report, &[rule("email", "CONTACT")], KEY);
+        assert_eq!(sarif["version"], "2.1.0");
+        let run = &sarif["runs"][0];
+        assert_eq!(run["originalUriBaseIds"]["SRCROOT"]["uri"], "file:///data/export/");
+
+        let rules = run["tool"]["driver"]["rules"].as_array().unwrap();
+        assert_eq!(rules.len(), 2);
+        assert_eq!(rules[0]["properties"]["group"], "CONTACT");
+        assert_eq!(rules[0]["properties"]["priority"], 7);
+        assert_eq!(rules[1]["id"], "ner_person");
+
+        let result = &run["results"][0];
+        assert_eq!(result["ruleIndex"], 0);
+        let location = &result["locations"][0]["physicalLocation"];
+        assert_eq!(location["artifactLocation"]["uri"], "logs/app%201.log");
+        assert_eq!(location["artifactLocation"]["uriBaseId"], "SRCROOT");
+        assert_eq!(location["region"]["startLine"], 2);
+        assert_eq!(location["region"]["byteOffset"], 40);
+        assert_eq!(location["region"]["byteLength"], 13);
+        assert_eq!(run["results"][1]["ruleIndex"], 1);
+    }
+
+    #[test]
+    fn test_fingerprints_are_stable_and_distinguish_repeats() {
+        let build = |line_shift: u64| {
+            let mut report = ScanReport::new(10);
+            report.add_finding(finding("/tmp/a.log", "email", "a@example.com", 1 + line_shift));
+            report.add_finding(finding("/tmp/a.log", "email", "a@example.com", 4 + line_shift));
+            report.add_finding(finding("/tmp/a.log", "email", "b@example.com", 6 + line_shift));
+            to_sarif(&report, &[], KEY)
+        };
+        let fingerprints = |sarif: &Value| -> Vec<String> {
+            sarif["runs"][0]["results"]
+                .as_array()
+                .unwrap()
+                .iter()
+                .map(|r| r["partialFingerprints"][FINGERPRINT_KEY].as_str().unwrap().to_string())
+                .collect()
+        };
+
+        let first = fingerprints(&build(0));
+        // 上方插入若干行后重新扫描，指纹不变
+        assert_eq!(first, fingerprints(&build(3)));
+        assert_ne!(first[0], first[1]);
+        assert_ne!(first[0], first[2]);
+        assert!(!first[0].contains("example"));
+
+        // 不同密钥（其他安装）得到不同指纹
+        let mut report = ScanReport::new(10);
+        report.add_finding(finding("/tmp/a.log", "email", "a@example.com", 1));
+        assert_ne!(fingerprints(&to_sarif(&report, &[], b"other-key"))[0], first[0]);
+    }
+
+    #[test]
+    fn test_fingerprint_key_is_persisted() {
+        let dir = std::env::temp_dir().join(format!("safemask_sarif_{}", uuid::Uuid::new_v4()));
+        fs::create_dir_all(&dir).unwrap();
+        let key = load_or_create_fingerprint_key(&dir);
+        assert_eq!(key.len(), FINGERPRINT_KEY_LEN);
+        assert_eq!(load_or_create_fingerprint_key(&dir), key);
+
+        // 损坏的密钥文件重新生成
+        fs::write(dir.join("fingerprint_key"), "zz").unwrap();
+        assert_ne!(load_or_create_fingerprint_key(&dir), key);
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_results_are_not_capped_by_samples() {
+        let mut report = ScanReport::new(1);
+        report.add_finding(finding("/tmp/a.log", "email", "a@example.com", 1));
+        let mut other = ScanReport::new(1);
+        other.add_finding(finding("/tmp/b.log", "email", "b@example.com", 2));
+        other.add_finding(finding("/tmp/b.log", "phone", "13800138000", 3));
+        report.merge(other);
+        assert_eq!(report.samples.len(), 1);
+
+        let sarif = to_sarif(&report, &[], KEY);
+        let run = &sarif["runs"][0];
+        assert_eq!(run["results"].as_array().unwrap().len(), 3);
+        assert_eq!(run["tool"]["driver"]["rules"].as_array().unwrap().len(), 2);
+        assert_eq!(run["results"][2]["locations"][0]["physicalLocation"]["region"]["startLine"], 3);
+    }
+
+    #[test]
+    fn test_single_file_and_sheet_locations() {
+        let mut sheet = finding("/tmp/客户.xlsx", "email", "a@example.com", 3);
+        sheet.location = Some("Sheet1".into());
+        let mut report = ScanReport::new(10);
+        report.add_finding(sheet);
+
+        let sarif = to_sarif(&report, &[], KEY);
+        let location = &sarif["runs"][0]["results"][0]["locations"][0];
+        assert_eq!(
+            location["physicalLocation"]["artifactLocation"]["uri"],
+            "file:///tmp/%E5%AE%A2%E6%88%B7.xlsx"
+        );
+        assert!(location["physicalLocation"]["region"].get("byteOffset").is_none());
+        assert_eq!(location["logicalLocations"][0]["name"], "Sheet1");
+        assert!(sarif["runs"][0].get("originalUriBaseIds").is_none());
+        assert_eq!(file_uri(Path::new(r"C:\Users\me\a.log")), "file:///C:/Users/me/a.log");
+    }
+}
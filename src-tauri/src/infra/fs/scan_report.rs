@@ -0,0 +1,367 @@
+//! 扫描报告（仅检测、不写脱敏文件）
+//!
+//! 记录每个命中的位置（文件 / 行 / 列）、实体类型、来源规则和打码后的预览值，
+//! 按类型与规则汇总计数。界面与 JSON / CSV / HTML 只展示前 N 条样本；
+//! 超出上限的命中另存于 `overflow`，只保留在内存中（不序列化、不传给前端），仅供 SARIF 导出完整结果。
+//! 报告可导出为 JSON、CSV、HTML（独立单文件，内联样式）以及 SARIF 2.1.0（见 [`super::sarif`]）。
+
+use crate::core::rules::Rule;
+use anyhow::Result;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::collections::BTreeMap;
+
+/// 默认保留的样本数
+pub const DEFAULT_MAX_SAMPLES: usize = 1000;
+
+/// 单条命中
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Finding {
+    /// 文件路径
+    pub file: String,
+    /// 文件内的位置补充（如 Excel 工作表名），纯文本为空
+    #[serde(default)]
+    pub location: Option<String>,
+    /// 行号（从 1 开始；Excel 为行号）
+    pub line: u64,
+    /// 列号（从 1 开始，按字符计；Excel 为列号）
+    pub column: u64,
+    /// 实体类型标签（如 `EMAIL`）
+    pub entity_type: String,
+    /// 命中的规则或识别器
+    pub rule: String,
+    /// 打码后的预览值（只保留首尾少量字符）
+    pub preview: String,
+    /// 脱敏时将使用的标签
+    pub mask: String,
+    /// 命中在扫描文本中的字节偏移（Word / PDF 为提取文本内偏移，Excel 为单元格内偏移）
+    #[serde(default)]
+    pub offset: u64,
+    /// 命中的字节长度
+    #[serde(default)]
+    pub length: u64,
+    /// 命中原文的 SHA-256 摘要（前 16 字节十六进制），只保留在内存中用于生成 SARIF 指纹，不随报告序列化
+    #[serde(skip)]
+    pub value_hash: String,
+}
+
+/// 扫描报告
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ScanReport {
+    /// 目录扫描的根目录（SARIF 导出时据此生成相对路径）；单文件扫描为空
+    #[serde(default)]
+    pub root: Option<String>,
+    /// 已扫描的文件
+    pub files: Vec<String>,
+    pub bytes_scanned: u64,
+    pub lines_scanned: u64,
+    /// 命中总数（不受样本上限影响）
+    pub total_findings: usize,
+    pub counts_by_type: BTreeMap<String, usize>,
+    pub counts_by_rule: BTreeMap<String, usize>,
+    /// 前 N 条命中（按文件内顺序）
+    pub samples: Vec<Finding>,
+    /// 超出样本上限的命中（只用于 SARIF 导出，保证安全平台拿到全部结果；不序列化）
+    #[serde(skip)]
+    pub overflow: Vec<Finding>,
+    /// 样本上限
+    pub max_samples: usize,
+    pub duration_secs: f64,
+}
+
+/// 报告导出格式
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ScanExportFormat {
+    Json,
+    Csv,
+    Html,
+    Sarif,
+}
+
+impl ScanExportFormat {
+    /// 解析格式名（忽略大小写）
+    pub fn parse(name: &str) -> Option<Self> {
+        match name.to_ascii_lowercase().as_str() {
+            "json" => Some(Self::Json),
+            "csv" => Some(Self::Csv),
+            "html" | "htm" => Some(Self::Html),
+            "sarif" => Some(Self::Sarif),
+            _ => None,
+        }
+    }
+}
+
+/// 命中原文的摘要（SHA-256 前 16 字节）
+pub fn value_hash(value: &[u8]) -> String {
+    Sha256::digest(value)[..16].iter().map(|b| format!("{:02x}", b)).collect()
+}
+
+/// 打码预览：保留首尾各 2 个字符，其余替换为 `*`；4 个字符以内全部打码
+pub fn preview_value(value: &str) -> String {
+    let chars: Vec<char> = value
+        .chars()
+        .map(|c| if c.is_control() { ' ' } else { c })
+        .collect();
+    let n = chars.len();
+    if n <= 4 {
+        return "*".repeat(n);
+    }
+    let mut preview: String = chars[..2].iter().collect();
+    preview.push_str(&"*".repeat(n - 4));
+    preview.extend(&chars[n - 2..]);
+    preview
+}
+
+impl ScanReport {
+    /// 创建空报告
+    pub fn new(max_samples: usize) -> Self {
+        Self {
+            max_samples,
+            ..Default::default()
+        }
+    }
+
+    /// 记录一条命中（超过样本上限时放入 `overflow`）
+    pub fn add_finding(&mut self, finding: Finding) {
+        self.total_findings += 1;
+        *self.counts_by_type.entry(finding.entity_type.clone()).or_insert(0) += 1;
+        *self.counts_by_rule.entry(finding.rule.clone()).or_insert(0) += 1;
+        if self.samples.len() < self.max_samples {
+            self.samples.push(finding);
+        } else {
+            self.overflow.push(finding);
+        }
+    }
+
+    /// 全部命中：样本在前，超出上限的在后
+    pub fn findings(&self) -> impl Iterator<Item = &Finding> {
+        self.samples.iter().chain(&self.overflow)
+    }
+
+    /// 可变访问全部命中（用于偏移换算）
+    pub fn findings_mut(&mut self) -> impl Iterator<Item = &mut Finding> {
+        self.samples.iter_mut().chain(&mut self.overflow)
+    }
+
+    /// 合并另一份报告（`other` 的样本排在后面）
+    pub fn merge(&mut self, other: ScanReport) {
+        for file in other.files {
+            if !self.files.contains(&file) {
+                self.files.push(file);
+            }
+        }
+        self.bytes_scanned += other.bytes_scanned;
+        self.lines_scanned += other.lines_scanned;
+        self.total_findings += other.total_findings;
+        for (k, v) in other.counts_by_type {
+            *self.counts_by_type.entry(k).or_insert(0) += v;
+        }
+        for (k, v) in other.counts_by_rule {
+            *self.counts_by_rule.entry(k).or_insert(0) += v;
+        }
+        let room = self.max_samples.saturating_sub(self.samples.len());
+        let mut incoming = other.samples.into_iter().chain(other.overflow);
+        self.samples.extend(incoming.by_ref().take(room));
+        self.overflow.extend(incoming);
+        self.duration_secs += other.duration_secs;
+    }
+
+    /// 标记目录扫描的根目录
+    pub fn with_root(mut self, root: impl Into<String>) -> Self {
+        self.root = Some(root.into());
+        self
+    }
+
+    /// 按格式渲染报告（`rules` 与 `fingerprint_key` 仅用于 SARIF 的规则元数据与指纹）
+    pub fn render(&self, format: ScanExportFormat, rules: &[Rule], fingerprint_key: &[u8]) -> Result<String> {
+        match format {
+            ScanExportFormat::Json => Ok(serde_json::to_string_pretty(self)?),
+            ScanExportFormat::Csv => self.to_csv(),
+            ScanExportFormat::Html => Ok(self.to_html()),
+            ScanExportFormat::Sarif => Ok(serde_json::to_string_pretty(&super::sarif::to_sarif(self, rules, fingerprint_key))?),
+        }
+    }
+
+    /// CSV：每行一条样本
+    fn to_csv(&self) -> Result<String> {
+        let mut writer = csv::Writer::from_writer(Vec::new());
+        writer.write_record(["file", "location", "line", "column", "entity_type", "rule", "preview", "mask"])?;
+        for f in &self.samples {
+            writer.write_record([
+                csv_safe(&f.file),
+                csv_safe(f.location.as_deref().unwrap_or("")),
+                f.line.to_string(),
+                f.column.to_string(),
+                csv_safe(&f.entity_type),
+                csv_safe(&f.rule),
+                csv_safe(&f.preview),
+                csv_safe(&f.mask),
+            ])?;
+        }
+        let bytes = writer.into_inner().map_err(|e| anyhow::anyhow!("CSV 写入失败: {}", e))?;
+        Ok(String::from_utf8(bytes)?)
+    }
+
+    /// HTML：汇总 + 类型计数 + 规则计数 + 样本表
+    fn to_html(&self) -> String {
+        let count_rows = |counts: &BTreeMap<String, usize>| {
+            let mut rows: Vec<(&String, &usize)> = counts.iter().collect();
+            rows.sort_by(|a, b| b.1.cmp(a.1).then(a.0.cmp(b.0)));
+            rows.iter()
+                .map(|(k, v)| format!("<tr><td>{}</td><td class=\"n\">{}</td></tr>", html_escape(k), v))
+                .collect::<String>()
+        };
+        let sample_rows: String = self
+            .samples
+            .iter()
+            .map(|f| {
+                let location = match &f.location {
+                    Some(loc) => format!("{} · {}", html_escape(&f.file), html_escape(loc)),
+                    None => html_escape(&f.file),
+                };
+                format!(
+                    "<tr><td>{}</td><td class=\"n\">{}:{}</td><td>{}</td><td>{}</td><td><code>{}</code></td></tr>",
+                    location,
+                    f.line,
+                    f.column,
+                    html_escape(&f.entity_type),
+                    html_escape(&f.rule),
+                    html_escape(&f.preview),
+                )
+            })
+            .collect();
+        let files: String = self
+            .files
+            .iter()
+            .map(|f| format!("<li>{}</li>", html_escape(f)))
+            .collect();
+
+        format!(
+            r#"<!DOCTYPE html>
+<html lang="zh-CN">
+<head>
+<meta charset="utf-8">
+<title>SafeMask 扫描报告</title>
+<style>
+body {{ font-family: -apple-system, "Segoe UI", "PingFang SC", sans-serif; margin: 2rem; color: #222; }}
+table {{ border-collapse: collapse; margin-bottom: 2rem; }}
+th, td {{ border: 1px solid #ddd; padding: 4px 10px; text-align: left; }}
+th {{ background: #f5f5f5; }}
+td.n {{ text-align: right; font-variant-numeric: tabular-nums; }}
+</style>
+</head>
+<body>
+<h1>SafeMask 扫描报告</h1>
+<p>扫描 {bytes} 字节 / {lines} 行，共 {total} 处命中，耗时 {secs:.2}s。样本显示前 {shown} 条。</p>
+<ul>{files}</ul>
+<h2>按实体类型</h2>
+<table><tr><th>类型</th><th>数量</th></tr>{by_type}</table>
+<h2>按规则</h2>
+<table><tr><th>规则</th><th>数量</th></tr>{by_rule}</table>
+<h2>样本</h2>
+<table><tr><th>文件</th><th>行:列</th><th>类型</th><th>规则</th><th>预览</th></tr>{samples}</table>
+</body>
+</html>
+"#,
+            bytes = self.bytes_scanned,
+            lines = self.lines_scanned,
+            total = self.total_findings,
+            secs = self.duration_secs,
+            shown = self.samples.len(),
+            files = files,
+            by_type = count_rows(&self.counts_by_type),
+            by_rule = count_rows(&self.counts_by_rule),
+            samples = sample_rows,
+        )
+    }
+}
+
+/// 防止 CSV 在表格软件中被当作公式执行
+fn csv_safe(value: &str) -> String {
+    if value.starts_with(['=', '+', '-', '@']) {
+        format!("'{}", value)
+    } else {
+        value.to_string()
+    }
+}
+
+fn html_escape(value: &str) -> String {
+    let mut out = String::with_capacity(value.len());
+    for c in value.chars() {
+        match c {
+            '<' => out.push_str("&lt;"),
+            '>' => out.push_str("&gt;"),
+            '&' => out.push_str("&amp;"),
+            '"' => out.push_str("&quot;"),
+            '\'' => out.push_str("&#39;"),
+            _ => out.push(c),
+        }
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn finding(entity_type: &str, rule: &str, line: u64) -> Finding {
+        Finding {
+            file: "a.log".into(),
+            location: None,
+            line,
+            column: 1,
+            entity_type: entity_type.into(),
+            rule: rule.into(),
+            preview: preview_value("<script>@x"),
+            mask: "<EMAIL>".into(),
+            offset: 0,
+            length: 10,
+            value_hash: value_hash(b"<script>@x"),
+        }
+    }
+
+    #[test]
+    fn test_preview_value() {
+        assert_eq!(preview_value("13800138000"), "13*******00");
+        assert_eq!(preview_value("张三丰先生"), "张三*先生");
+        assert_eq!(preview_value("abcd"), "****");
+    }
+
+    #[test]
+    fn test_sample_cap_and_merge() {
+        let mut a = ScanReport::new(2);
+        a.add_finding(finding("EMAIL", "email", 1));
+        let mut b = ScanReport::new(2);
+        b.add_finding(finding("EMAIL", "email", 5));
+        b.add_finding(finding("PHONE", "phone", 6));
+        a.merge(b);
+        assert_eq!(a.total_findings, 3);
+        assert_eq!(a.samples.len(), 2);
+        assert_eq!(a.samples[1].line, 5);
+        assert_eq!(a.counts_by_type["EMAIL"], 2);
+        assert_eq!(a.counts_by_rule["phone"], 1);
+    }
+
+    #[test]
+    fn test_exports_escape_content() {
+        let mut report = ScanReport::new(10);
+        report.files.push("a.log".into());
+        report.add_finding(finding("EMAIL", "=cmd", 1));
+
+        let html = report.render(ScanExportFormat::Html, &[], &[]).unwrap();
+        assert!(html.contains("&lt;s******@x"));
+        assert!(!html.contains("<script>"));
+
+        let csv = report.render(ScanExportFormat::Csv, &[], &[]).unwrap();
+        assert!(csv.starts_with("file,location,line"));
+        assert!(csv.contains(",'=cmd,"));
+
+        let rendered = report.render(ScanExportFormat::Json, &[], &[]).unwrap();
+        // 原文摘要与超出上限的命中只保留在内存中
+        assert!(!rendered.contains("value_hash") && !rendered.contains("overflow"));
+        let json: ScanReport = serde_json::from_str(&rendered).unwrap();
+        assert_eq!(json.total_findings, 1);
+        assert_eq!(ScanExportFormat::parse("HTM"), Some(ScanExportFormat::Html));
+    }
+}
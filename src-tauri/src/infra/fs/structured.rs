@@ -0,0 +1,1164 @@
+//! JSON / JSON Lines / YAML 结构化脱敏
+//!
+//! 只在字符串值内部做识别（键名、数字、结构字符不会被替换），脱敏后重新按格式转义，输出始终合法：
+//! - 键名规则：键名（忽略大小写与 `_` `-`）以规则词结尾时（如 `db_password`、`accessToken`），
+//!   其下所有值整体脱敏，数字 / 布尔值替换为掩码字符串
+//! - JSONPath 选择器：支持 `$`、`.key`、`['key']`、`[n]`、`*`、`[*]` 与递归下降 `..key`，
+//!   命中节点（含其子树）的所有值整体脱敏
+//!
+//! JSON / JSON Lines 只替换值所在的字节区间，键顺序、缩进与其余字节原样保留；
+//! YAML 按 libyaml 事件流给出的位置原位替换标量（注释按纯文本识别），引号风格、锚点与文档分隔符同样原样保留。
+//! 整体不是合法 JSON 的 `.json` 按 JSON Lines 逐行处理，无法解析的行与 YAML 回退到纯文本识别。
+
+use crate::common::state::EntitySpanBrief;
+use crate::core::file_options::StructuredOptions;
+use crate::core::hybrid_engine::HybridEngine;
+use crate::core::recognizer::{EntitySpan, EntityType};
+use crate::infra::fs::processor::{check_cancel, count_lines, span_to_finding, LineTracker};
+use crate::infra::fs::scan_report::ScanReport;
+use anyhow::{anyhow, bail, Result};
+use rayon::prelude::*;
+use serde::de::IgnoredAny;
+use serde_yaml::Value as YamlValue;
+use std::ffi::CStr;
+use std::mem::MaybeUninit;
+use std::ops::Range;
+use std::sync::atomic::AtomicBool;
+use unsafe_libyaml::{
+    YAML_ALIAS_EVENT, YAML_DOUBLE_QUOTED_SCALAR_STYLE, YAML_FLOW_MAPPING_STYLE, YAML_FLOW_SEQUENCE_STYLE,
+    YAML_FOLDED_SCALAR_STYLE, YAML_LITERAL_SCALAR_STYLE, YAML_MAPPING_END_EVENT, YAML_MAPPING_START_EVENT,
+    YAML_PLAIN_SCALAR_STYLE, YAML_SCALAR_EVENT, YAML_SEQUENCE_END_EVENT, YAML_SEQUENCE_START_EVENT,
+    YAML_SINGLE_QUOTED_SCALAR_STYLE, YAML_STREAM_END_EVENT, yaml_event_delete, yaml_event_t, yaml_parser_delete,
+    yaml_parser_initialize, yaml_parser_parse, yaml_parser_set_input_string, yaml_parser_t, yaml_scalar_style_t,
+};
+
+/// JSON Lines 每批并行处理的行数
+const BATCH_LINES: usize = 4096;
+
+/// 结构化文本格式
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum StructuredFormat {
+    Json,
+    JsonLines,
+    Yaml,
+}
+
+/// 结构化文本无法解析（调用方据此回退到纯文本流水线）
+#[derive(Debug, thiserror::Error)]
+#[error("结构化文本解析失败: {0}")]
+pub struct Malformed(pub String);
+
+// ─────────────────────────────────────────────────────────────────────────────
+// 路径与选择器
+// ─────────────────────────────────────────────────────────────────────────────
+
+/// 值在文档中的路径元素
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum PathElem {
+    Key(String),
+    Index(usize),
+}
+
+/// JSONPath 单步
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Step {
+    Key(String),
+    Index(usize),
+    Wildcard,
+    /// `..`：跳过任意层后匹配内层单步
+    Descendant(Box<Step>),
+}
+
+impl Step {
+    fn matches(&self, elem: &PathElem) -> bool {
+        match (self, elem) {
+            (Self::Key(k), PathElem::Key(e)) => k == e,
+            (Self::Index(i), PathElem::Index(e)) => i == e,
+            (Self::Wildcard, _) => true,
+            _ => false,
+        }
+    }
+}
+
+/// 解析 JSONPath 选择器
+fn parse_selector(selector: &str) -> Result<Vec<Step>> {
+    let rest = selector.trim().strip_prefix('$').ok_or_else(|| anyhow!("选择器需以 `$` 开头"))?;
+    let bytes = rest.as_bytes();
+    let mut steps = Vec::new();
+    let mut pos = 0;
+    while pos < bytes.len() {
+        let descendant = bytes[pos..].starts_with(b"..");
+        let step = match bytes[pos] {
+            b'.' => {
+                pos += if descendant { 2 } else { 1 };
+                if bytes.get(pos) == Some(&b'[') {
+                    let (step, next) = parse_bracket(rest, pos)?;
+                    pos = next;
+                    step
+                } else {
+                    let end = rest[pos..].find(['.', '[']).map_or(rest.len(), |i| pos + i);
+                    let name = &rest[pos..end];
+                    pos = end;
+                    match name {
+                        "" => bail!("选择器 `{}` 缺少键名", selector),
+                        "*" => Step::Wildcard,
+                        _ => Step::Key(name.to_string()),
+                    }
+                }
+            }
+            b'[' => {
+                let (step, next) = parse_bracket(rest, pos)?;
+                pos = next;
+                step
+            }
+            _ => bail!("选择器 `{}` 在第 {} 个字符处无法解析", selector, pos + 2),
+        };
+        steps.push(if descendant { Step::Descendant(Box::new(step)) } else { step });
+    }
+    Ok(steps)
+}
+
+/// 解析 `[...]`（`pos` 指向 `[`），返回单步与 `]` 之后的位置
+fn parse_bracket(rest: &str, pos: usize) -> Result<(Step, usize)> {
+    let inner_start = pos + 1;
+    let bytes = rest.as_bytes();
+    if let Some(&quote) = bytes.get(inner_start).filter(|&&b| b == b'\'' || b == b'"') {
+        let close = rest[inner_start + 1..]
+            .find(quote as char)
+            .map(|i| inner_start + 1 + i)
+            .ok_or_else(|| anyhow!("选择器引号未闭合"))?;
+        if bytes.get(close + 1) != Some(&b']') {
+            bail!("选择器缺少 `]`");
+        }
+        return Ok((Step::Key(rest[inner_start + 1..close].to_string()), close + 2));
+    }
+    let close = rest[inner_start..].find(']').map(|i| inner_start + i).ok_or_else(|| anyhow!("选择器缺少 `]`"))?;
+    let inner = rest[inner_start..close].trim();
+    let step = match inner {
+        "*" => Step::Wildcard,
+        _ => Step::Index(inner.parse().map_err(|_| anyhow!("无效的数组下标 `{}`", inner))?),
+    };
+    Ok((step, close + 1))
+}
+
+/// 选择器是否匹配路径的某个前缀（选中节点的子树也算命中）
+fn selector_matches(steps: &[Step], path: &[PathElem]) -> bool {
+    match steps.split_first() {
+        None => true,
+        Some((Step::Descendant(inner), rest)) => {
+            (0..path.len()).any(|i| inner.matches(&path[i]) && selector_matches(rest, &path[i + 1..]))
+        }
+        Some((step, rest)) => path.first().is_some_and(|e| step.matches(e)) && selector_matches(rest, &path[1..]),
+    }
+}
+
+/// 编译后的键名规则与选择器
+struct StructuredRules {
+    /// (归一化规则词, 原规则词)
+    key_rules: Vec<(String, String)>,
+    selectors: Vec<Vec<Step>>,
+}
+
+impl StructuredRules {
+    fn new(options: &StructuredOptions) -> Self {
+        let key_rules = options
+            .key_rules
+            .iter()
+            .map(|k| (normalize_key(k), k.trim().to_string()))
+            .filter(|(n, _)| !n.is_empty())
+            .collect();
+        let selectors = options
+            .paths
+            .iter()
+            .filter_map(|p| match parse_selector(p) {
+                Ok(steps) => Some(steps),
+                Err(e) => {
+                    log::warn!("⚠️ 忽略无效的 JSONPath 选择器 `{}`: {}", p, e);
+                    None
+                }
+            })
+            .collect();
+        Self { key_rules, selectors }
+    }
+
+    /// 路径上的值是否需要整体脱敏，返回掩码使用的实体类型
+    ///
+    /// 键名（归一化后）以规则词结尾即命中，复数形式（`credentials`、`db_passwords`）同样命中。
+    fn forced(&self, path: &[PathElem]) -> Option<EntityType> {
+        for elem in path {
+            if let PathElem::Key(key) = elem {
+                let normalized = normalize_key(key);
+                let singular = normalized.strip_suffix('s');
+                let matches = |rule: &str| normalized.ends_with(rule) || singular.is_some_and(|k| k.ends_with(rule));
+                if let Some((_, rule)) = self.key_rules.iter().find(|(n, _)| matches(n)) {
+                    return Some(EntityType::from_label(rule));
+                }
+            }
+        }
+        if self.selectors.iter().any(|s| selector_matches(s, path)) {
+            let key = path.iter().rev().find_map(|e| match e {
+                PathElem::Key(k) => Some(k.as_str()),
+                PathElem::Index(_) => None,
+            });
+            return Some(EntityType::from_label(key.unwrap_or("value")));
+        }
+        None
+    }
+}
+
+/// 键名归一化：小写并去掉 `_` `-`
+fn normalize_key(key: &str) -> String {
+    key.chars().filter(|c| *c != '_' && *c != '-').flat_map(char::to_lowercase).collect()
+}
+
+fn whole_span(entity_type: EntityType, len: usize) -> EntitySpan {
+    EntitySpan::new(0, len, entity_type, 1.0, "structured_key")
+}
+
+/// 字符串值内需要替换的实体；返回空表示不替换
+fn value_spans(engine: &HybridEngine, text: &str, forced: Option<&EntityType>) -> Vec<EntitySpan> {
+    match forced {
+        Some(_) if text.is_empty() => Vec::new(),
+        Some(entity_type) => vec![whole_span(entity_type.clone(), text.len())],
+        None => engine.detect(text.as_bytes()).into_iter().filter(|s| s.start < s.end).collect(),
+    }
+}
+
+/// 实体及其在源文本中的字节区间
+type LocatedSpans = Vec<(EntitySpan, Range<usize>)>;
+
+/// 一处值替换：源文本区间、替换后的字节与其中的实体（区间均为源文本偏移）
+struct Edit {
+    range: Range<usize>,
+    bytes: Vec<u8>,
+    spans: LocatedSpans,
+}
+
+impl Edit {
+    fn shifted(mut self, offset: usize) -> Self {
+        self.range = self.range.start + offset..self.range.end + offset;
+        for (_, source) in &mut self.spans {
+            *source = source.start + offset..source.end + offset;
+        }
+        self
+    }
+}
+
+/// 按顺序把替换写入输出，同时生成带全文行列号的实体摘要
+struct EditWriter<'a> {
+    data: &'a [u8],
+    last: usize,
+    tracker: LineTracker<'a>,
+}
+
+impl<'a> EditWriter<'a> {
+    fn new(data: &'a [u8]) -> Self {
+        Self { data, last: 0, tracker: LineTracker::new(data) }
+    }
+
+    /// 写出 `end` 之前的原文与其间的替换（`edits` 按位置排序且都在 `end` 之前）
+    fn write(
+        &mut self,
+        engine: &HybridEngine,
+        edits: impl IntoIterator<Item = Edit>,
+        end: usize,
+        output: &mut Vec<u8>,
+        entities: &mut Vec<EntitySpanBrief>,
+    ) {
+        for edit in edits {
+            output.extend_from_slice(&self.data[self.last..edit.range.start]);
+            output.extend_from_slice(&edit.bytes);
+            entities.extend(edit.spans.iter().map(|(span, source)| {
+                let (line, column) = self.tracker.locate(source.start);
+                let (start, end) = (source.start, source.end);
+                EntitySpanBrief { start, end, line: Some(line), column: Some(column), ..engine.brief(span) }
+            }));
+            self.last = edit.range.end;
+        }
+        output.extend_from_slice(&self.data[self.last..end]);
+        self.last = end;
+    }
+}
+
+// ─────────────────────────────────────────────────────────────────────────────
+// JSON
+// ─────────────────────────────────────────────────────────────────────────────
+
+/// JSON 中的标量值（非字符串值只在需要整体脱敏时记录）
+struct ValueToken {
+    range: Range<usize>,
+    is_string: bool,
+    forced: Option<EntityType>,
+}
+
+/// 遍历已校验的 JSON 文本，按出现顺序收集标量值的字节区间
+struct JsonWalker<'a> {
+    data: &'a [u8],
+    pos: usize,
+    path: Vec<PathElem>,
+    rules: &'a StructuredRules,
+    values: Vec<ValueToken>,
+}
+
+impl JsonWalker<'_> {
+    fn value(&mut self) -> Result<()> {
+        self.skip_ws();
+        match self.data.get(self.pos) {
+            Some(b'{') => {
+                self.pos += 1;
+                let mut first = true;
+                loop {
+                    self.skip_ws();
+                    if self.eat(b'}') {
+                        break;
+                    }
+                    if !first {
+                        self.expect(b',')?;
+                        self.skip_ws();
+                    }
+                    first = false;
+                    let key = self.string()?;
+                    let key: String = serde_json::from_slice(&self.data[key])?;
+                    self.skip_ws();
+                    self.expect(b':')?;
+                    self.path.push(PathElem::Key(key));
+                    self.value()?;
+                    self.path.pop();
+                }
+            }
+            Some(b'[') => {
+                self.pos += 1;
+                let mut index = 0;
+                loop {
+                    self.skip_ws();
+                    if self.eat(b']') {
+                        break;
+                    }
+                    if index > 0 {
+                        self.expect(b',')?;
+                    }
+                    self.path.push(PathElem::Index(index));
+                    self.value()?;
+                    self.path.pop();
+                    index += 1;
+                }
+            }
+            Some(b'"') => {
+                let range = self.string()?;
+                let forced = self.rules.forced(&self.path);
+                self.values.push(ValueToken { range, is_string: true, forced });
+            }
+            Some(_) => {
+                let start = self.pos;
+                while self.data.get(self.pos).is_some_and(|b| !matches!(b, b',' | b'}' | b']' | b' ' | b'\t' | b'\r' | b'\n')) {
+                    self.pos += 1;
+                }
+                if &self.data[start..self.pos] != b"null"
+                    && let Some(forced) = self.rules.forced(&self.path)
+                {
+                    self.values.push(ValueToken { range: start..self.pos, is_string: false, forced: Some(forced) });
+                }
+            }
+            None => bail!("JSON 意外结束"),
+        }
+        Ok(())
+    }
+
+    /// 字符串（含引号）的字节区间
+    fn string(&mut self) -> Result<Range<usize>> {
+        let start = self.pos;
+        self.expect(b'"')?;
+        loop {
+            let i = memchr::memchr2(b'"', b'\\', &self.data[self.pos..]).ok_or_else(|| anyhow!("JSON 字符串未闭合"))?;
+            self.pos += i;
+            if self.data[self.pos] == b'\\' {
+                self.pos += 2;
+            } else {
+                self.pos += 1;
+                return Ok(start..self.pos);
+            }
+        }
+    }
+
+    fn skip_ws(&mut self) {
+        while self.data.get(self.pos).is_some_and(|b| matches!(b, b' ' | b'\t' | b'\r' | b'\n')) {
+            self.pos += 1;
+        }
+    }
+
+    fn eat(&mut self, byte: u8) -> bool {
+        let hit = self.data.get(self.pos) == Some(&byte);
+        if hit {
+            self.pos += 1;
+        }
+        hit
+    }
+
+    fn expect(&mut self, byte: u8) -> Result<()> {
+        if !self.eat(byte) {
+            bail!("JSON 第 {} 字节处应为 `{}`", self.pos, byte as char);
+        }
+        Ok(())
+    }
+}
+
+/// 单个值的替换结果：（新的 JSON 字面量, 值内实体）
+fn mask_json_value(engine: &HybridEngine, data: &[u8], token: &ValueToken) -> Option<(Vec<u8>, Vec<EntitySpan>)> {
+    let raw = &data[token.range.clone()];
+    if !token.is_string {
+        let span = whole_span(token.forced.clone()?, raw.len());
+        return Some((serde_json::to_vec(&engine.resolve_mask(&span)).ok()?, vec![span]));
+    }
+    let text: String = serde_json::from_slice(raw).ok()?;
+    let spans = value_spans(engine, &text, token.forced.as_ref());
+    if spans.is_empty() {
+        return None;
+    }
+    let masked = engine.mask_spans(text.as_bytes(), &spans);
+    Some((serde_json::to_vec(&String::from_utf8_lossy(&masked)).ok()?, spans))
+}
+
+/// 字符串值（含引号的原始字面量）中解码后偏移 `pos` 对应的原始字节偏移
+fn json_raw_offset(raw: &[u8], pos: usize) -> usize {
+    let (mut at, mut decoded) = (1, 0);
+    while decoded < pos && at < raw.len() {
+        if raw[at] != b'\\' {
+            at += 1;
+            decoded += 1;
+            continue;
+        }
+        if raw.get(at + 1) != Some(&b'u') {
+            at += 2;
+            decoded += 1;
+            continue;
+        }
+        let unit = |i: usize| raw.get(i..i + 4).and_then(|h| u32::from_str_radix(std::str::from_utf8(h).ok()?, 16).ok());
+        let high = unit(at + 2).unwrap_or(0);
+        if (0xD800..0xDC00).contains(&high) && raw[at + 6..].starts_with(b"\\u") {
+            at += 12;
+            decoded += 4;
+        } else {
+            at += 6;
+            decoded += char::from_u32(high).map_or(3, char::len_utf8);
+        }
+    }
+    at
+}
+
+/// 单个 JSON 文档中的替换（区间相对 `data`）；`data` 不是合法 JSON 时返回 `Malformed`
+fn json_document_edits(data: &[u8], engine: &HybridEngine, rules: &StructuredRules) -> Result<Vec<Edit>> {
+    serde_json::from_slice::<IgnoredAny>(data).map_err(|e| Malformed(e.to_string()))?;
+    let mut walker = JsonWalker { data, pos: 0, path: Vec::new(), rules, values: Vec::new() };
+    walker.value()?;
+
+    Ok(walker
+        .values
+        .par_iter()
+        .filter_map(|token| {
+            let (bytes, spans) = mask_json_value(engine, data, token)?;
+            let range = token.range.clone();
+            let raw = &data[range.clone()];
+            let offset = |pos: usize| if raw[0] == b'"' { range.start + json_raw_offset(raw, pos) } else { range.start + pos };
+            let spans = spans
+                .into_iter()
+                .map(|span| {
+                    let source = offset(span.start)..offset(span.end);
+                    (span, source)
+                })
+                .collect();
+            Some(Edit { range, bytes, spans })
+        })
+        .collect())
+}
+
+/// JSON Lines 中一行（`line` 为含换行符的区间）的替换，区间相对 `data`；无法解析的行按纯文本识别
+fn json_line_edits(data: &[u8], line: Range<usize>, engine: &HybridEngine, rules: &StructuredRules) -> Vec<Edit> {
+    let text = &data[line.clone()];
+    let body_len = text.len() - text.iter().rev().take_while(|&&b| b == b'\n' || b == b'\r').count();
+    let body = &text[..body_len];
+    if body.iter().all(u8::is_ascii_whitespace) {
+        return Vec::new();
+    }
+    let edits = json_document_edits(body, engine, rules).unwrap_or_else(|_| {
+        let spans: Vec<EntitySpan> = engine.detect(body).into_iter().filter(|s| s.start < s.end).collect();
+        if spans.is_empty() {
+            return Vec::new();
+        }
+        let bytes = engine.mask_spans(body, &spans);
+        let spans = spans.into_iter().map(|span| {
+            let source = span.start..span.end;
+            (span, source)
+        });
+        vec![Edit { range: 0..body.len(), bytes, spans: spans.collect() }]
+    });
+    edits.into_iter().map(|edit| edit.shifted(line.start)).collect()
+}
+
+/// 按换行符切分的行区间（含换行符）
+fn line_ranges(data: &[u8]) -> impl Iterator<Item = Range<usize>> + '_ {
+    let mut start = 0;
+    data.split_inclusive(|&b| b == b'\n').map(move |line| {
+        let range = start..start + line.len();
+        start = range.end;
+        range
+    })
+}
+
+/// 脱敏 JSON 文本：整体是单个 JSON 文档时结构化处理，否则按 JSON Lines 逐行处理
+pub fn mask_json(
+    data: &[u8],
+    engine: &HybridEngine,
+    options: &StructuredOptions,
+    cancel: &AtomicBool,
+    progress: impl Fn(f64),
+    mut sink: impl FnMut(&[u8]) -> Result<()>,
+) -> Result<(u64, Vec<EntitySpanBrief>)> {
+    let rules = StructuredRules::new(options);
+    match json_document_edits(data, engine, &rules) {
+        Ok(edits) => {
+            let (mut output, mut entities) = (Vec::with_capacity(data.len()), Vec::new());
+            EditWriter::new(data).write(engine, edits, data.len(), &mut output, &mut entities);
+            sink(&output)?;
+            Ok((bytecount::count(data, b'\n') as u64, entities))
+        }
+        Err(e) if e.is::<Malformed>() => {
+            log::info!("📄 内容不是单个 JSON 文档，按 JSON Lines 逐行处理: {}", e);
+            mask_lines(data, engine, &rules, cancel, progress, sink)
+        }
+        Err(e) => Err(e),
+    }
+}
+
+/// 脱敏 JSON Lines 文本（每行一个 JSON 文档；无法解析的行按纯文本识别）
+pub fn mask_json_lines(
+    data: &[u8],
+    engine: &HybridEngine,
+    options: &StructuredOptions,
+    cancel: &AtomicBool,
+    progress: impl Fn(f64),
+    sink: impl FnMut(&[u8]) -> Result<()>,
+) -> Result<(u64, Vec<EntitySpanBrief>)> {
+    mask_lines(data, engine, &StructuredRules::new(options), cancel, progress, sink)
+}
+
+fn mask_lines(
+    data: &[u8],
+    engine: &HybridEngine,
+    rules: &StructuredRules,
+    cancel: &AtomicBool,
+    progress: impl Fn(f64),
+    mut sink: impl FnMut(&[u8]) -> Result<()>,
+) -> Result<(u64, Vec<EntitySpanBrief>)> {
+    let total = data.len().max(1) as f64;
+    let mut lines = line_ranges(data).peekable();
+    let mut line_count = 0u64;
+    let mut writer = EditWriter::new(data);
+    let mut entities = Vec::new();
+    while lines.peek().is_some() {
+        check_cancel(cancel)?;
+        let batch: Vec<Range<usize>> = lines.by_ref().take(BATCH_LINES).collect();
+        let edits: Vec<Vec<Edit>> = batch.par_iter().map(|line| json_line_edits(data, line.clone(), engine, rules)).collect();
+
+        let done = batch.last().map_or(0, |l| l.end);
+        let mut output = Vec::with_capacity(done - writer.last);
+        writer.write(engine, edits.into_iter().flatten(), done, &mut output, &mut entities);
+        sink(&output)?;
+        line_count += batch.len() as u64;
+        progress(done as f64 / total);
+    }
+    Ok((line_count, entities))
+}
+
+// ─────────────────────────────────────────────────────────────────────────────
+// YAML
+// ─────────────────────────────────────────────────────────────────────────────
+
+/// `!!str` 标签
+const YAML_STR_TAG: &str = "tag:yaml.org,2002:str";
+
+/// libyaml 解析事件（只保留原位替换需要的信息）
+enum YamlEvent {
+    Scalar(YamlScalar),
+    /// 映射 / 序列开始（`flow` 为 `{...}` / `[...]` 写法）
+    CollectionStart { mapping: bool, flow: bool },
+    CollectionEnd,
+    Alias,
+    /// 流与文档边界
+    Boundary,
+}
+
+struct YamlScalar {
+    /// 解码后的值
+    value: String,
+    /// 源文本字节区间（含锚点与标签）
+    range: Range<usize>,
+    style: yaml_scalar_style_t,
+    tag: Option<String>,
+}
+
+/// 解析器句柄，离开作用域时释放 libyaml 内部缓冲
+struct YamlParser(Box<MaybeUninit<yaml_parser_t>>);
+
+impl Drop for YamlParser {
+    fn drop(&mut self) {
+        // SAFETY: 只有初始化成功后才会构造 `YamlParser`
+        unsafe { yaml_parser_delete(self.0.as_mut_ptr()) }
+    }
+}
+
+/// 用 libyaml 把整个 YAML 流解析为事件序列；语法错误返回 `Malformed`
+fn yaml_events(data: &[u8]) -> Result<Vec<YamlEvent>> {
+    let mut raw = Box::new(MaybeUninit::<yaml_parser_t>::uninit());
+    // SAFETY: 解析器分配在堆上且不再移动；输入 `data` 的生命周期覆盖整个解析过程
+    unsafe {
+        if yaml_parser_initialize(raw.as_mut_ptr()).fail {
+            bail!("YAML 解析器初始化失败");
+        }
+        yaml_parser_set_input_string(raw.as_mut_ptr(), data.as_ptr(), data.len() as u64);
+    }
+    let mut parser = YamlParser(raw);
+    let parser = parser.0.as_mut_ptr();
+
+    let mut events = Vec::new();
+    loop {
+        let mut event = MaybeUninit::<yaml_event_t>::uninit();
+        // SAFETY: 事件由 `yaml_parser_parse` 填充，读取完字段后立即释放
+        unsafe {
+            if yaml_parser_parse(parser, event.as_mut_ptr()).fail {
+                let parser = &*parser;
+                let (problem, mark) = (parser.problem, parser.problem_mark);
+                let problem = if problem.is_null() {
+                    "未知错误".into()
+                } else {
+                    CStr::from_ptr(problem.cast()).to_string_lossy()
+                };
+                return Err(Malformed(format!("{}（第 {} 行第 {} 列）", problem, mark.line + 1, mark.column + 1)).into());
+            }
+            let event = event.assume_init_mut();
+            let converted = match event.type_ {
+                YAML_SCALAR_EVENT => {
+                    let scalar = &event.data.scalar;
+                    let value = std::slice::from_raw_parts(scalar.value, scalar.length as usize);
+                    let tag = (!scalar.tag.is_null())
+                        .then(|| CStr::from_ptr(scalar.tag.cast()).to_string_lossy().into_owned());
+                    YamlEvent::Scalar(YamlScalar {
+                        value: String::from_utf8_lossy(value).into_owned(),
+                        range: event.start_mark.index as usize..event.end_mark.index as usize,
+                        style: scalar.style,
+                        tag,
+                    })
+                }
+                YAML_MAPPING_START_EVENT => YamlEvent::CollectionStart {
+                    mapping: true,
+                    flow: event.data.mapping_start.style == YAML_FLOW_MAPPING_STYLE,
+                },
+                YAML_SEQUENCE_START_EVENT => YamlEvent::CollectionStart {
+                    mapping: false,
+                    flow: event.data.sequence_start.style == YAML_FLOW_SEQUENCE_STYLE,
+                },
+                YAML_MAPPING_END_EVENT | YAML_SEQUENCE_END_EVENT => YamlEvent::CollectionEnd,
+                YAML_ALIAS_EVENT => YamlEvent::Alias,
+                _ => YamlEvent::Boundary,
+            };
+            let done = event.type_ == YAML_STREAM_END_EVENT;
+            yaml_event_delete(event);
+            events.push(converted);
+            if done {
+                break;
+            }
+        }
+    }
+    Ok(events)
+}
+
+/// 事件流中当前所在的集合
+struct YamlFrame {
+    node: YamlNode,
+    flow: bool,
+    /// 位于复合键（以映射 / 序列作为键）内部，其中的标量不替换
+    in_key: bool,
+}
+
+enum YamlNode {
+    /// 当前键；`None` 表示下一个节点是键
+    Mapping(Option<String>),
+    /// 当前元素下标
+    Sequence(usize),
+}
+
+impl YamlFrame {
+    fn expects_key(&self) -> bool {
+        matches!(self.node, YamlNode::Mapping(None))
+    }
+
+    /// 当前节点结束：映射在键与值之间切换，序列下标递增
+    fn advance(&mut self, key: Option<String>) {
+        match &mut self.node {
+            YamlNode::Mapping(current @ None) => *current = Some(key.unwrap_or_default()),
+            YamlNode::Mapping(current) => *current = None,
+            YamlNode::Sequence(index) => *index += 1,
+        }
+    }
+}
+
+/// 需要检查的标量值
+struct YamlTarget {
+    scalar: YamlScalar,
+    /// 值本身的字节区间（不含锚点与标签）
+    range: Range<usize>,
+    forced: Option<EntityType>,
+    flow: bool,
+}
+
+/// 未加引号的标量按 YAML 1.2 core schema 解析出的类型
+enum PlainKind {
+    Null,
+    /// 数字 / 布尔值
+    Scalar,
+    String,
+}
+
+fn plain_kind(text: &str) -> PlainKind {
+    // 只有这些字符开头的写法可能解析为 null / 数字 / 布尔值
+    if !text.is_empty() && !text.starts_with(|c: char| c.is_ascii_digit() || "+-.~nNtTfF".contains(c)) {
+        return PlainKind::String;
+    }
+    match serde_yaml::from_str::<YamlValue>(text) {
+        Ok(YamlValue::Null) => PlainKind::Null,
+        Ok(YamlValue::Number(_) | YamlValue::Bool(_)) => PlainKind::Scalar,
+        _ => PlainKind::String,
+    }
+}
+
+/// 跳过标量前的锚点（`&a`）与标签（`!!str`），返回值本身的起点（`&` `!` 不能作为标量的首字符）
+fn skip_properties(data: &[u8], range: &Range<usize>) -> usize {
+    let mut pos = range.start;
+    while pos < range.end && matches!(data[pos], b'&' | b'!') {
+        while pos < range.end && !data[pos].is_ascii_whitespace() {
+            pos += 1;
+        }
+        while pos < range.end && data[pos].is_ascii_whitespace() {
+            pos += 1;
+        }
+    }
+    pos
+}
+
+/// 脱敏 YAML 文本：按解析器事件给出的位置原位替换标量，注释按纯文本识别，
+/// 引号风格、锚点与文档分隔符原样保留；无法解析时返回 `Malformed`
+pub fn mask_yaml(
+    data: &[u8],
+    engine: &HybridEngine,
+    options: &StructuredOptions,
+    mut sink: impl FnMut(&[u8]) -> Result<()>,
+) -> Result<(u64, Vec<EntitySpanBrief>)> {
+    let edits = yaml_edits(data, engine, &StructuredRules::new(options))?;
+    let (mut output, mut entities) = (Vec::with_capacity(data.len()), Vec::new());
+    EditWriter::new(data).write(engine, edits, data.len(), &mut output, &mut entities);
+    sink(&output)?;
+    Ok((bytecount::count(data, b'\n') as u64, entities))
+}
+
+/// YAML 流中的标量与注释替换（按位置排序）
+fn yaml_edits(data: &[u8], engine: &HybridEngine, rules: &StructuredRules) -> Result<Vec<Edit>> {
+    let mut stack: Vec<YamlFrame> = Vec::new();
+    let mut targets = Vec::new();
+    // 所有标量（含键）的源文本区间，用于区分注释与标量内的 `#`
+    let mut scalars = Vec::new();
+    for event in yaml_events(data)? {
+        match event {
+            YamlEvent::CollectionStart { mapping, flow } => {
+                let parent = stack.last();
+                let in_key = parent.is_some_and(|f| f.in_key || f.expects_key());
+                let flow = flow || parent.is_some_and(|f| f.flow);
+                let node = if mapping { YamlNode::Mapping(None) } else { YamlNode::Sequence(0) };
+                stack.push(YamlFrame { node, flow, in_key });
+            }
+            YamlEvent::CollectionEnd => {
+                stack.pop();
+                if let Some(frame) = stack.last_mut() {
+                    frame.advance(None);
+                }
+            }
+            YamlEvent::Alias => {
+                if let Some(frame) = stack.last_mut() {
+                    frame.advance(None);
+                }
+            }
+            YamlEvent::Scalar(scalar) => {
+                scalars.push(scalar.range.clone());
+                let Some(frame) = stack.last() else {
+                    // 文档根是单个标量
+                    let range = skip_properties(data, &scalar.range)..scalar.range.end;
+                    targets.push(YamlTarget { forced: rules.forced(&[]), scalar, range, flow: false });
+                    continue;
+                };
+                if frame.expects_key() {
+                    let key = scalar.value;
+                    stack.last_mut().unwrap().advance(Some(key));
+                    continue;
+                }
+                if !frame.in_key {
+                    let path: Vec<PathElem> = stack
+                        .iter()
+                        .filter_map(|f| match &f.node {
+                            YamlNode::Mapping(key) => key.clone().map(PathElem::Key),
+                            YamlNode::Sequence(index) => Some(PathElem::Index(*index)),
+                        })
+                        .collect();
+                    let range = skip_properties(data, &scalar.range)..scalar.range.end;
+                    targets.push(YamlTarget { forced: rules.forced(&path), scalar, range, flow: frame.flow });
+                }
+                stack.last_mut().unwrap().advance(None);
+            }
+            YamlEvent::Boundary => {}
+        }
+    }
+
+    let mut edits: Vec<Edit> = targets.par_iter().filter_map(|target| mask_yaml_scalar(engine, data, target)).collect();
+    edits.par_extend(yaml_comments(data, &scalars).into_par_iter().filter_map(|range| {
+        let spans: Vec<EntitySpan> = engine.detect(&data[range.clone()]).into_iter().filter(|s| s.start < s.end).collect();
+        if spans.is_empty() {
+            return None;
+        }
+        let bytes = engine.mask_spans(&data[range.clone()], &spans);
+        let spans = spans
+            .into_iter()
+            .map(|span| {
+                let source = span.start..span.end;
+                (span, source)
+            })
+            .collect();
+        Some(Edit { range: 0..range.len(), bytes, spans }.shifted(range.start))
+    }));
+    edits.sort_by_key(|edit| edit.range.start);
+    Ok(edits)
+}
+
+/// 注释区间：`#` 位于行首或空白之后且不在任何标量内，到行尾为止（`scalars` 按位置排序）
+fn yaml_comments(data: &[u8], scalars: &[Range<usize>]) -> Vec<Range<usize>> {
+    let mut comments = Vec::new();
+    let mut pos = 0;
+    for (gap_end, next) in scalars.iter().map(|r| (r.start, r.end)).chain([(data.len(), data.len())]) {
+        while pos < gap_end
+            && let Some(i) = memchr::memchr(b'#', &data[pos..gap_end])
+        {
+            let hash = pos + i;
+            let line_end = memchr::memchr(b'\n', &data[hash..]).map_or(data.len(), |j| hash + j);
+            if hash == 0 || data[hash - 1].is_ascii_whitespace() {
+                let end = if line_end > hash && data[line_end - 1] == b'\r' { line_end - 1 } else { line_end };
+                comments.push(hash..end);
+                pos = line_end;
+            } else {
+                pos = hash + 1;
+            }
+        }
+        pos = pos.max(next);
+    }
+    comments
+}
+
+/// 单个标量的替换
+fn mask_yaml_scalar(engine: &HybridEngine, data: &[u8], target: &YamlTarget) -> Option<Edit> {
+    let scalar = &target.scalar;
+    let raw = &data[target.range.clone()];
+    let kind = if scalar.style != YAML_PLAIN_SCALAR_STYLE || scalar.tag.as_deref() == Some(YAML_STR_TAG) {
+        PlainKind::String
+    } else {
+        plain_kind(&scalar.value)
+    };
+    match kind {
+        PlainKind::Null => None,
+        PlainKind::Scalar => {
+            let span = whole_span(target.forced.clone()?, raw.len());
+            let bytes = render_yaml_scalar(raw, scalar, &engine.resolve_mask(&span), target.flow);
+            Some(Edit { range: target.range.clone(), bytes, spans: vec![(span, target.range.clone())] })
+        }
+        PlainKind::String => {
+            let spans = value_spans(engine, &scalar.value, target.forced.as_ref());
+            if spans.is_empty() {
+                return None;
+            }
+            let masked = engine.mask_spans(scalar.value.as_bytes(), &spans);
+            let bytes = render_yaml_scalar(raw, scalar, &String::from_utf8_lossy(&masked), target.flow);
+            let mut cursor = 0;
+            let spans = spans
+                .into_iter()
+                .map(|span| {
+                    let source = yaml_source_range(raw, scalar, &span, &mut cursor);
+                    (span, target.range.start + source.start..target.range.start + source.end)
+                })
+                .collect();
+            Some(Edit { range: target.range.clone(), bytes, spans })
+        }
+    }
+}
+
+/// 值内偏移 → 标量原文内区间：原文与值逐字相同（单行且无转义）时直接换算，
+/// 否则在原文中按顺序查找实体原文；找不到时定位到标量起点（空区间）
+fn yaml_source_range(raw: &[u8], scalar: &YamlScalar, span: &EntitySpan, cursor: &mut usize) -> Range<usize> {
+    let value = scalar.value.as_bytes();
+    let quoted = matches!(scalar.style, YAML_SINGLE_QUOTED_SCALAR_STYLE | YAML_DOUBLE_QUOTED_SCALAR_STYLE);
+    let inner = if quoted && raw.len() >= 2 { 1..raw.len() - 1 } else { 0..raw.len() };
+    if (scalar.style == YAML_PLAIN_SCALAR_STYLE || quoted) && &raw[inner.clone()] == value {
+        return inner.start + span.start..inner.start + span.end;
+    }
+    let text = &value[span.start..span.end];
+    match memchr::memmem::find(&raw[*cursor..], text) {
+        Some(i) if !text.is_empty() => {
+            let start = *cursor + i;
+            *cursor = start + text.len();
+            start..*cursor
+        }
+        _ => 0..0,
+    }
+}
+
+/// 按原标量的写法输出替换后的值；原写法放不下时改用双引号（JSON 字符串同时是合法的 YAML 双引号标量）
+fn render_yaml_scalar(raw: &[u8], scalar: &YamlScalar, text: &str, flow: bool) -> Vec<u8> {
+    let rendered = match scalar.style {
+        YAML_PLAIN_SCALAR_STYLE if plain_safe(text, flow) => Some(text.as_bytes().to_vec()),
+        YAML_SINGLE_QUOTED_SCALAR_STYLE if !text.contains('\n') => {
+            Some(format!("'{}'", text.replace('\'', "''")).into_bytes())
+        }
+        YAML_LITERAL_SCALAR_STYLE | YAML_FOLDED_SCALAR_STYLE => render_block(raw, &scalar.value, text),
+        _ => None,
+    };
+    rendered.unwrap_or_else(|| serde_json::to_vec(text).unwrap_or_default())
+}
+
+/// 替换后仍可不加引号：单行、首尾无空白、解析回来仍是同一个字符串，流式集合内不含 `,[]{}`
+fn plain_safe(text: &str, flow: bool) -> bool {
+    let single_line = !text.is_empty() && !text.contains('\n') && text.trim() == text;
+    let flow_safe = !flow || !text.contains([',', '[', ']', '{', '}']);
+    single_line
+        && flow_safe
+        && matches!(serde_yaml::from_str::<YamlValue>(text), Ok(YamlValue::String(s)) if s == text)
+}
+
+/// 块标量（`|` / `>`）：保留头部（折叠块改为字面块，保证换行不被重新折叠）、缩进与末尾空行，逐行写出替换后的值
+fn render_block(raw: &[u8], value: &str, text: &str) -> Option<Vec<u8>> {
+    let header_end = memchr::memchr(b'\n', raw)? + 1;
+    let body = &raw[header_end..];
+    let content_len = body.iter().rposition(|b| !b.is_ascii_whitespace()).map_or(0, |i| i + 1);
+    let content_len = if body.get(content_len) == Some(&b'\n') { content_len + 1 } else { content_len };
+    let (content, tail) = body.split_at(content_len);
+
+    // 缩进 = 原文首个非空行的前导空格 - 值中对应行的前导空格
+    let leading = |line: &[u8]| line.iter().take_while(|&&b| b == b' ').count();
+    let raw_first = content.split(|&b| b == b'\n').find(|l| !l.iter().all(u8::is_ascii_whitespace))?;
+    let value_first = value.split('\n').find(|l| !l.trim().is_empty())?;
+    let indent = leading(raw_first).checked_sub(leading(value_first.as_bytes()))?;
+
+    let mut out = raw[..header_end].to_vec();
+    out[0] = b'|';
+    for (i, line) in text.trim_end_matches('\n').split('\n').enumerate() {
+        if i > 0 {
+            out.push(b'\n');
+        }
+        if !line.is_empty() {
+            out.extend(std::iter::repeat_n(b' ', indent));
+            out.extend_from_slice(line.as_bytes());
+        }
+    }
+    if content.ends_with(b"\n") {
+        out.push(b'\n');
+    }
+    out.extend_from_slice(tail);
+    Some(out)
+}
+
+// ─────────────────────────────────────────────────────────────────────────────
+// 扫描
+// ─────────────────────────────────────────────────────────────────────────────
+
+/// 扫描结构化文本：命中与脱敏时替换的实体一致（键名规则与选择器命中的值整体计为一处），偏移相对 `data`；
+/// YAML 无法解析时返回 `Malformed`
+pub fn scan_structured(
+    data: &[u8],
+    format: StructuredFormat,
+    file: &str,
+    engine: &HybridEngine,
+    options: &StructuredOptions,
+    max_samples: usize,
+) -> Result<ScanReport> {
+    let rules = StructuredRules::new(options);
+    let by_lines = || -> Vec<Edit> {
+        let lines: Vec<Range<usize>> = line_ranges(data).collect();
+        lines.par_iter().flat_map_iter(|line| json_line_edits(data, line.clone(), engine, &rules)).collect()
+    };
+    let edits = match format {
+        StructuredFormat::Json => match json_document_edits(data, engine, &rules) {
+            Err(e) if e.is::<Malformed>() => by_lines(),
+            other => other?,
+        },
+        StructuredFormat::JsonLines => by_lines(),
+        StructuredFormat::Yaml => yaml_edits(data, engine, &rules)?,
+    };
+
+    let mut report = ScanReport::new(max_samples);
+    report.bytes_scanned = data.len() as u64;
+    report.lines_scanned = count_lines(data);
+    let mut tracker = LineTracker::new(data);
+    for (span, source) in edits.into_iter().flat_map(|edit| edit.spans) {
+        let mut finding = span_to_finding(&EntitySpan { start: source.start, end: source.end, ..span }, data, file, engine);
+        (finding.line, finding.column) = tracker.locate(source.start);
+        report.add_finding(finding);
+    }
+    Ok(report)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::rules::Rule;
+
+    fn engine() -> HybridEngine {
+        HybridEngine::from_rules(vec![Rule {
+            name: "email".into(),
+            pattern: r"[a-z0-9.]+@[a-z0-9.]+\.[a-z]+".into(),
+            mask: "<EMAIL>".into(),
+            priority: 10,
+            enabled: true,
+            is_custom: false,
+            case_insensitive: false,
+            whole_word: false,
+            unicode_boundary: false,
+            group: String::new(),
+        }])
+    }
+
+    fn run(format: StructuredFormat, input: &str, options: &StructuredOptions) -> (String, Vec<EntitySpanBrief>) {
+        let engine = engine();
+        let mut out = Vec::new();
+        let sink = |bytes: &[u8]| {
+            out.extend_from_slice(bytes);
+            Ok(())
+        };
+        let cancel = AtomicBool::new(false);
+        let (_, entities) = match format {
+            StructuredFormat::Json => mask_json(input.as_bytes(), &engine, options, &cancel, |_| {}, sink),
+            StructuredFormat::JsonLines => mask_json_lines(input.as_bytes(), &engine, options, &cancel, |_| {}, sink),
+            StructuredFormat::Yaml => mask_yaml(input.as_bytes(), &engine, options, sink),
+        }
+        .unwrap();
+        (String::from_utf8(out).unwrap(), entities)
+    }
+
+    #[test]
+    fn test_selector_parsing_and_matching() {
+        let steps = parse_selector("$.user.*['e-mail']").unwrap();
+        let path = |elems: &[&str]| -> Vec<PathElem> {
+            elems
+                .iter()
+                .map(|e| e.parse().map(PathElem::Index).unwrap_or_else(|_| PathElem::Key(e.to_string())))
+                .collect()
+        };
+        assert!(selector_matches(&steps, &path(&["user", "0", "e-mail"])));
+        assert!(selector_matches(&steps, &path(&["user", "bob", "e-mail", "home"])));
+        assert!(!selector_matches(&steps, &path(&["admin", "0", "e-mail"])));
+
+        let recursive = parse_selector("$..token").unwrap();
+        assert!(selector_matches(&recursive, &path(&["a", "1", "token"])));
+        assert!(selector_matches(&parse_selector("$.items[1]").unwrap(), &path(&["items", "1", "x"])));
+        assert!(parse_selector("user.email").is_err());
+        assert!(parse_selector("$.items[x]").is_err());
+    }
+
+    #[test]
+    fn test_json_masks_string_values_and_keeps_layout() {
+        let input = "{\n  \"a@x.com\": \"mail \\\"a@x.com\\\"\",\n  \"db_password\": 12345,\n  \"n\": null,\n  \"list\": [\"b@y.org\", 7]\n}\n";
+        let (out, entities) = run(StructuredFormat::Json, input, &StructuredOptions::default());
+        assert_eq!(
+            out,
+            "{\n  \"a@x.com\": \"mail \\\"<EMAIL>\\\"\",\n  \"db_password\": \"<PASSWORD>\",\n  \"n\": null,\n  \"list\": [\"<EMAIL>\", 7]\n}\n"
+        );
+        serde_json::from_str::<serde_json::Value>(&out).unwrap();
+        let positions: Vec<_> = entities.iter().map(|e| (e.line, e.column)).collect();
+        assert_eq!(positions, vec![(Some(2), Some(22)), (Some(3), Some(18)), (Some(5), Some(13))]);
+        let values: Vec<_> = entities.iter().map(|e| &input[e.start..e.end]).collect();
+        assert_eq!(values, vec!["a@x.com", "12345", "b@y.org"]);
+    }
+
+    #[test]
+    fn test_key_rules_match_plural_keys() {
+        let input = r#"{"credentials":{"user":"u1"},"API_TOKENS":["t1"],"db-passwords":"p1","status":"ok"}"#;
+        let (out, _) = run(StructuredFormat::Json, input, &StructuredOptions::default());
+        assert_eq!(
+            out,
+            r#"{"credentials":{"user":"<credential>"},"API_TOKENS":["<API_KEY>"],"db-passwords":"<PASSWORD>","status":"ok"}"#
+        );
+    }
+
+    #[test]
+    fn test_json_offsets_skip_escapes() {
+        let input = r#"{"m": "\u00e9\ud83d\ude00 \"x\" a@x.com"}"#;
+        let (_, entities) = run(StructuredFormat::Json, input, &StructuredOptions::default());
+        assert_eq!(&input[entities[0].start..entities[0].end], "a@x.com");
+        assert_eq!(entities[0].column, Some(entities[0].start as u64 + 1));
+    }
+
+    #[test]
+    fn test_json_selectors_mask_subtrees() {
+        let options = StructuredOptions { key_rules: Vec::new(), paths: vec!["$.user.*.email".into()] };
+        let input = r#"{"user":{"alice":{"email":"not-an-address","age":3},"bob":{"email":["x","y"]}},"email":"keep"}"#;
+        let (out, _) = run(StructuredFormat::Json, input, &options);
+        assert_eq!(
+            out,
+            r#"{"user":{"alice":{"email":"<EMAIL>","age":3},"bob":{"email":["<EMAIL>","<EMAIL>"]}},"email":"keep"}"#
+        );
+    }
+
+    #[test]
+    fn test_json_lines_fall_back_per_line() {
+        let input = "{\"token\":\"abc\"}\r\nnot json a@x.com\n\n{\"m\":\"b@y.org\"}";
+        let (out, entities) = run(StructuredFormat::JsonLines, input, &StructuredOptions::default());
+        assert_eq!(out, "{\"token\":\"<API_KEY>\"}\r\nnot json <EMAIL>\n\n{\"m\":\"<EMAIL>\"}");
+        let positions: Vec<_> = entities.iter().map(|e| (e.line, e.column)).collect();
+        assert_eq!(positions, vec![(Some(1), Some(11)), (Some(2), Some(10)), (Some(4), Some(7))]);
+        let values: Vec<_> = entities.iter().map(|e| &input[e.start..e.end]).collect();
+        assert_eq!(values, vec!["abc", "a@x.com", "b@y.org"]);
+
+        // 多个顶层文档的 .json 按行处理
+        let (out, _) = run(StructuredFormat::Json, "{\"a\":\"a@x.com\"}\n{\"b\":1}\n", &StructuredOptions::default());
+        assert_eq!(out, "{\"a\":\"<EMAIL>\"}\n{\"b\":1}\n");
+    }
+
+    #[test]
+    fn test_yaml_preserves_key_order() {
+        let input = "zeta: a@x.com\nalpha:\n  secret: 42\n  note: \"hi\"\n---\n- b@y.org\n";
+        let (out, entities) = run(StructuredFormat::Yaml, input, &StructuredOptions::default());
+        assert_eq!(out, "zeta: <EMAIL>\nalpha:\n  secret: <API_KEY>\n  note: \"hi\"\n---\n- <EMAIL>\n");
+        let positions: Vec<_> = entities.iter().map(|e| (&input[e.start..e.end], e.line, e.column)).collect();
+        assert_eq!(positions, vec![("a@x.com", Some(1), Some(7)), ("42", Some(3), Some(11)), ("b@y.org", Some(6), Some(3))]);
+
+        let engine = engine();
+        let err = mask_yaml(b"a: [1, 2", &engine, &StructuredOptions::default(), |_| Ok(())).unwrap_err();
+        assert!(err.is::<Malformed>());
+    }
+
+    #[test]
+    fn test_yaml_patches_scalars_in_place() {
+        let options = StructuredOptions { key_rules: vec!["password".into()], ..StructuredOptions::default() };
+        let input = "# owner: a@x.com\nowner: &o 'a@x.com' # primary\nbackup: *o\ndb:\n  password: \"hunter2\"\n  hosts: [a@x.com, h2]\nnote: |\n  ping a@x.com\n    then b@y.org\n\nfolded: >-\n  mail a@x.com\n  now\nport: 5432\n";
+        let (out, entities) = run(StructuredFormat::Yaml, input, &options);
+        assert_eq!(
+            out,
+            "# owner: <EMAIL>\nowner: &o '<EMAIL>' # primary\nbackup: *o\ndb:\n  password: \"<PASSWORD>\"\n  hosts: [<EMAIL>, h2]\nnote: |\n  ping <EMAIL>\n    then <EMAIL>\n\nfolded: |-\n  mail <EMAIL> now\nport: 5432\n"
+        );
+        let values: Vec<_> = entities.iter().map(|e| (&input[e.start..e.end], e.line)).collect();
+        assert_eq!(
+            values,
+            vec![
+                ("a@x.com", Some(1)),
+                ("a@x.com", Some(2)),
+                ("hunter2", Some(5)),
+                ("a@x.com", Some(6)),
+                ("a@x.com", Some(8)),
+                ("b@y.org", Some(9)),
+                ("a@x.com", Some(12)),
+            ]
+        );
+        assert_eq!(serde_yaml::from_str::<YamlValue>(&out).unwrap()["backup"], YamlValue::String("<EMAIL>".into()));
+    }
+}
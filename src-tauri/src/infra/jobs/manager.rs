@@ -0,0 +1,640 @@
+use crate::common::errors::{AppError, AppResult};
+use crate::common::state::{SharedEngine, SharedSettings};
+use crate::infra::fs::processor::{self, Cancelled};
+use anyhow::Context;
+use log::{error, info, warn};
+use parking_lot::{Condvar, Mutex};
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, VecDeque};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::Duration;
+
+/// 默认并发任务数
+pub const DEFAULT_CONCURRENCY: usize = 2;
+
+/// 历史中保留的已结束任务上限
+const MAX_FINISHED_JOBS: usize = 200;
+
+/// 自动重试前的等待（按重试次数线性递增）
+const RETRY_BACKOFF: Duration = Duration::from_millis(500);
+
+/// 进度事件的最小推进幅度（避免事件风暴）
+const PROGRESS_STEP: f64 = 0.01;
+
+/// 任务状态
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum JobStatus {
+    Queued,
+    Running,
+    Completed,
+    Failed,
+    Cancelled,
+}
+
+impl JobStatus {
+    /// 是否已结束
+    pub fn is_finished(self) -> bool {
+        matches!(self, Self::Completed | Self::Failed | Self::Cancelled)
+    }
+}
+
+/// 文件脱敏任务
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Job {
+    pub id: String,
+    pub input_path: String,
+    pub output_path: String,
+    pub status: JobStatus,
+    /// 0.0 ~ 1.0
+    pub progress: f64,
+    /// 已执行次数（含重试）
+    pub attempts: u32,
+    /// 自动重试上限
+    pub max_retries: u32,
+    /// 最近一次失败原因
+    pub error: Option<String>,
+    pub created_at: String,
+    pub started_at: Option<String>,
+    pub finished_at: Option<String>,
+    pub processed_bytes: u64,
+    pub entity_count: usize,
+}
+
+fn now() -> String {
+    chrono::Local::now().to_rfc3339()
+}
+
+/// 任务表（受同一把锁保护）
+#[derive(Default)]
+struct JobTable {
+    /// 全部任务（按提交顺序）
+    jobs: Vec<Job>,
+    /// 待执行的任务 ID
+    queue: VecDeque<String>,
+    /// 运行中任务的取消标记
+    cancel_flags: HashMap<String, Arc<AtomicBool>>,
+}
+
+impl JobTable {
+    fn get_mut(&mut self, id: &str) -> Option<&mut Job> {
+        self.jobs.iter_mut().find(|j| j.id == id)
+    }
+
+    /// 只保留最近的已结束任务
+    fn trim_history(&mut self) {
+        let finished = self.jobs.iter().filter(|j| j.status.is_finished()).count();
+        let mut excess = finished.saturating_sub(MAX_FINISHED_JOBS);
+        self.jobs.retain(|j| {
+            if excess > 0 && j.status.is_finished() {
+                excess -= 1;
+                false
+            } else {
+                true
+            }
+        });
+    }
+}
+
+type UpdateCallback = Box<dyn Fn(&Job) + Send + Sync>;
+
+struct Inner {
+    table: Mutex<JobTable>,
+    wakeup: Condvar,
+    /// 置位后空闲的工作线程退出（运行中的任务执行完再退出）
+    shutdown: AtomicBool,
+    store_path: PathBuf,
+    engine: SharedEngine,
+    /// 每次尝试开始时从中取文件处理选项快照
+    settings: SharedSettings,
+    on_update: UpdateCallback,
+}
+
+/// 后台任务管理器
+///
+/// 可随意克隆；最后一个克隆被释放时通知工作线程退出。
+#[derive(Clone)]
+pub struct JobManager {
+    inner: Arc<Inner>,
+    _shutdown: Arc<ShutdownGuard>,
+}
+
+/// 随最后一个 `JobManager` 释放，唤醒并停止工作线程（工作线程只持有 `Inner`）
+struct ShutdownGuard(Arc<Inner>);
+
+impl Drop for ShutdownGuard {
+    fn drop(&mut self) {
+        // 持锁置位，避免工作线程检查标记与进入等待之间丢失唤醒
+        let table = self.0.table.lock();
+        self.0.shutdown.store(true, Ordering::Relaxed);
+        drop(table);
+        self.0.wakeup.notify_all();
+    }
+}
+
+impl JobManager {
+    /// 创建任务管理器并启动 `concurrency` 个工作线程
+    ///
+    /// `store_path` 为任务历史文件；上次退出时未完成的任务会重新入队。
+    pub fn start(
+        store_path: PathBuf,
+        engine: SharedEngine,
+        settings: SharedSettings,
+        concurrency: usize,
+        on_update: impl Fn(&Job) + Send + Sync + 'static,
+    ) -> Self {
+        let manager = Self::new(store_path, engine, settings, on_update);
+        manager.spawn_workers(concurrency);
+        manager
+    }
+
+    /// 创建任务管理器（不启动工作线程）
+    fn new(
+        store_path: PathBuf,
+        engine: SharedEngine,
+        settings: SharedSettings,
+        on_update: impl Fn(&Job) + Send + Sync + 'static,
+    ) -> Self {
+        let mut table = JobTable {
+            jobs: load_jobs(&store_path),
+            ..Default::default()
+        };
+        for job in table.jobs.iter_mut().filter(|j| !j.status.is_finished()) {
+            job.status = JobStatus::Queued;
+            job.progress = 0.0;
+            table.queue.push_back(job.id.clone());
+        }
+        if !table.queue.is_empty() {
+            info!("📋 恢复 {} 个未完成的文件任务", table.queue.len());
+        }
+
+        let inner = Arc::new(Inner {
+            table: Mutex::new(table),
+            wakeup: Condvar::new(),
+            shutdown: AtomicBool::new(false),
+            store_path,
+            engine,
+            settings,
+            on_update: Box::new(on_update),
+        });
+        Self { _shutdown: Arc::new(ShutdownGuard(inner.clone())), inner }
+    }
+
+    fn spawn_workers(&self, concurrency: usize) {
+        for i in 0..concurrency.max(1) {
+            let inner = self.inner.clone();
+            let spawned = std::thread::Builder::new()
+                .name(format!("safemask-job-{}", i))
+                .spawn(move || worker_loop(inner));
+            if let Err(e) = spawned {
+                error!("❌ 任务工作线程启动失败: {}", e);
+            }
+        }
+    }
+
+    /// 提交任务
+    pub fn submit(&self, input_path: &Path, output_path: &Path, max_retries: u32) -> Job {
+        let job = Job {
+            id: uuid::Uuid::new_v4().to_string(),
+            input_path: input_path.to_string_lossy().to_string(),
+            output_path: output_path.to_string_lossy().to_string(),
+            status: JobStatus::Queued,
+            progress: 0.0,
+            attempts: 0,
+            max_retries,
+            error: None,
+            created_at: now(),
+            started_at: None,
+            finished_at: None,
+            processed_bytes: 0,
+            entity_count: 0,
+        };
+        {
+            let mut table = self.inner.table.lock();
+            table.jobs.push(job.clone());
+            table.queue.push_back(job.id.clone());
+            self.inner.persist(&table);
+        }
+        self.inner.wakeup.notify_one();
+        (self.inner.on_update)(&job);
+        job
+    }
+
+    /// 全部任务（最新提交的在前）
+    pub fn list(&self) -> Vec<Job> {
+        self.inner.table.lock().jobs.iter().rev().cloned().collect()
+    }
+
+    /// 查询单个任务
+    pub fn get(&self, id: &str) -> Option<Job> {
+        self.inner.table.lock().jobs.iter().find(|j| j.id == id).cloned()
+    }
+
+    /// 取消任务
+    ///
+    /// 排队中的任务立即取消；运行中的任务在下一个检查点停止。
+    pub fn cancel(&self, id: &str) -> AppResult<Job> {
+        let mut table = self.inner.table.lock();
+        // 工作线程写入最终状态后才移除取消标志，期间的任务同样视为已结束
+        let finished = table.jobs.iter().find(|j| j.id == id).is_some_and(|j| j.status.is_finished());
+        if finished {
+            return Err(AppError::Config("任务已结束，无法取消".into()));
+        }
+        if let Some(flag) = table.cancel_flags.get(id) {
+            flag.store(true, Ordering::Relaxed);
+            return table
+                .jobs
+                .iter()
+                .find(|j| j.id == id)
+                .cloned()
+                .ok_or_else(|| AppError::Config(format!("任务不存在: {}", id)));
+        }
+
+        table.queue.retain(|q| q != id);
+        let job = table
+            .get_mut(id)
+            .ok_or_else(|| AppError::Config(format!("任务不存在: {}", id)))?;
+        job.status = JobStatus::Cancelled;
+        job.finished_at = Some(now());
+        let job = job.clone();
+        self.inner.persist(&table);
+        drop(table);
+        (self.inner.on_update)(&job);
+        Ok(job)
+    }
+
+    /// 将已结束的任务重新入队（重置进度与重试计数）
+    pub fn requeue(&self, id: &str) -> AppResult<Job> {
+        let mut table = self.inner.table.lock();
+        let job = table
+            .get_mut(id)
+            .ok_or_else(|| AppError::Config(format!("任务不存在: {}", id)))?;
+        if !job.status.is_finished() {
+            return Err(AppError::Config("任务尚未结束，无法重新入队".into()));
+        }
+        job.status = JobStatus::Queued;
+        job.progress = 0.0;
+        job.attempts = 0;
+        job.error = None;
+        job.started_at = None;
+        job.finished_at = None;
+        let job = job.clone();
+        table.queue.push_back(job.id.clone());
+        self.inner.persist(&table);
+        drop(table);
+        self.inner.wakeup.notify_one();
+        (self.inner.on_update)(&job);
+        Ok(job)
+    }
+}
+
+impl Inner {
+    /// 持久化任务历史（失败只记录日志，不影响任务执行）
+    fn persist(&self, table: &JobTable) {
+        let result = serde_json::to_string_pretty(&table.jobs)
+            .map_err(|e| AppError::Internal(format!("任务序列化失败: {}", e)))
+            .and_then(|json| {
+                if let Some(parent) = self.store_path.parent() {
+                    fs::create_dir_all(parent)?;
+                }
+                let tmp_path = self.store_path.with_extension("json.tmp");
+                fs::write(&tmp_path, json.as_bytes())?;
+                // rename 会原子替换已存在的目标文件，不能先删除（否则崩溃时丢失全部历史）
+                fs::rename(&tmp_path, &self.store_path)?;
+                Ok(())
+            });
+        if let Err(e) = result {
+            warn!("⚠️ 任务历史保存失败: {}", e);
+        }
+    }
+
+    /// 修改任务并通知（`persist` 为 false 时只通知，用于高频进度）
+    fn update(&self, id: &str, persist: bool, f: impl FnOnce(&mut Job)) -> Option<Job> {
+        let mut table = self.table.lock();
+        let job = table.get_mut(id)?;
+        f(job);
+        let job = job.clone();
+        if persist {
+            if job.status.is_finished() {
+                table.trim_history();
+            }
+            self.persist(&table);
+        }
+        drop(table);
+        (self.on_update)(&job);
+        Some(job)
+    }
+}
+
+fn load_jobs(path: &Path) -> Vec<Job> {
+    if !path.exists() {
+        return vec![];
+    }
+    match fs::read_to_string(path).map(|s| serde_json::from_str::<Vec<Job>>(&s)) {
+        Ok(Ok(jobs)) => jobs,
+        Ok(Err(e)) => {
+            error!("解析任务历史失败: {}", e);
+            vec![]
+        }
+        Err(e) => {
+            error!("读取任务历史失败: {}", e);
+            vec![]
+        }
+    }
+}
+
+/// 工作线程：取出排队任务并执行，管理器释放后退出
+fn worker_loop(inner: Arc<Inner>) {
+    loop {
+        let (id, cancel) = {
+            let mut table = inner.table.lock();
+            let id = loop {
+                if inner.shutdown.load(Ordering::Relaxed) {
+                    return;
+                }
+                if let Some(id) = table.queue.pop_front() {
+                    break id;
+                }
+                inner.wakeup.wait(&mut table);
+            };
+            let cancel = Arc::new(AtomicBool::new(false));
+            table.cancel_flags.insert(id.clone(), cancel.clone());
+            (id, cancel)
+        };
+
+        run_job(&inner, &id, cancel);
+        inner.table.lock().cancel_flags.remove(&id);
+    }
+}
+
+/// 任务的临时输出路径（与输出同目录，成功后改名覆盖输出；文件名带任务 ID，避免同名输出的任务互相覆盖）
+fn partial_output_path(output: &Path, id: &str) -> PathBuf {
+    let name = output.file_name().map(|n| n.to_string_lossy().to_string()).unwrap_or_default();
+    output.with_file_name(format!("{}.{}.tmp", name, id))
+}
+
+/// 执行任务（含自动重试）
+///
+/// 每次尝试先写临时文件，成功后改名为输出文件；失败或取消只清理临时文件，已存在的输出文件保持不变。
+fn run_job(inner: &Arc<Inner>, id: &str, cancel: Arc<AtomicBool>) {
+    loop {
+        let Some(job) = inner.update(id, true, |job| {
+            job.status = JobStatus::Running;
+            job.attempts += 1;
+            job.progress = 0.0;
+            job.started_at = Some(now());
+        }) else {
+            return;
+        };
+
+        // 每次尝试使用最新的引擎与处理选项快照，尝试期间修改设置不影响本次处理
+        let engine = inner.engine.read().clone();
+        let options = inner.settings.read().process_options();
+        let progress_inner = inner.clone();
+        let progress_id = id.to_string();
+        let last_reported = Mutex::new(0.0f64);
+        let output = Path::new(&job.output_path);
+        let partial = partial_output_path(output, id);
+        let result = processor::process_file_cancellable(
+            Path::new(&job.input_path),
+            partial.as_path(),
+            &engine,
+            &options,
+            cancel.clone(),
+            move |progress| {
+                let mut last = last_reported.lock();
+                if progress - *last >= PROGRESS_STEP {
+                    *last = progress;
+                    progress_inner.update(&progress_id, false, |job| job.progress = progress.min(1.0));
+                }
+            },
+        )
+        .and_then(|stats| {
+            fs::rename(&partial, output).context("无法写入输出文件")?;
+            Ok(stats)
+        });
+        if result.is_err() {
+            let _ = fs::remove_file(&partial);
+        }
+
+        match result {
+            Ok(stats) => {
+                inner.update(id, true, |job| {
+                    job.status = JobStatus::Completed;
+                    job.progress = 1.0;
+                    job.error = None;
+                    job.finished_at = Some(now());
+                    job.processed_bytes = stats.processed_bytes;
+                    job.entity_count = stats.entities.len();
+                });
+                return;
+            }
+            Err(e) if e.is::<Cancelled>() || cancel.load(Ordering::Relaxed) => {
+                inner.update(id, true, |job| {
+                    job.status = JobStatus::Cancelled;
+                    job.finished_at = Some(now());
+                });
+                return;
+            }
+            Err(e) => {
+                let message = format!("{:#}", e);
+                let retry = job.attempts <= job.max_retries;
+                warn!("⚠️ 文件任务 {} 第 {} 次执行失败: {}", id, job.attempts, message);
+                inner.update(id, true, |job| {
+                    job.error = Some(message);
+                    if !retry {
+                        job.status = JobStatus::Failed;
+                        job.finished_at = Some(now());
+                    }
+                });
+                if !retry {
+                    return;
+                }
+                std::thread::sleep(RETRY_BACKOFF * job.attempts);
+                if cancel.load(Ordering::Relaxed) {
+                    inner.update(id, true, |job| {
+                        job.status = JobStatus::Cancelled;
+                        job.finished_at = Some(now());
+                    });
+                    return;
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::config::AppSettings;
+    use crate::core::file_options::ProcessOptions;
+    use crate::core::hybrid_engine::HybridEngine;
+    use crate::core::rules::Rule;
+    use parking_lot::RwLock;
+    use std::time::Instant;
+
+    fn temp_dir(tag: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("safemask_jobs_{}_{}", tag, uuid::Uuid::new_v4()));
+        fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    fn engine() -> SharedEngine {
+        let rules = vec![Rule {
+            name: "email".to_string(),
+            pattern: r"[a-zA-Z0-9._%+-]+@[a-zA-Z0-9.-]+\.[a-zA-Z]{2,}".to_string(),
+            mask: "<EMAIL>".to_string(),
+            priority: 0,
+            enabled: true,
+            is_custom: false,
+            case_insensitive: false,
+            whole_word: false,
+            unicode_boundary: false,
+            group: String::new(),
+        }];
+        Arc::new(RwLock::new(Arc::new(HybridEngine::from_rules(rules))))
+    }
+
+    fn settings() -> SharedSettings {
+        Arc::new(RwLock::new(AppSettings::default()))
+    }
+
+    fn wait_finished(manager: &JobManager, id: &str) -> Job {
+        let deadline = Instant::now() + Duration::from_secs(10);
+        loop {
+            let job = manager.get(id).unwrap();
+            if job.status.is_finished() || Instant::now() > deadline {
+                return job;
+            }
+            std::thread::sleep(Duration::from_millis(10));
+        }
+    }
+
+    #[test]
+    fn test_job_completes_and_persists() {
+        let dir = temp_dir("run");
+        let input = dir.join("in.log");
+        fs::write(&input, "mail a@example.com\n").unwrap();
+        let store = dir.join("jobs.json");
+
+        let manager = JobManager::start(store.clone(), engine(), settings(), 1, |_| {});
+        let job = manager.submit(&input, &dir.join("out.log"), 0);
+        let job = wait_finished(&manager, &job.id);
+        assert_eq!(job.status, JobStatus::Completed);
+        assert_eq!(job.entity_count, 1);
+        assert_eq!(fs::read_to_string(dir.join("out.log")).unwrap(), "mail <EMAIL>\n");
+
+        let saved = load_jobs(&store);
+        assert_eq!(saved.len(), 1);
+        assert_eq!(saved[0].status, JobStatus::Completed);
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_retry_requeue_and_restart_recovery() {
+        let dir = temp_dir("retry");
+        let store = dir.join("jobs.json");
+        let input = dir.join("missing.log");
+
+        let manager = JobManager::start(store.clone(), engine(), settings(), 1, |_| {});
+        let job = manager.submit(&input, &dir.join("out.log"), 1);
+        let job = wait_finished(&manager, &job.id);
+        assert_eq!(job.status, JobStatus::Failed);
+        assert_eq!(job.attempts, 2);
+        assert!(job.error.is_some());
+        assert!(manager.cancel(&job.id).is_err());
+
+        // 源文件补上后手动重新入队
+        fs::write(&input, "b@example.com").unwrap();
+        let requeued = manager.requeue(&job.id).unwrap();
+        assert_eq!(requeued.attempts, 0);
+        assert_eq!(wait_finished(&manager, &job.id).status, JobStatus::Completed);
+
+        // 模拟上次退出时仍在运行的任务：重启后自动重新入队
+        let mut saved = load_jobs(&store);
+        saved[0].status = JobStatus::Running;
+        fs::write(&store, serde_json::to_string(&saved).unwrap()).unwrap();
+        let restarted = JobManager::start(store.clone(), engine(), settings(), 1, |_| {});
+        assert_eq!(wait_finished(&restarted, &job.id).status, JobStatus::Completed);
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_failed_attempt_keeps_existing_output() {
+        let dir = temp_dir("fail");
+        let input = dir.join("broken.docx");
+        fs::write(&input, "not a zip archive").unwrap();
+        let output = dir.join("out.docx");
+        fs::write(&output, "previous").unwrap();
+
+        let manager = JobManager::start(dir.join("jobs.json"), engine(), settings(), 1, |_| {});
+        let job = manager.submit(&input, &output, 0);
+        assert_eq!(wait_finished(&manager, &job.id).status, JobStatus::Failed);
+        assert_eq!(fs::read_to_string(&output).unwrap(), "previous");
+        assert!(!partial_output_path(&output, &job.id).exists());
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_cancel_queued_then_requeue() {
+        let dir = temp_dir("cancel");
+        let input = dir.join("in.log");
+        fs::write(&input, "d@example.com").unwrap();
+
+        // 尚未启动工作线程：任务必然在排队
+        let manager = JobManager::new(dir.join("jobs.json"), engine(), settings(), |_| {});
+        let job = manager.submit(&input, &dir.join("out.log"), 0);
+        assert_eq!(manager.cancel(&job.id).unwrap().status, JobStatus::Cancelled);
+        assert!(manager.inner.table.lock().queue.is_empty());
+
+        manager.requeue(&job.id).unwrap();
+        manager.spawn_workers(1);
+        assert_eq!(wait_finished(&manager, &job.id).status, JobStatus::Completed);
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_workers_exit_when_manager_dropped() {
+        let dir = temp_dir("shutdown");
+        let manager = JobManager::start(dir.join("jobs.json"), engine(), settings(), 2, |_| {});
+        let inner = Arc::downgrade(&manager.inner);
+        let clone = manager.clone();
+        drop(manager);
+        // 仍有克隆存活时工作线程继续运行
+        std::thread::sleep(Duration::from_millis(50));
+        assert_eq!(inner.strong_count(), 4);
+
+        drop(clone);
+        let deadline = Instant::now() + Duration::from_secs(10);
+        while inner.strong_count() > 0 && Instant::now() < deadline {
+            std::thread::sleep(Duration::from_millis(10));
+        }
+        assert_eq!(inner.strong_count(), 0);
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_cancelled_processing_removes_output() {
+        let dir = temp_dir("abort");
+        let input = dir.join("in.log");
+        fs::write(&input, "e@example.com\n".repeat(1000)).unwrap();
+        let output = dir.join("out.log");
+
+        let engine = engine().read().clone();
+        let err = processor::process_file_cancellable(
+            &input,
+            &output,
+            &engine,
+            &ProcessOptions::default(),
+            Arc::new(AtomicBool::new(true)),
+            |_| {},
+        )
+        .unwrap_err();
+        assert!(err.is::<Cancelled>());
+        assert!(!output.exists());
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
@@ -0,0 +1,11 @@
+//! 后台文件脱敏任务队列
+//!
+//! - 每个任务有独立 ID，按提交顺序排队，由固定数量的工作线程并发执行（并发上限）
+//! - 每次状态变化与进度推进都通过回调通知（Tauri 侧转发为 `job-update` 事件）
+//! - 运行中的任务可取消：取消标记在文本块 / 文档条目之间检查
+//! - 失败任务按 `max_retries` 自动重试，也可手动重新入队
+//! - 任务历史持久化到 JSON 文件；重启时未完成的任务（排队 / 运行中）重新入队
+
+mod manager;
+
+pub use manager::{Job, JobManager, JobStatus, DEFAULT_CONCURRENCY};
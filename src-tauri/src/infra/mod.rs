@@ -2,4 +2,5 @@ pub mod config;
 pub mod fs;
 pub mod clipboard;
 pub mod ai;
-pub mod record_writer;
\ No newline at end of file
+pub mod record_writer;
+pub mod jobs;
\ No newline at end of file
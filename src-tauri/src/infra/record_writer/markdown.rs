@@ -195,11 +195,23 @@ fn format_record(item: &MaskHistoryItem, record_num: u32) -> String {
 
     if !item.entities.is_empty() {
         s.push_str("### 识别实体\n");
-        s.push_str("| 类型 | 起始 | 结束 | 脱敏值 |\n");
-        s.push_str("|------|------|------|--------|\n");
+        // 文件记录带行列号
+        let positioned = item.entities.iter().any(|e| e.line.is_some());
+        if positioned {
+            s.push_str("| 类型 | 行:列 | 起始 | 结束 | 脱敏值 |\n");
+            s.push_str("|------|-------|------|------|--------|\n");
+        } else {
+            s.push_str("| 类型 | 起始 | 结束 | 脱敏值 |\n");
+            s.push_str("|------|------|------|--------|\n");
+        }
         for e in &item.entities {
-            s.push_str(&format!("| {} | {} | {} | {} |\n",
-                e.entity_type, e.start, e.end, e.mask_label));
+            if positioned {
+                s.push_str(&format!("| {} | {}:{} | {} | {} | {} |\n",
+                    e.entity_type, e.line.unwrap_or(0), e.column.unwrap_or(0), e.start, e.end, e.mask_label));
+            } else {
+                s.push_str(&format!("| {} | {} | {} | {} |\n",
+                    e.entity_type, e.start, e.end, e.mask_label));
+            }
         }
         s.push('\n');
     }
@@ -239,6 +251,9 @@ mod tests {
                 end: 17,
                 entity_type: "PHONE".into(),
                 mask_label: "138****8000".into(),
+                line: None,
+                column: None,
+                field: None,
             }],
         );
         let result = format_record(&item, 1);
@@ -249,6 +264,27 @@ mod tests {
         assert!(result.contains("PHONE"));
         assert!(result.contains("SHADOW"));
         assert!(result.contains("---"));
+        assert!(!result.contains("行:列"));
+    }
+
+    #[test]
+    fn test_format_file_record_with_positions() {
+        let item = make_item(
+            "a\nmail a@b.cn",
+            "a\nmail <EMAIL>",
+            "FILE",
+            vec![EntitySpanBrief {
+                start: 7,
+                end: 13,
+                entity_type: "EMAIL".into(),
+                mask_label: "<EMAIL>".into(),
+                line: Some(2),
+                column: Some(6),
+                field: None,
+            }],
+        );
+        let result = format_record(&item, 1);
+        assert!(result.contains("| EMAIL | 2:6 | 7 | 13 | <EMAIL> |"));
     }
 
     #[test]
@@ -73,6 +73,16 @@ fn main() {
             api::system::get_all_detailed_rules,
             api::system::save_rule_api,
             api::system::delete_rule_api,
+            api::system::get_allowlist,                 // 白名单 CRUD
+            api::system::save_allowlist_entry,
+            api::system::delete_allowlist_entry,
+            api::system::build_edm_index,               // EDM 精确匹配索引
+            api::system::get_edm_index_info,
+            api::system::delete_edm_index,
+            api::system::get_dictionaries,              // 外部大字典（FST）
+            api::system::save_dictionary,
+            api::system::delete_dictionary,
+            api::system::rebuild_dictionaries,
             api::system::get_mask_history,
             api::system::clear_history_cmd,
             api::system::toggle_monitor,
@@ -80,6 +90,14 @@ fn main() {
             api::system::get_app_info,
             api::text::mask_text,
             api::files::process_file_gui,
+            api::files::process_directory_gui,
+            api::files::scan_files_gui,                 // 仅扫描（发现报告）
+            api::files::scan_directory_gui,             // 仅扫描目录
+            api::files::export_scan_report,
+            api::files::enqueue_file_job,               // 后台文件任务队列
+            api::files::list_jobs,
+            api::files::cancel_job,
+            api::files::requeue_job,
             api::system::toggle_always_on_top,
             api::system::update_app_settings,
             api::system::get_app_settings,
@@ -242,10 +260,19 @@ fn init_app_state(handle: &AppHandle) -> Result<(), Box<dyn std::error::Error>>
     let custom_dir = ConfigLoader::get_custom_storage_path(handle);
     let device_id = crate::core::download_auth::get_or_create_device_id(&custom_dir);
     info!("🔑 设备 ID: {}", device_id);
+    let fingerprint_key = crate::infra::fs::sarif::load_or_create_fingerprint_key(&custom_dir);
 
     // 加载并编译规则引擎
     let rules = ConfigLoader::load_all_rules(handle);
     let mut engine = HybridEngine::from_rules(rules);
+    // 加载白名单（冲突解决后豁免）
+    engine.set_allowlist(crate::core::allowlist::Allowlist::new(&ConfigLoader::load_allowlist(handle)));
+    // 加载 EDM 精确匹配索引（如已构建）
+    if let Some(index) = ConfigLoader::load_edm_index(handle) {
+        engine.set_edm_index(Arc::new(index));
+    }
+    // 加载外部大字典（源词表未变化时直接内存映射已编译的 FST）
+    engine.set_dictionaries(ConfigLoader::prepare_dictionaries(handle, false).0);
 
     // 🚀 启用 AI 引擎（如果模型可用）
     // 尝试多个可能的模型目录路径
@@ -254,18 +281,43 @@ fn init_app_state(handle: &AppHandle) -> Result<(), Box<dyn std::error::Error>>
     engine.enable_ai_engine(&models_dir);
     // 同步脱敏标签包裹样式
     engine.set_wrapper_style(&settings.mask_wrapper_style);
+    // 同步网络地址识别的保留地址过滤
+    engine.set_network_filter(
+        settings.ip_skip_private,
+        settings.ip_skip_loopback,
+        settings.ip_skip_documentation,
+    );
+    engine.set_connection_string_parts(settings.conn_mask_user, settings.conn_mask_host);
+    // 按需加载国际证件识别包
+    if settings.international_pack_enabled {
+        engine.enable_international_pack();
+    }
     info!("🤖 AI 引擎初始化完成，状态: {:?}", engine.ai_status());
 
     // 注意：AI 模型加载是异步的，不会阻塞启动
     // 如果模型加载失败，规则引擎仍然可以正常工作
 
-    let engine = Arc::new(engine);
+    let engine = Arc::new(RwLock::new(Arc::new(engine)));
+    let settings = Arc::new(RwLock::new(settings));
+
+    // 启动后台文件任务队列（任务历史与引擎共用 custom 目录，上次未完成的任务自动恢复）
+    let job_events = handle.clone();
+    let jobs = crate::infra::jobs::JobManager::start(
+        custom_dir.join("jobs.json"),
+        engine.clone(),
+        settings.clone(),
+        crate::infra::jobs::DEFAULT_CONCURRENCY,
+        move |job| {
+            let _ = job_events.emit("job-update", job);
+        },
+    );
 
     // 构建核心状态机（持久化 models_dir，供 reload_engine_internal 重建 AI）
     let app_state = AppState {
-        engine: Arc::new(RwLock::new(engine)),
-        settings: Arc::new(RwLock::new(settings)),
+        engine,
+        settings,
         device_id: Arc::new(device_id),
+        fingerprint_key: Arc::new(fingerprint_key),
         shadow_store: Arc::new(RwLock::new(crate::common::state::ShadowClipboard::default())),
         is_magic_pasting: Arc::new(AtomicBool::new(false)),
         is_monitor_on: Arc::new(Mutex::new(true)),
@@ -274,6 +326,8 @@ fn init_app_state(handle: &AppHandle) -> Result<(), Box<dyn std::error::Error>>
         is_recording_mode: Arc::new(AtomicBool::new(false)),
         models_dir: models_dir.clone(),
         record_writer: Arc::new(RwLock::new(None)),
+        jobs,
+        last_scan_report: Arc::new(Mutex::new(None)),
     };
 
     // 托管状态
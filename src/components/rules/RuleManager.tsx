@@ -20,6 +20,9 @@ interface FormState {
   priority: number;
   is_custom: boolean;
   enabled: boolean;
+  case_insensitive: boolean;
+  whole_word: boolean;
+  unicode_boundary: boolean;
 }
 
 interface ValidationErrors {
@@ -38,6 +41,9 @@ const EMPTY_FORM: FormState = {
   priority: 10,
   is_custom: true,
   enabled: true,
+  case_insensitive: false,
+  whole_word: false,
+  unicode_boundary: false,
 };
 
 /** Convert a Rule into the form state shape (strip bracket wrappers for editing). */
@@ -49,6 +55,9 @@ function ruleToForm(r: Rule): FormState {
     priority: r.priority,
     is_custom: r.is_custom,
     enabled: r.enabled,
+    case_insensitive: r.case_insensitive ?? false,
+    whole_word: r.whole_word ?? false,
+    unicode_boundary: r.unicode_boundary ?? false,
   };
 }
 
@@ -61,6 +70,9 @@ function formToRule(form: FormState, isCustom: boolean): Rule {
     priority: Number(form.priority),
     is_custom: isCustom,
     enabled: form.enabled,
+    case_insensitive: form.case_insensitive,
+    whole_word: form.whole_word,
+    unicode_boundary: form.unicode_boundary,
   };
 }
 
@@ -242,6 +254,9 @@ export default function RuleManager() {
       priority: selectedRule.priority,
       is_custom: true,
       enabled: true,
+      case_insensitive: selectedRule.case_insensitive ?? false,
+      whole_word: selectedRule.whole_word ?? false,
+      unicode_boundary: selectedRule.unicode_boundary ?? false,
     });
     setSelectedName(null);
     setValidationErrors({});
@@ -928,6 +943,32 @@ export default function RuleManager() {
           )}
           {renderFormField("PRIORITY", "priority", "number", "", isSystemRule ?? false)}
 
+          {/* Match options */}
+          <div className="flex flex-wrap gap-4 px-1">
+            {([
+              ["case_insensitive", "忽略大小写"],
+              ["whole_word", "整词匹配"],
+              ["unicode_boundary", "Unicode/CJK 边界"],
+            ] as const).map(([field, label]) => (
+              <label
+                key={field}
+                className={cn(
+                  "flex items-center gap-2 text-xs font-medium",
+                  isSystemRule ? "text-zinc-600 cursor-not-allowed" : "text-zinc-400 cursor-pointer",
+                )}
+              >
+                <input
+                  type="checkbox"
+                  checked={form[field]}
+                  disabled={isSystemRule ?? false}
+                  onChange={(e) => updateForm({ [field]: e.target.checked })}
+                  className="accent-amber-500"
+                />
+                {label}
+              </label>
+            ))}
+          </div>
+
           {/* System rule warning */}
           {isSystemRule && (
             <div className="bg-amber-900/10 border border-amber-500/20 p-4 rounded-2xl flex gap-4 animate-[fadeSlideIn_0.3s_ease_forwards]">
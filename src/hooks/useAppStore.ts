@@ -66,6 +66,22 @@ export const useAppStore = create<AppState>()((set, get) => ({
     model_download_urls: [],
     record_writer_enabled: false,
     mask_wrapper_style: "angle",
+    ip_skip_private: false,
+    ip_skip_loopback: false,
+    ip_skip_documentation: false,
+    conn_mask_user: true,
+    conn_mask_host: false,
+    international_pack_enabled: false,
+    chunk_overlap_lines: 32,
+    text_output_utf8: false,
+    csv_options: { policies: [], infer_columns: true, sample_rows: 200 },
+    structured_options: {
+      key_rules: ['password', 'passwd', 'pwd', 'secret', 'token', 'api_key', 'private_key', 'access_key', 'credential', 'authorization'],
+      paths: [],
+    },
+    key_value_options: {
+      sensitive_keys: ['*PASSWORD', '*PASSWD', '*_PWD', '*SECRET', '*TOKEN', '*_KEY', '*APIKEY', 'DSN', '*_DSN'],
+    },
     // 从 localStorage 同步读取，避免首帧 store 值与 DOM (由 main.tsx 预应用) 不一致；
     // 后续 bootstrap 从 Rust 加载的 settings 会覆盖此值。
     theme: loadPersistedTheme(),
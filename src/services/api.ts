@@ -13,6 +13,66 @@ export interface Rule {
   priority: number;
   is_custom: boolean;
   enabled: boolean;
+  /** 忽略大小写 */
+  case_insensitive?: boolean;
+  /** 整词匹配（ASCII 词边界） */
+  whole_word?: boolean;
+  /** Unicode / CJK 感知的词边界（隐含整词匹配） */
+  unicode_boundary?: boolean;
+  /** 所属规则组（加载时由规则文件填充） */
+  group?: string;
+}
+
+/** 白名单条目（对应 custom/allowlist.yaml） */
+export interface AllowlistEntry {
+  /** 条目 ID，新建时留空由后端生成 */
+  id: string;
+  /** 匹配方式 */
+  kind: "exact" | "regex" | "entity_type";
+  /** 精确值或正则表达式 */
+  value: string;
+  /** 作用的实体类型标签（如 "EMAIL"），为空表示不限 */
+  entity_types: string[];
+  /** 过期日期 YYYY-MM-DD（含当天），null 表示永久有效 */
+  expires_on: string | null;
+  /** 豁免原因 */
+  reason: string;
+  enabled: boolean;
+}
+
+/** EDM 索引列选择（entity_type 为空时按列名推断） */
+export interface EdmColumnSpec {
+  column: string;
+  entity_type?: string | null;
+}
+
+/** EDM 索引信息 */
+export interface EdmIndexInfo {
+  exists: boolean;
+  entries: number;
+  columns: { name: string; entity_type: string }[];
+}
+
+/** 外部大字典登记（对应 custom/dictionaries.yaml） */
+export interface DictionarySpec {
+  name: string;
+  /** 词表路径，相对路径以 custom 目录为基准 */
+  source: string;
+  entity_type: string;
+  mask?: string | null;
+  case_insensitive: boolean;
+  whole_word: boolean;
+  /** 按 Unicode 词边界整词匹配 */
+  unicode_boundary?: boolean;
+  enabled: boolean;
+}
+
+/** 字典编译结果 */
+export interface DictionaryBuildReport {
+  name: string;
+  rebuilt: boolean;
+  terms: number;
+  error: string | null;
 }
 
 /** 实体跨度摘要（IPC 精简版，用于前端高亮） */
@@ -25,6 +85,12 @@ export interface EntitySpanBrief {
   entity_type: string;
   /** 脱敏标签 "<EMAIL>" | "[PERSON]" | ... */
   mask_label: string;
+  /** 行号（仅文件处理记录，此时 start/end 为全文件偏移） */
+  line?: number;
+  /** 列号（按字符计） */
+  column?: number;
+  /** CSV / TSV 字段序号（从 1 开始） */
+  field?: number;
 }
 
 /** 历史记录条目 (包含宇宙模式标记) */
@@ -38,6 +104,38 @@ export interface HistoryItem {
   entities: EntitySpanBrief[];
 }
 
+/** CSV / TSV 单列策略：`column` 为表头名或列序号（从 1 开始） */
+export interface CsvColumnPolicy {
+  column: string | number;
+  action: 'mask' | 'detect' | 'skip';
+  /** `mask` 的脱敏策略：replace / partial_mask / hash / redact / token / template，默认 replace */
+  strategy?: string;
+  /** `mask` 的实体类型（如 `phone`），为空时取推断类型或表头名 */
+  entity_type?: string;
+}
+
+export interface CsvOptions {
+  policies: CsvColumnPolicy[];
+  /** 根据抽样行推断整列实体类型 */
+  infer_columns: boolean;
+  /** 列推断抽样的数据行数 */
+  sample_rows: number;
+}
+
+/** JSON / JSON Lines / YAML 结构化脱敏选项 */
+export interface StructuredOptions {
+  /** 键名规则词：键名（忽略大小写与 `_` `-`）以规则词结尾时其值整体脱敏 */
+  key_rules: string[];
+  /** JSONPath 选择器，如 `$.user.*.email`、`$..token` */
+  paths: string[];
+}
+
+/** 键值配置（.env / .properties / .ini / TOML）脱敏选项 */
+export interface KeyValueOptions {
+  /** 敏感键 glob 模式（忽略大小写，`.` `-` 视为 `_`），命中的键其值整体脱敏 */
+  sensitive_keys: string[];
+}
+
 /** 全局应用设置 (影子模式核心配置) */
 export interface AppSettings {
   magic_paste_shortcut: string;
@@ -49,6 +147,28 @@ export interface AppSettings {
   record_writer_enabled: boolean;
   /** 脱敏标签包裹样式: "angle" | "square" */
   mask_wrapper_style: string;
+  /** 网络地址识别：跳过私有/链路本地地址 */
+  ip_skip_private: boolean;
+  /** 网络地址识别：跳过回环与未指定地址 */
+  ip_skip_loopback: boolean;
+  /** 网络地址识别：跳过文档示例地址 */
+  ip_skip_documentation: boolean;
+  /** 连接串：除密码外是否同时脱敏用户名 */
+  conn_mask_user: boolean;
+  /** 连接串：是否同时脱敏主机名 */
+  conn_mask_host: boolean;
+  /** 是否加载国际证件识别包（SSN / NINO / Steuer-ID / NIR / 欧盟增值税号等） */
+  international_pack_enabled: boolean;
+  /** 大文件分块处理的块间重叠行数（跨行实体与上下文识别），0 表示关闭 */
+  chunk_overlap_lines: number;
+  /** 非 UTF-8 文本（GBK / UTF-16 / Latin-1）统一输出为 UTF-8，默认按原编码写回 */
+  text_output_utf8: boolean;
+  /** CSV / TSV 列策略与整列实体推断 */
+  csv_options: CsvOptions;
+  /** JSON / YAML 键名规则与 JSONPath 选择器 */
+  structured_options: StructuredOptions;
+  /** 键值配置的敏感键模式 */
+  key_value_options: KeyValueOptions;
   /**
    * UI 主题标识符。IPC 边界保持宽泛的 `string` 类型以兼容旧版配置文件；
    * 应用层通过 `normalizeThemeId()` 规范化为合法的 `ThemeId`。
@@ -108,6 +228,102 @@ export interface ProcessResponse {
   throughput: string;
 }
 
+/** 扫描命中 */
+export interface Finding {
+  file: string;
+  /** 文件内位置补充（如 Excel 工作表名） */
+  location: string | null;
+  line: number;
+  column: number;
+  entity_type: string;
+  rule: string;
+  /** 打码后的预览值 */
+  preview: string;
+  mask: string;
+  /** 字节偏移与长度 */
+  offset: number;
+  length: number;
+}
+
+/** 扫描报告 */
+export interface ScanReport {
+  /** 目录扫描的根目录 */
+  root: string | null;
+  files: string[];
+  bytes_scanned: number;
+  lines_scanned: number;
+  total_findings: number;
+  counts_by_type: Record<string, number>;
+  counts_by_rule: Record<string, number>;
+  /** 前 N 条命中 */
+  samples: Finding[];
+  max_samples: number;
+  duration_secs: number;
+}
+
+/** 后台文件任务（进度与状态通过 `job-update` 事件推送） */
+export interface Job {
+  id: string;
+  input_path: string;
+  output_path: string;
+  status: "queued" | "running" | "completed" | "failed" | "cancelled";
+  /** 0 ~ 1 */
+  progress: number;
+  attempts: number;
+  max_retries: number;
+  error: string | null;
+  created_at: string;
+  started_at: string | null;
+  finished_at: string | null;
+  processed_bytes: number;
+  entity_count: number;
+}
+
+/** 目录批量处理选项 */
+export interface BatchOptions {
+  /** 包含的 glob（相对输入目录，为空表示全部） */
+  include?: string[];
+  /** 排除的 glob（对文件和目录都生效） */
+  exclude?: string[];
+  /** 单个文件大小上限（字节） */
+  max_file_size?: number | null;
+  skip_hidden?: boolean;
+  skip_binary?: boolean;
+}
+
+/** 批量处理中单个文件的结果 */
+export interface BatchFileReport {
+  relative_path: string;
+  output_path: string | null;
+  status: "processed" | "skipped" | "failed";
+  reason: string | null;
+  bytes: number;
+  entity_count: number;
+  duration_secs: number;
+}
+
+/** `batch-progress` 事件负载 */
+export interface BatchProgress {
+  file: BatchFileReport;
+  files_done: number;
+  files_total: number;
+  bytes_done: number;
+  bytes_total: number;
+}
+
+/** 批量处理汇总 */
+export interface BatchSummary {
+  output_root: string;
+  files_total: number;
+  processed: number;
+  skipped: number;
+  failed: number;
+  bytes_processed: number;
+  entity_counts: Record<string, number>;
+  duration_secs: number;
+  files: BatchFileReport[];
+}
+
 
 /** 规则导入单项结果 */
 export interface ImportRuleItemResult {
@@ -172,6 +388,62 @@ export const MaskAPI = {
     return await invoke("delete_rule_api", { name });
   },
 
+  // === 白名单 ===
+
+  /** 获取全部白名单条目 */
+  async getAllowlist(): Promise<AllowlistEntry[]> {
+    return await invoke("get_allowlist");
+  },
+
+  /** 新增或更新白名单条目，返回条目 ID */
+  async saveAllowlistEntry(entry: AllowlistEntry): Promise<string> {
+    return await invoke("save_allowlist_entry", { entry });
+  },
+
+  /** 删除白名单条目 */
+  async deleteAllowlistEntry(id: string): Promise<string> {
+    return await invoke("delete_allowlist_entry", { id });
+  },
+
+  // === EDM 精确匹配 ===
+
+  /** 从 CSV 构建 EDM 加盐哈希索引（columns 为空时索引全部列） */
+  async buildEdmIndex(csvPath: string, columns: EdmColumnSpec[]): Promise<EdmIndexInfo> {
+    return await invoke("build_edm_index", { csvPath, columns });
+  },
+
+  /** 获取 EDM 索引信息 */
+  async getEdmIndexInfo(): Promise<EdmIndexInfo> {
+    return await invoke("get_edm_index_info");
+  },
+
+  /** 删除 EDM 索引 */
+  async deleteEdmIndex(): Promise<void> {
+    await invoke("delete_edm_index");
+  },
+
+  // === 外部大字典 ===
+
+  /** 获取外部字典登记列表 */
+  async getDictionaries(): Promise<DictionarySpec[]> {
+    return await invoke("get_dictionaries");
+  },
+
+  /** 保存外部字典登记 */
+  async saveDictionary(spec: DictionarySpec): Promise<string> {
+    return await invoke("save_dictionary", { spec });
+  },
+
+  /** 删除外部字典登记 */
+  async deleteDictionary(name: string): Promise<string> {
+    return await invoke("delete_dictionary", { name });
+  },
+
+  /** 重建外部字典（force 为 false 时仅重建有变化的词表） */
+  async rebuildDictionaries(force: boolean): Promise<DictionaryBuildReport[]> {
+    return await invoke("rebuild_dictionaries", { force });
+  },
+
   /** 从本地 YAML 文件批量导入自定义规则 */
   async importCustomRules(paths: string[]): Promise<ImportRulesReport> {
     return await invoke("import_custom_rules", { paths });
@@ -230,6 +502,54 @@ export const MaskAPI = {
     return await invoke("process_file_gui", { inputPath });
   },
 
+  // === 仅扫描 ===
+
+  /** 扫描文件，只生成发现报告（进度通过 `scan-progress` 事件推送） */
+  async scanFiles(inputPaths: string[], maxSamples?: number): Promise<ScanReport> {
+    return await invoke("scan_files_gui", { inputPaths, maxSamples: maxSamples ?? null });
+  },
+
+  /** 扫描目录（过滤选项同批量脱敏，进度通过 `scan-progress` 事件推送） */
+  async scanDirectory(inputDir: string, options?: BatchOptions, maxSamples?: number): Promise<ScanReport> {
+    return await invoke("scan_directory_gui", { inputDir, options: options ?? null, maxSamples: maxSamples ?? null });
+  },
+
+  /** 导出最近一次扫描的报告（SARIF 包含超出样本上限的全部命中） */
+  async exportScanReport(format: "json" | "csv" | "html" | "sarif", outputPath: string): Promise<void> {
+    return await invoke("export_scan_report", { format, outputPath });
+  },
+
+  // === 后台文件任务 ===
+
+  /** 提交后台文件脱敏任务 */
+  async enqueueFileJob(inputPath: string, maxRetries?: number): Promise<Job> {
+    return await invoke("enqueue_file_job", { inputPath, maxRetries: maxRetries ?? null });
+  },
+
+  /** 列出任务（最新在前） */
+  async listJobs(): Promise<Job[]> {
+    return await invoke("list_jobs");
+  },
+
+  /** 取消任务 */
+  async cancelJob(id: string): Promise<Job> {
+    return await invoke("cancel_job", { id });
+  },
+
+  /** 重新入队 */
+  async requeueJob(id: string): Promise<Job> {
+    return await invoke("requeue_job", { id });
+  },
+
+  /** 批量脱敏目录（进度通过 `batch-progress` 事件推送） */
+  async processDirectory(
+    inputDir: string,
+    outputDir?: string | null,
+    options?: BatchOptions,
+  ): Promise<BatchSummary> {
+    return await invoke("process_directory_gui", { inputDir, outputDir: outputDir ?? null, options: options ?? null });
+  },
+
   /** 打开文件所在目录 */
   async openFolder(path: string): Promise<void> {
     const { revealItemInDir } = await import('@tauri-apps/plugin-opener');